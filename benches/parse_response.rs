@@ -0,0 +1,92 @@
+//! Compares the performance of the `serde_json` and `simd-json` response
+//! parsing backends on large recorded payloads, as well as eager and lazy
+//! decoding of `/block_results` responses.
+//!
+//! Run with `cargo bench --features simd-json` to include the `simd-json`
+//! backend.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use tendermint_rpc::endpoint::{block_results, genesis, tx_search};
+use tendermint_rpc::response::Wrapper;
+use tendermint_rpc::Response;
+
+fn read_json_fixture(name: &str) -> String {
+    std::fs::read_to_string(format!("tests/support/{}.json", name)).unwrap()
+}
+
+// Repeats the transactions of a recorded `/tx_search` response, to obtain a
+// payload comparable in size to a full page of results.
+fn large_tx_search() -> String {
+    let mut json: serde_json::Value =
+        serde_json::from_str(&read_json_fixture("tx_search_with_prove")).unwrap();
+    let txs = json["result"]["txs"].as_array().unwrap().clone();
+    let repeated: Vec<_> = txs.iter().cycle().take(100).cloned().collect();
+    json["result"]["total_count"] = repeated.len().to_string().into();
+    json["result"]["txs"] = repeated.into();
+    json.to_string()
+}
+
+// Repeats the transaction results of a recorded `/block_results` response, to
+// obtain a payload comparable in size to that of a busy block.
+fn large_block_results() -> String {
+    let mut json: serde_json::Value =
+        serde_json::from_str(&read_json_fixture("block_results")).unwrap();
+    let txs_results = json["result"]["txs_results"].as_array().unwrap().clone();
+    let repeated: Vec<_> = txs_results.iter().cycle().take(5000).cloned().collect();
+    json["result"]["txs_results"] = repeated.into();
+    json.to_string()
+}
+
+// Sums the gas used by all transactions, either decoding all of the results up
+// front or one at a time.
+fn bench_block_results(c: &mut Criterion, json: &str) {
+    let mut group = c.benchmark_group("block_results");
+    group.bench_function("eager", |b| {
+        b.iter(|| {
+            let response = block_results::Response::from_string(json).unwrap();
+            response
+                .txs_results
+                .unwrap()
+                .iter()
+                .map(|tx| tx.gas_used.value())
+                .sum::<u64>()
+        })
+    });
+    group.bench_function("lazy", |b| {
+        b.iter(|| {
+            let response = block_results::LazyResponse::from_string(json).unwrap();
+            response
+                .txs_results()
+                .unwrap()
+                .map(|tx| tx.unwrap().gas_used.value())
+                .sum::<u64>()
+        })
+    });
+    group.finish();
+}
+
+fn bench_backends<R: Response>(c: &mut Criterion, name: &str, json: &str) {
+    let mut group = c.benchmark_group(name);
+    group.bench_function("serde_json", |b| {
+        b.iter(|| serde_json::from_str::<Wrapper<R>>(json).unwrap())
+    });
+    #[cfg(feature = "simd-json")]
+    group.bench_function("simd_json", |b| {
+        b.iter(|| {
+            let mut bytes = json.as_bytes().to_vec();
+            simd_json::serde::from_slice::<Wrapper<R>>(&mut bytes).unwrap()
+        })
+    });
+    // Whichever backend is enabled, including our own overheads.
+    group.bench_function("from_string", |b| b.iter(|| R::from_string(json).unwrap()));
+    group.finish();
+}
+
+fn parse_response(c: &mut Criterion) {
+    bench_backends::<genesis::Response>(c, "genesis", &read_json_fixture("genesis"));
+    bench_backends::<tx_search::Response>(c, "tx_search", &large_tx_search());
+    bench_block_results(c, &large_block_results());
+}
+
+criterion_group!(benches, parse_response);
+criterion_main!(benches);
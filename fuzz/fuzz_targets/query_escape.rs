@@ -0,0 +1,37 @@
+//! Round-trips arbitrary strings through the escaping of string operands in
+//! queries and back, ensuring that a value can never break out of its quotes.
+//!
+//! Run with `cargo fuzz run query_escape` from the crate's directory.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use tendermint_rpc::query::Operand;
+
+fuzz_target!(|data: &[u8]| {
+    let value = match std::str::from_utf8(data) {
+        Ok(value) => value,
+        Err(_) => return,
+    };
+
+    // Parsing arbitrary input must never panic.
+    let _ = value.parse::<Operand>();
+
+    let rendered = Operand::from(value).to_string();
+
+    // The only unescaped quotes must be the opening and closing ones.
+    assert!(rendered.starts_with('\''), "{}", rendered);
+    let mut chars = rendered.char_indices().skip(1);
+    while let Some((i, ch)) = chars.next() {
+        match ch {
+            '\\' => {
+                chars.next();
+            }
+            '\'' => assert_eq!(i + 1, rendered.len(), "{}", rendered),
+            _ => (),
+        }
+    }
+
+    let parsed = rendered.parse::<Operand>().unwrap();
+    assert_eq!(Operand::String(value.to_owned()), parsed);
+});
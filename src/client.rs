@@ -1,31 +1,137 @@
 //! Tendermint RPC client.
 
+mod app_hash;
+pub use app_hash::AppHashTransition;
+mod cached;
+pub use cached::CachingClient;
+mod finalized;
+pub use finalized::FinalizedQueryClient;
+mod follower;
+pub use follower::{ChainFollower, FollowEvent};
+mod health_gated;
+pub use health_gated::HealthGatedClient;
+mod light_block;
+pub use light_block::LightBlock;
+mod multi;
+pub use multi::MultiClient;
+mod node_snapshot;
+pub use node_snapshot::NodeSnapshot;
+mod pinned;
+pub use pinned::PinnedHeightClient;
 mod subscription;
 pub use subscription::{Subscription, SubscriptionClient};
+#[cfg(any(test, feature = "timed-client"))]
+mod timed;
+#[cfg(any(test, feature = "timed-client"))]
+pub use timed::{RequestDuration, TimedClient, DEFAULT_DURATION_CAPACITY};
 pub mod sync;
 
 mod transport;
 pub use transport::mock::{MockClient, MockRequestMatcher, MockRequestMethodMatcher};
 
+#[cfg(feature = "wasm-client")]
+pub use transport::fetch::FetchClient;
 #[cfg(feature = "http-client")]
-pub use transport::http::{HttpClient, HttpClientUrl};
+pub use transport::http::{HttpClient, HttpClientBuilder};
+#[cfg(any(feature = "http-client", feature = "wasm-client"))]
+pub use transport::http_url::HttpClientUrl;
+#[cfg(feature = "http-client")]
+pub use transport::interceptor::{IncomingResponse, OutgoingRequest, RequestInterceptor};
 #[cfg(feature = "websocket-client")]
-pub use transport::websocket::{WebSocketClient, WebSocketClientDriver, WebSocketClientUrl};
+pub use transport::websocket::{
+    EventParseErrorPolicy, WebSocketClient, WebSocketClientBuilder, WebSocketClientDriver,
+    WebSocketClientUrl,
+};
 
 use crate::endpoint::validators::DEFAULT_VALIDATORS_PER_PAGE;
 use crate::endpoint::*;
-use crate::error::Error;
+use crate::error::{Error, ErrorKind};
 use crate::paging::Paging;
 use crate::query::Query;
-use crate::{Order, Result, SimpleRequest};
+use crate::{Method, Order, Result, SimpleRequest};
 use async_trait::async_trait;
+use futures::stream::{self, StreamExt};
+use serde::de::DeserializeOwned;
+use std::collections::VecDeque;
+use std::convert::TryFrom;
+use std::future::Future;
 use std::time::Duration;
 use tendermint::abci::{self, Transaction};
 use tendermint::block::Height;
 use tendermint::evidence::Evidence;
-use tendermint::Genesis;
+use tendermint::{validator, Genesis};
 use tokio::time;
 
+/// A stream returned by a [`Client`], which is `Send` unless the `unsend`
+/// feature is enabled.
+#[cfg(not(feature = "unsend"))]
+pub type ClientStream<'a, T> = futures::stream::BoxStream<'a, T>;
+
+/// A stream returned by a [`Client`], which is `Send` unless the `unsend`
+/// feature is enabled.
+#[cfg(feature = "unsend")]
+pub type ClientStream<'a, T> = futures::stream::LocalBoxStream<'a, T>;
+
+/// How frequently [`Client::wait_until_healthy`] polls the `/health` endpoint.
+const HEALTH_POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// How frequently [`Client::broadcast_tx_with_progress`] polls for the
+/// progress of a transaction.
+const TX_PROGRESS_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// The number of transactions requested per page by
+/// [`Client::tx_search_stream`], which is the most Tendermint allows.
+const TX_SEARCH_PER_PAGE: u8 = tx_search::MAX_PER_PAGE;
+
+/// Paging state for [`Client::tx_search_stream`].
+struct TxSearchState {
+    // The next page to fetch.
+    page: u32,
+    // The total number of transactions fetched so far.
+    fetched: usize,
+    // Fetched transactions not yet yielded.
+    buffer: VecDeque<tx_search::ResultTx>,
+    // Whether there are no more pages to fetch.
+    done: bool,
+}
+
+/// Wait before polling again for the progress of the given transaction,
+/// failing if doing so would pass the deadline.
+async fn wait_for_tx_progress(
+    deadline: time::Instant,
+    hash: &abci::transaction::Hash,
+    awaited: &str,
+) -> Result<()> {
+    if time::Instant::now() + TX_PROGRESS_POLL_INTERVAL > deadline {
+        return Err(Error::client_internal_error(format!(
+            "timed out waiting for transaction {} to be {}",
+            hash, awaited
+        ))
+        .with_kind(ErrorKind::Timeout));
+    }
+    time::sleep(TX_PROGRESS_POLL_INTERVAL).await;
+    Ok(())
+}
+
+fn parse_server_version(version: &str) -> Result<semver::Version> {
+    let trimmed = version.trim();
+    let trimmed = trimmed.strip_prefix('v').unwrap_or(trimmed);
+    semver::Version::parse(trimmed)
+        .map_err(|e| Error::parse_error(format!("invalid node version \"{}\": {}", version, e)))
+}
+
+/// Reject an explicit height of 0, which the node would otherwise silently
+/// interpret as the latest height.
+fn explicit_height(height: Height, latest: &str) -> Result<Height> {
+    if height.value() == 0 {
+        return Err(Error::invalid_params(&format!(
+            "height must be greater than 0 (use `{}` for the latest height)",
+            latest
+        )));
+    }
+    Ok(height)
+}
+
 /// Provides lightweight access to the Tendermint RPC. It gives access to all
 /// endpoints with the exception of the event subscription-related ones.
 ///
@@ -33,7 +139,23 @@ use tokio::time;
 /// [`SubscriptionClient`] trait.
 ///
 /// [`SubscriptionClient`]: trait.SubscriptionClient.html
-#[async_trait]
+///
+/// ### `Send` futures
+///
+/// By default, the futures returned by this trait's methods are `Send`, so
+/// that they can be spawned onto a multi-threaded executor (e.g. via
+/// [`tokio::spawn`]), which requires implementations to be `Sync`. With the
+/// `unsend` feature enabled, they are not required to be `Send` (and nor are
+/// those of [`SubscriptionClient`]), so that the trait can be implemented by
+/// clients which are not thread-safe, e.g. for use on a single-threaded
+/// executor or in the browser. Implementations must then use
+/// `#[async_trait(?Send)]` rather than `#[async_trait]`.
+///
+/// Since features are additive, enabling `unsend` affects all users of the
+/// crate in a build, and so should only be enabled by applications rather
+/// than libraries.
+#[cfg_attr(not(feature = "unsend"), async_trait)]
+#[cfg_attr(feature = "unsend", async_trait(?Send))]
 pub trait Client {
     /// `/abci_info`: get information about the ABCI application.
     async fn abci_info(&self) -> Result<abci_info::AbciInfo> {
@@ -57,12 +179,101 @@ pub trait Client {
             .response)
     }
 
+    /// `/abci_query`: query the value stored under the given key by the given
+    /// module of a Cosmos SDK application, using the conventional
+    /// `/store/<module>/key` path (see [`abci_query::Request::store_key`]).
+    async fn store_query(
+        &self,
+        module: &str,
+        key: &[u8],
+        height: Option<Height>,
+        prove: bool,
+    ) -> Result<abci_query::AbciQuery> {
+        let request = abci_query::Request::store_key(module, key, height, prove)?;
+        Ok(self.perform(request).await?.response)
+    }
+
+    /// `/abci_query`: query the ABCI application, requesting a proof.
+    ///
+    /// Some nodes fail to generate proofs for certain paths, even though the
+    /// value itself is available. If `fallback` is set and the query fails
+    /// because a proof could not be generated, it is retried without
+    /// requesting a proof, and [`abci_query::ProvenQuery::proof_dropped`] is
+    /// set.
+    async fn abci_query_with_proof<V>(
+        &self,
+        path: Option<abci::Path>,
+        data: V,
+        height: Option<Height>,
+        fallback: bool,
+    ) -> Result<abci_query::ProvenQuery>
+    where
+        V: Into<Vec<u8>> + Send,
+    {
+        let data = data.into();
+        let proven = self
+            .abci_query(path.clone(), data.clone(), height, true)
+            .await;
+        let proof_failed = match &proven {
+            Ok(response) => response.is_proof_failure(),
+            Err(e) => abci_query::is_proof_error(e),
+        };
+        if !fallback || !proof_failed {
+            return proven.map(|response| abci_query::ProvenQuery {
+                response,
+                proof_dropped: false,
+            });
+        }
+        Ok(abci_query::ProvenQuery {
+            response: self.abci_query(path, data, height, false).await?,
+            proof_dropped: true,
+        })
+    }
+
+    /// `/abci_query`: query the ABCI application, decoding the returned value
+    /// from JSON (see [`abci_query::AbciQuery::value_json`]).
+    async fn abci_query_decoded<V, T>(
+        &self,
+        path: Option<abci::Path>,
+        data: V,
+        height: Option<Height>,
+    ) -> Result<T>
+    where
+        V: Into<Vec<u8>> + Send,
+        T: DeserializeOwned,
+    {
+        self.abci_query(path, data, height, false)
+            .await?
+            .value_json()
+    }
+
+    /// `/abci_query`: query the ABCI application, decoding the returned value
+    /// as a Protobuf message (see [`abci_query::AbciQuery::value_proto`]).
+    async fn abci_query_decoded_proto<V, T>(
+        &self,
+        path: Option<abci::Path>,
+        data: V,
+        height: Option<Height>,
+    ) -> Result<T>
+    where
+        V: Into<Vec<u8>> + Send,
+        T: prost::Message + Default,
+    {
+        self.abci_query(path, data, height, false)
+            .await?
+            .value_proto()
+    }
+
     /// `/block`: get block at a given height.
+    ///
+    /// A height of 0 is rejected rather than being interpreted as the latest
+    /// height, as the node would do. Use [`Client::latest_block`] instead.
     async fn block<H>(&self, height: H) -> Result<block::Response>
     where
         H: Into<Height> + Send,
     {
-        self.perform(block::Request::new(height.into())).await
+        let height = explicit_height(height.into(), "latest_block")?;
+        self.perform(block::Request::new(height)).await
     }
 
     /// `/block`: get the latest block.
@@ -70,13 +281,48 @@ pub trait Client {
         self.perform(block::Request::default()).await
     }
 
+    /// `/block`: get the evidence of validator misbehavior committed in the
+    /// block at a given height.
+    ///
+    /// Evidence is not included in `/block_results`, so this fetches the whole
+    /// block and extracts its evidence.
+    async fn block_evidence<H>(&self, height: H) -> Result<Vec<Evidence>>
+    where
+        H: Into<Height> + Send,
+    {
+        Ok(self
+            .block(height)
+            .await?
+            .block
+            .evidence
+            .iter()
+            .cloned()
+            .collect())
+    }
+
     /// `/block_results`: get ABCI results for a block at a particular height.
+    ///
+    /// A height of 0 is rejected rather than being interpreted as the latest
+    /// height. Use [`Client::latest_block_results`] instead.
     async fn block_results<H>(&self, height: H) -> Result<block_results::Response>
     where
         H: Into<Height> + Send,
     {
-        self.perform(block_results::Request::new(height.into()))
-            .await
+        let height = explicit_height(height.into(), "latest_block_results")?;
+        self.perform(block_results::Request::new(height)).await
+    }
+
+    /// `/block_results`: get ABCI results for a block at a particular height,
+    /// decoding the results of its transactions and its events lazily (see
+    /// [`block_results::LazyResponse`]).
+    ///
+    /// A height of 0 is rejected, as for [`Client::block_results`].
+    async fn block_results_lazy<H>(&self, height: H) -> Result<block_results::LazyResponse>
+    where
+        H: Into<Height> + Send,
+    {
+        let height = explicit_height(height.into(), "latest_block_results")?;
+        self.perform(block_results::LazyRequest::new(height)).await
     }
 
     /// `/block_results`: get ABCI results for the latest block.
@@ -88,7 +334,7 @@ pub trait Client {
     ///
     /// Block headers are returned in descending order (highest first).
     ///
-    /// Returns at most 20 items.
+    /// Returns at most 20 items (see [`Client::blockchain_asc`] for more).
     async fn blockchain<H>(&self, min: H, max: H) -> Result<blockchain::Response>
     where
         H: Into<Height> + Send,
@@ -98,29 +344,269 @@ pub trait Client {
             .await
     }
 
+    /// `/blockchain`: get block headers for `min` <= `height` <= `max`, in
+    /// ascending order (lowest first).
+    ///
+    /// Unlike [`Client::blockchain`], any number of headers may be requested:
+    /// they are fetched via as many `/blockchain` requests as necessary, in
+    /// windows of [`blockchain::MAX_BLOCKCHAIN_HEADERS`]. Heights beyond the
+    /// latest block are ignored.
+    async fn blockchain_asc<H>(&self, min: H, max: H) -> Result<blockchain::Response>
+    where
+        H: Into<Height> + Send,
+    {
+        let (min, max) = (min.into().value(), max.into().value());
+        if min > max {
+            return Err(Error::invalid_params(&format!(
+                "minimum height {} exceeds maximum height {}",
+                min, max
+            )));
+        }
+        let height =
+            |h: u64| Height::try_from(h).map_err(|e| Error::client_internal_error(e.to_string()));
+        let mut block_metas = Vec::new();
+        let mut start = min;
+        loop {
+            let end = max.min(start + blockchain::MAX_BLOCKCHAIN_HEADERS - 1);
+            let response = self.blockchain(height(start)?, height(end)?).await?;
+            let mut metas: Vec<_> = response
+                .block_metas
+                .into_iter()
+                .filter(|meta| (start..=end).contains(&meta.header.height.value()))
+                .collect();
+            metas.sort_by_key(|meta| meta.header.height);
+            block_metas.extend(metas);
+            if end >= max || end >= response.last_height.value() {
+                return Ok(blockchain::Response {
+                    last_height: response.last_height,
+                    block_metas,
+                });
+            }
+            start = end + 1;
+        }
+    }
+
     /// `/broadcast_tx_async`: broadcast a transaction, returning immediately.
-    async fn broadcast_tx_async(&self, tx: Transaction) -> Result<broadcast::tx_async::Response> {
-        self.perform(broadcast::tx_async::Request::new(tx)).await
+    ///
+    /// The transaction may be given as a [`Transaction`] or as its raw bytes
+    /// (see [`broadcast::IntoTransaction`]).
+    async fn broadcast_tx_async<T>(&self, tx: T) -> Result<broadcast::tx_async::Response>
+    where
+        T: broadcast::IntoTransaction + Send,
+    {
+        self.perform(broadcast::tx_async::Request::new(tx.into_transaction()))
+            .await
     }
 
     /// `/broadcast_tx_sync`: broadcast a transaction, returning the response
     /// from `CheckTx`.
-    async fn broadcast_tx_sync(&self, tx: Transaction) -> Result<broadcast::tx_sync::Response> {
-        self.perform(broadcast::tx_sync::Request::new(tx)).await
+    ///
+    /// The transaction may be given as a [`Transaction`] or as its raw bytes
+    /// (see [`broadcast::IntoTransaction`]).
+    async fn broadcast_tx_sync<T>(&self, tx: T) -> Result<broadcast::tx_sync::Response>
+    where
+        T: broadcast::IntoTransaction + Send,
+    {
+        self.perform(broadcast::tx_sync::Request::new(tx.into_transaction()))
+            .await
+    }
+
+    /// Broadcast many transactions via `/broadcast_tx_sync`, with at most
+    /// `concurrency` broadcasts in flight at any one time.
+    ///
+    /// Results are returned in the same order as the given transactions.
+    async fn broadcast_many(
+        &self,
+        txs: Vec<Transaction>,
+        concurrency: usize,
+    ) -> Vec<Result<broadcast::tx_sync::Response>> {
+        self.broadcast_many_with_backoff(txs, concurrency, Duration::default(), 0)
+            .await
+    }
+
+    /// As per [`Client::broadcast_many`], but whenever the node reports that
+    /// its mempool is full, the broadcast is retried up to `max_retries` times
+    /// after waiting for `backoff`, which doubles with each retry.
+    async fn broadcast_many_with_backoff(
+        &self,
+        txs: Vec<Transaction>,
+        concurrency: usize,
+        backoff: Duration,
+        max_retries: usize,
+    ) -> Vec<Result<broadcast::tx_sync::Response>> {
+        stream::iter(txs)
+            .map(|tx| async move {
+                let mut delay = backoff;
+                let mut retries = 0;
+                loop {
+                    match self.broadcast_tx_sync(tx.clone()).await {
+                        Err(e) if retries < max_retries && e.is_mempool_full() => {
+                            time::sleep(delay).await;
+                            delay *= 2;
+                            retries += 1;
+                        }
+                        result => return result,
+                    }
+                }
+            })
+            .buffered(concurrency.max(1))
+            .collect()
+            .await
     }
 
     /// `/broadcast_tx_commit`: broadcast a transaction, returning the response
     /// from `DeliverTx`.
-    async fn broadcast_tx_commit(&self, tx: Transaction) -> Result<broadcast::tx_commit::Response> {
-        self.perform(broadcast::tx_commit::Request::new(tx)).await
+    ///
+    /// The transaction may be given as a [`Transaction`] or as its raw bytes
+    /// (see [`broadcast::IntoTransaction`]).
+    async fn broadcast_tx_commit<T>(&self, tx: T) -> Result<broadcast::tx_commit::Response>
+    where
+        T: broadcast::IntoTransaction + Send,
+    {
+        self.perform(broadcast::tx_commit::Request::new(tx.into_transaction()))
+            .await
+    }
+
+    /// Broadcast a transaction via `/broadcast_tx_sync`, then poll the node
+    /// until the block including it has been committed, invoking
+    /// `on_progress` at each state the transaction reaches (see
+    /// [`broadcast::TxProgress`]), e.g. to drive a progress bar.
+    ///
+    /// Unlike [`Client::broadcast_tx_commit`], this does not hold a request
+    /// open while waiting, and so is not subject to the node's commit
+    /// timeout. Instead, it fails if the transaction has not been committed
+    /// within `timeout`, or if it fails `CheckTx`. Otherwise, the result of
+    /// `DeliverTx` is returned, which may itself indicate failure.
+    async fn broadcast_tx_with_progress<T, F>(
+        &self,
+        tx: T,
+        timeout: Duration,
+        mut on_progress: F,
+    ) -> Result<tx_search::ResultTx>
+    where
+        T: broadcast::IntoTransaction + Send,
+        F: FnMut(broadcast::TxProgress) + Send,
+    {
+        let deadline = time::Instant::now() + timeout;
+        let check_tx = self.broadcast_tx_sync(tx).await?;
+        let hash = check_tx.hash;
+        on_progress(broadcast::TxProgress::CheckTx(check_tx.clone()));
+        if check_tx.code.is_err() {
+            return Err(Error::client_internal_error(format!(
+                "CheckTx failed for transaction {} with code {}: {}",
+                hash,
+                check_tx.code.value(),
+                check_tx.log
+            )));
+        }
+
+        let query = Query::eq("tx.hash", hash.to_string());
+        let tx = loop {
+            let response = self
+                .tx_search(query.clone(), false, 1, 1, Order::Ascending)
+                .await?;
+            if let Some(tx) = response.txs.into_iter().next() {
+                break tx;
+            }
+            wait_for_tx_progress(deadline, &hash, "included in a block").await?;
+        };
+        let height = tx.height;
+        on_progress(broadcast::TxProgress::Included(tx.clone()));
+
+        while !self.commit(height).await?.canonical {
+            wait_for_tx_progress(deadline, &hash, "committed").await?;
+        }
+        on_progress(broadcast::TxProgress::Committed { hash, height });
+        Ok(tx)
     }
 
     /// `/commit`: get block commit at a given height.
+    ///
+    /// A height of 0 is rejected rather than being interpreted as the latest
+    /// height. Use [`Client::latest_commit`] instead.
     async fn commit<H>(&self, height: H) -> Result<commit::Response>
     where
         H: Into<Height> + Send,
     {
-        self.perform(commit::Request::new(height.into())).await
+        let height = explicit_height(height.into(), "latest_commit")?;
+        self.perform(commit::Request::new(height)).await
+    }
+
+    /// `/commit`: get the commits at each of the heights from `min` to `max`
+    /// (inclusive), yielding them in order of height.
+    ///
+    /// Commits are fetched as the returned stream is consumed, with up to
+    /// `concurrency` requests in flight at any one time. Fails if `min`
+    /// exceeds `max`.
+    fn commits<H>(
+        &self,
+        min: H,
+        max: H,
+        concurrency: usize,
+    ) -> Result<ClientStream<'_, Result<commit::Response>>>
+    where
+        H: Into<Height>,
+        Self: Sync + Sized,
+    {
+        let (min, max) = (min.into(), max.into());
+        if min > max {
+            return Err(Error::invalid_params(&format!(
+                "minimum height {} exceeds maximum height {}",
+                min, max
+            )));
+        }
+        Ok(Box::pin(
+            stream::iter(min.value()..=max.value())
+                .map(move |height| async move {
+                    let height = Height::try_from(height)
+                        .map_err(|e| Error::client_internal_error(e.to_string()))?;
+                    self.commit(height).await
+                })
+                .buffered(concurrency.max(1)),
+        ))
+    }
+
+    /// Assemble the [`LightBlock`] at the given height from its signed header
+    /// (via `/commit`) and the validator sets at that height and the next
+    /// (via `/validators`), which are fetched concurrently.
+    ///
+    /// The next validator set is only available once the block at the given
+    /// height has been committed.
+    async fn light_block<H>(&self, height: H) -> Result<LightBlock>
+    where
+        H: Into<Height> + Send,
+    {
+        let height = height.into();
+        let (commit, validators, next_validators) = futures::try_join!(
+            self.commit(height),
+            self.validators(height, Paging::All),
+            self.validators(height.increment(), Paging::All),
+        )?;
+        Ok(LightBlock {
+            signed_header: commit.signed_header,
+            validators: validator::Set::without_proposer(validators.validators),
+            next_validators: validator::Set::without_proposer(next_validators.validators),
+        })
+    }
+
+    /// Fetch the block at the given height, the block following it (which
+    /// must already have been committed) and the results of executing the
+    /// block, and check them against one another (see
+    /// [`AppHashTransition`]).
+    ///
+    /// Inconsistencies are reported via the returned transition rather than
+    /// as errors.
+    async fn app_hash_transition<H>(&self, height: H) -> Result<AppHashTransition>
+    where
+        H: Into<Height> + Send,
+    {
+        let height = height.into();
+        let (block, next, results) = futures::try_join!(
+            self.block(height),
+            self.block(height.increment()),
+            self.block_results(height),
+        )?;
+        AppHashTransition::from_responses(block, next, &results)
     }
 
     /// `/consensus_state`: get current consensus state
@@ -130,6 +616,11 @@ pub trait Client {
 
     // TODO(thane): Simplify once validators endpoint removes pagination.
     /// `/validators`: get validators a given height.
+    ///
+    /// When fetching all pages (see [`Paging::All`]), fails if the node's
+    /// reported total is not reached within [`DEFAULT_MAX_PAGES`] pages.
+    ///
+    /// [`DEFAULT_MAX_PAGES`]: crate::DEFAULT_MAX_PAGES
     async fn validators<H>(&self, height: H, paging: Paging) -> Result<validators::Response>
     where
         H: Into<Height> + Send,
@@ -151,32 +642,68 @@ pub trait Client {
                 ))
                 .await
             }
-            Paging::All => {
-                let mut page_num = 1_usize;
-                let mut validators = Vec::new();
-                let per_page = DEFAULT_VALIDATORS_PER_PAGE.into();
-                loop {
-                    let response = self
-                        .perform(validators::Request::new(
-                            Some(height),
-                            Some(page_num.into()),
-                            Some(per_page),
-                        ))
-                        .await?;
-                    validators.extend(response.validators);
-                    if validators.len() as i32 == response.total {
-                        return Ok(validators::Response::new(
-                            response.block_height,
-                            validators,
-                            response.total,
-                        ));
-                    }
-                    page_num += 1;
-                }
+            Paging::All => self.all_validators(height, crate::DEFAULT_MAX_PAGES).await,
+        }
+    }
+
+    /// `/validators`: get all validators at a given height, fetching one page
+    /// after another.
+    ///
+    /// Fails if the node's reported total is not reached within `max_pages`
+    /// pages.
+    async fn all_validators<H>(&self, height: H, max_pages: usize) -> Result<validators::Response>
+    where
+        H: Into<Height> + Send,
+    {
+        let height = height.into();
+        let mut page_num = 1_usize;
+        let mut validators = Vec::new();
+        let mut total = 0;
+        let per_page = DEFAULT_VALIDATORS_PER_PAGE.into();
+        loop {
+            if page_num > max_pages {
+                return Err(Error::client_internal_error(format!(
+                    "fetched {} pages of validators at height {} without reaching the \
+                     total of {} reported by the node (got {})",
+                    max_pages,
+                    height,
+                    total,
+                    validators.len()
+                )));
+            }
+            let response = self
+                .perform(validators::Request::new(
+                    Some(height),
+                    Some(page_num.into()),
+                    Some(per_page),
+                ))
+                .await?;
+            validators.extend(response.validators);
+            total = response.total;
+            if validators.len() as i32 == total {
+                return Ok(validators::Response::new(
+                    response.block_height,
+                    validators,
+                    response.total,
+                ));
             }
+            page_num += 1;
         }
     }
 
+    /// `/validators`: get the validators at the latest block height, as
+    /// reported by `/status`.
+    ///
+    /// Resolving the height up front ensures that all pages (e.g. when using
+    /// [`Paging::All`]) are fetched at the same height. A new block may be
+    /// committed between the two requests, in which case the validators
+    /// returned are those of the block preceding the actual latest one (see
+    /// the response's `block_height`).
+    async fn latest_validators(&self, paging: Paging) -> Result<validators::Response> {
+        let height = self.status().await?.sync_info.latest_block_height;
+        self.validators(height, paging).await
+    }
+
     /// `/commit`: get the latest block commit
     async fn latest_commit(&self) -> Result<commit::Response> {
         self.perform(commit::Request::default()).await
@@ -195,23 +722,85 @@ pub trait Client {
         Ok(self.perform(genesis::Request).await?.genesis)
     }
 
+    /// `/genesis`: get the initial validator set from the genesis file,
+    /// without decoding the rest of it (see [`genesis::ValidatorsRequest`]).
+    async fn genesis_validators(&self) -> Result<Vec<validator::Info>> {
+        Ok(self
+            .perform(genesis::ValidatorsRequest)
+            .await?
+            .genesis
+            .validators)
+    }
+
     /// `/net_info`: obtain information about P2P and other network connections.
     async fn net_info(&self) -> Result<net_info::Response> {
         self.perform(net_info::Request).await
     }
 
+    /// Assess the health of each of the node's peers, based on the
+    /// connection statistics reported by `/net_info` (see
+    /// [`net_info::PeerInfo::health`]).
+    async fn peer_health(
+        &self,
+        thresholds: net_info::HealthThresholds,
+    ) -> Result<Vec<net_info::PeerHealth>> {
+        Ok(self.net_info().await?.peer_health(&thresholds))
+    }
+
     /// `/status`: get Tendermint status including node info, pubkey, latest
     /// block hash, app hash, block height and time.
     async fn status(&self) -> Result<status::Response> {
         self.perform(status::Request).await
     }
 
+    /// The inclusive range of heights of the blocks the node can serve, as
+    /// reported by `/status` (see [`status::SyncInfo::available_height_range`]).
+    ///
+    /// Pruned nodes cannot serve blocks below the start of this range.
+    async fn available_height_range(&self) -> Result<(Height, Height)> {
+        Ok(self.status().await?.sync_info.available_height_range())
+    }
+
+    /// Fetch the node's `/status` and `/abci_info` concurrently, summarizing
+    /// the state of the node and its application (see [`NodeSnapshot`]).
+    async fn node_snapshot(&self) -> Result<NodeSnapshot> {
+        let (status, abci_info) = futures::try_join!(self.status(), self.abci_info())?;
+        Ok(NodeSnapshot::from_responses(status, abci_info))
+    }
+
+    /// Obtain the Tendermint version of the node, as reported by `/status`.
+    ///
+    /// A leading `v` in the reported version (e.g. `v0.34.9`) is ignored.
+    async fn server_version(&self) -> Result<semver::Version> {
+        let status = self.status().await?;
+        parse_server_version(&status.node_info.version.to_string())
+    }
+
+    /// Check whether the node is recent enough to support the given method
+    /// (see [`Method::min_version`]), so that callers can feature-detect
+    /// before calling it.
+    ///
+    /// Pre-release and build metadata in the node's version are disregarded,
+    /// since forks commonly use them to tag their own releases.
+    async fn supports(&self, method: Method) -> Result<bool> {
+        let version = self.server_version().await?;
+        let min = method.min_version();
+        Ok((version.major, version.minor, version.patch) >= (min.major, min.minor, min.patch))
+    }
+
     /// `/broadcast_evidence`: broadcast an evidence.
     async fn broadcast_evidence(&self, e: Evidence) -> Result<evidence::Response> {
         self.perform(evidence::Request::new(e)).await
     }
 
     /// `/tx_search`: search for transactions with their results.
+    ///
+    /// Fails without contacting the node if the query contains local-only
+    /// conditions (see [`Query::validate_for_server`]) or more conditions
+    /// than the client allows (see [`Client::max_query_conditions`]), or if
+    /// `per_page` is not between 1 and [`tx_search::MAX_PER_PAGE`], since the
+    /// node would otherwise silently use a different page size (throwing off
+    /// any pagination based on `total_count`).
     async fn tx_search(
         &self,
         query: Query,
@@ -220,33 +809,264 @@ pub trait Client {
         per_page: u8,
         order: Order,
     ) -> Result<tx_search::Response> {
+        query.validate_for_server_with_max(self.max_query_conditions())?;
+        if per_page == 0 || per_page > tx_search::MAX_PER_PAGE {
+            return Err(Error::invalid_params(&format!(
+                "per_page must be between 1 and {}, but was {}",
+                tx_search::MAX_PER_PAGE,
+                per_page
+            )));
+        }
         self.perform(tx_search::Request::new(query, prove, page, per_page, order))
             .await
     }
 
-    /// Poll the `/health` endpoint until it returns a successful result or
-    /// the given `timeout` has elapsed.
-    async fn wait_until_healthy<T>(&self, timeout: T) -> Result<()>
+    /// `/tx_search`: search for transactions with their results, sorting
+    /// them by height and then by index within their block, in the given
+    /// order (see [`tx_search::Response::sort_by_position`]).
+    ///
+    /// Unlike [`Client::tx_search`], the order of transactions at the same
+    /// height does not depend on how the node breaks ties.
+    async fn tx_search_sorted(
+        &self,
+        query: Query,
+        prove: bool,
+        page: u32,
+        per_page: u8,
+        order: Order,
+    ) -> Result<tx_search::Response> {
+        let mut response = self
+            .tx_search(query, prove, page, per_page, order.clone())
+            .await?;
+        response.sort_by_position(&order);
+        Ok(response)
+    }
+
+    /// `/tx_search`: search for transactions with their results, fetching
+    /// pages of up to `per_page` results even if they exceed the node's
+    /// maximum page size.
+    ///
+    /// Nodes silently reduce the page size of requests exceeding their
+    /// maximum (100 by default), which would otherwise shift the results
+    /// returned for all but the first page. Instead, the requested page is
+    /// assembled from as many of the node's pages as necessary, of at most
+    /// `max_per_page` (itself at most [`tx_search::MAX_PER_PAGE`]) results
+    /// each. If the node responds with fewer results
+    /// than requested while more remain, its actual maximum is taken to be the
+    /// number of results it returned, and the page is fetched again in
+    /// smaller chunks. The results are returned in the order given by the
+    /// node.
+    ///
+    /// Note that [`Client::tx_search_stream`] is unaffected by the node's
+    /// maximum page size, as it fetches all results in turn.
+    #[allow(clippy::too_many_arguments)]
+    async fn tx_search_chunked(
+        &self,
+        query: Query,
+        prove: bool,
+        page: u32,
+        per_page: u32,
+        order: Order,
+        max_per_page: u8,
+    ) -> Result<tx_search::Response> {
+        if page == 0 || per_page == 0 || max_per_page == 0 {
+            return Err(Error::invalid_params(
+                "page, per_page and max_per_page must all be greater than 0",
+            ));
+        }
+        let start = (page as usize - 1) * per_page as usize;
+        let end = start + per_page as usize;
+        let mut chunk = max_per_page.min(tx_search::MAX_PER_PAGE) as usize;
+        'fetch: loop {
+            let mut txs = Vec::with_capacity(per_page as usize);
+            let mut total_count = 0;
+            let mut node_page = start / chunk + 1;
+            let mut offset = (node_page - 1) * chunk;
+            while offset < end {
+                let response = self
+                    .tx_search(
+                        query.clone(),
+                        prove,
+                        node_page as u32,
+                        chunk as u8,
+                        order.clone(),
+                    )
+                    .await?;
+                total_count = response.total_count;
+                let received = response.txs.len();
+                if received > 0 && received < chunk && offset + received < total_count as usize {
+                    // The node capped the page size, so fetch the requested
+                    // page again in chunks of its actual maximum.
+                    chunk = received;
+                    continue 'fetch;
+                }
+                txs.extend(
+                    response
+                        .txs
+                        .into_iter()
+                        .enumerate()
+                        .filter(|(i, _)| (start..end).contains(&(offset + i)))
+                        .map(|(_, tx)| tx),
+                );
+                if received < chunk || offset + received >= total_count as usize {
+                    break;
+                }
+                offset += chunk;
+                node_page += 1;
+            }
+            return Ok(tx_search::Response { txs, total_count });
+        }
+    }
+
+    /// Search for transactions via `/tx_search`, yielding them one at a time.
+    ///
+    /// Pages of results are only fetched as the returned stream is consumed,
+    /// so arbitrarily large result sets can be processed incrementally. The
+    /// stream ends after the first error.
+    fn tx_search_stream(
+        &self,
+        query: Query,
+        prove: bool,
+        order: Order,
+    ) -> ClientStream<'_, Result<tx_search::ResultTx>>
     where
-        T: Into<Duration> + Send,
+        Self: Sync + Sized,
     {
-        let timeout = timeout.into();
-        let poll_interval = Duration::from_millis(200);
-        let mut attempts_remaining = timeout.as_millis() / poll_interval.as_millis();
+        let state = TxSearchState {
+            page: 1,
+            fetched: 0,
+            buffer: VecDeque::new(),
+            done: false,
+        };
+        Box::pin(stream::unfold(state, move |mut state| {
+            let (query, order) = (query.clone(), order.clone());
+            async move {
+                loop {
+                    if let Some(tx) = state.buffer.pop_front() {
+                        return Some((Ok(tx), state));
+                    }
+                    if state.done {
+                        return None;
+                    }
+                    match self
+                        .tx_search(
+                            query.clone(),
+                            prove,
+                            state.page,
+                            TX_SEARCH_PER_PAGE,
+                            order.clone(),
+                        )
+                        .await
+                    {
+                        Ok(response) => {
+                            state.page += 1;
+                            state.fetched += response.txs.len();
+                            state.done = response.txs.is_empty()
+                                || state.fetched >= response.total_count as usize;
+                            state.buffer.extend(response.txs);
+                        }
+                        Err(e) => {
+                            state.done = true;
+                            return Some((Err(e), state));
+                        }
+                    }
+                }
+            }
+        }))
+    }
 
-        while self.health().await.is_err() {
-            if attempts_remaining == 0 {
+    /// Repeatedly run the given operation (typically one or more requests
+    /// via this client), waiting `interval` between attempts, until it
+    /// produces a value or the given `timeout` has elapsed, e.g. to wait for
+    /// a transaction to be included in a block.
+    ///
+    /// Fails as soon as the operation fails, so operations which should be
+    /// retried on failure must map their errors to `Ok(None)`. Fails with an
+    /// [`ErrorKind::Timeout`] error if waiting for another attempt would take
+    /// longer than the time remaining.
+    ///
+    /// All timing is driven by Tokio's timer, so the polling loop honours a
+    /// paused clock (see [`tokio::time::pause`]) and can be exercised in tests
+    /// without any real delays.
+    async fn poll_until<F, Fut, T>(
+        &self,
+        mut operation: F,
+        timeout: Duration,
+        interval: Duration,
+    ) -> Result<T>
+    where
+        F: FnMut() -> Fut + Send,
+        Fut: Future<Output = Result<Option<T>>> + Send,
+        T: Send,
+    {
+        let deadline = time::Instant::now() + timeout;
+        loop {
+            if let Some(value) = operation().await? {
+                return Ok(value);
+            }
+            if time::Instant::now() + interval > deadline {
                 return Err(Error::client_internal_error(format!(
-                    "timed out waiting for healthy response after {}ms",
+                    "timed out polling after {}ms",
                     timeout.as_millis()
-                )));
+                ))
+                .with_kind(ErrorKind::Timeout));
             }
-
-            attempts_remaining -= 1;
-            time::sleep(poll_interval).await;
+            time::sleep(interval).await;
         }
+    }
 
-        Ok(())
+    /// Poll the `/health` endpoint until it returns a successful result or
+    /// the given `timeout` has elapsed (see [`Client::poll_until`]).
+    async fn wait_until_healthy<T>(&self, timeout: T) -> Result<()>
+    where
+        T: Into<Duration> + Send,
+    {
+        let timeout = timeout.into();
+        self.poll_until(
+            move || async move { Ok(self.health().await.ok()) },
+            timeout,
+            HEALTH_POLL_INTERVAL,
+        )
+        .await
+        .map_err(|e| {
+            Error::client_internal_error(format!(
+                "timed out waiting for healthy response after {}ms",
+                timeout.as_millis()
+            ))
+            .with_kind(e.kind())
+        })
+    }
+
+    /// Perform a request against the RPC endpoint, failing if no response
+    /// has been received by the given `deadline`.
+    ///
+    /// Since the deadline is absolute, a sequence of requests can share a
+    /// single deadline without having to compute the time remaining for each.
+    async fn perform_with_deadline<R, D>(&self, request: R, deadline: D) -> Result<R::Response>
+    where
+        R: SimpleRequest,
+        D: Into<time::Instant> + Send,
+    {
+        let method = request.method();
+        time::timeout_at(deadline.into(), self.perform(request))
+            .await
+            .map_err(|_| {
+                Error::client_internal_error(format!(
+                    "deadline elapsed before receiving a response to /{}",
+                    method
+                ))
+                .with_kind(ErrorKind::Timeout)
+            })?
+    }
+
+    /// The maximum number of conditions (see [`Query::condition_count`]) in
+    /// queries sent by this client, beyond which they are rejected without
+    /// contacting the node, if any.
+    ///
+    /// Defaults to no limit, as Tendermint itself imposes none. Clients which
+    /// wrap another client use that client's limit.
+    fn max_query_conditions(&self) -> Option<usize> {
+        None
     }
 
     /// Perform a request against the RPC endpoint
@@ -254,3 +1074,1040 @@ pub trait Client {
     where
         R: SimpleRequest;
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::query::EventType;
+    use crate::test_support::{
+        counting_transport, fixture, transport_requests, HeightRecordingMatcher,
+    };
+    use crate::{Request, Response};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tokio::time::Instant;
+
+    // Fails the first `failures` health checks, after which it responds
+    // successfully.
+    struct FlakyHealthMatcher {
+        failures: AtomicUsize,
+        health: String,
+    }
+
+    impl MockRequestMatcher for FlakyHealthMatcher {
+        fn response_for<R>(&self, request: R) -> Option<Result<R::Response>>
+        where
+            R: Request,
+        {
+            if request.method() != Method::Health {
+                return None;
+            }
+            let remaining = self.failures.load(Ordering::SeqCst);
+            if remaining > 0 {
+                self.failures.store(remaining - 1, Ordering::SeqCst);
+                return Some(Err(Error::http_error("node is not healthy yet")));
+            }
+            Some(R::Response::from_string(&self.health))
+        }
+    }
+
+    // Simulates a node which takes a while to respond to broadcasts, tracking
+    // how many are in flight. Each response's log echoes the broadcast
+    // transaction. The first `mempool_full` broadcasts are rejected as though
+    // the mempool were full.
+    #[derive(Default)]
+    struct SlowBroadcastClient {
+        started: AtomicUsize,
+        in_flight: AtomicUsize,
+        max_in_flight: AtomicUsize,
+        mempool_full: usize,
+    }
+
+    #[cfg_attr(not(feature = "unsend"), async_trait)]
+    #[cfg_attr(feature = "unsend", async_trait(?Send))]
+    impl Client for SlowBroadcastClient {
+        async fn perform<R>(&self, request: R) -> Result<R::Response>
+        where
+            R: Request,
+        {
+            let n = self.started.fetch_add(1, Ordering::SeqCst);
+            if n < self.mempool_full {
+                return Err(Error::new(
+                    crate::error::Code::InternalError,
+                    Some("mempool is full: number of txs 5000 (max: 5000)".to_string()),
+                ));
+            }
+            let request: serde_json::Value = serde_json::from_str(&request.into_json()).unwrap();
+            let in_flight = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.max_in_flight.fetch_max(in_flight, Ordering::SeqCst);
+            // Later broadcasts complete sooner than earlier ones.
+            time::sleep(Duration::from_millis(100 - n as u64)).await;
+            self.in_flight.fetch_sub(1, Ordering::SeqCst);
+            R::Response::from_string(format!(
+                r#"{{"jsonrpc":"2.0","id":"","result":{{"code":"0","data":"","log":{},"hash":"88D4266FD4E6338D13B845FCF289579D209C897823B9217DA3E161936F031589"}}}}"#,
+                request["params"]["tx"]
+            ))
+        }
+    }
+
+    // Serves the transactions from the `tx_search_no_prove` fixture in pages
+    // of the requested size, capped at the node's maximum page size (three by
+    // default), recording which pages were requested. Like Tendermint, fails
+    // requests for pages past the last.
+    struct PagedTxSearchMatcher {
+        txs: Vec<serde_json::Value>,
+        max_per_page: usize,
+        pages_requested: std::sync::Mutex<Vec<u32>>,
+    }
+
+    impl PagedTxSearchMatcher {
+        fn new(fixture: &str) -> Self {
+            let fixture: serde_json::Value = serde_json::from_str(fixture).unwrap();
+            Self {
+                txs: fixture["result"]["txs"].as_array().unwrap().clone(),
+                max_per_page: 3,
+                pages_requested: Default::default(),
+            }
+        }
+
+        fn with_max_per_page(mut self, max_per_page: usize) -> Self {
+            self.max_per_page = max_per_page;
+            self
+        }
+
+        fn pages_requested(&self) -> Vec<u32> {
+            self.pages_requested.lock().unwrap().clone()
+        }
+    }
+
+    impl MockRequestMatcher for PagedTxSearchMatcher {
+        fn response_for<R>(&self, request: R) -> Option<Result<R::Response>>
+        where
+            R: Request,
+        {
+            if request.method() != Method::TxSearch {
+                return None;
+            }
+            let request: serde_json::Value = serde_json::from_str(&request.into_json()).unwrap();
+            let page: u32 = request["params"]["page"].as_str().unwrap().parse().unwrap();
+            let per_page: usize = request["params"]["per_page"]
+                .as_str()
+                .unwrap()
+                .parse()
+                .unwrap();
+            self.pages_requested.lock().unwrap().push(page);
+
+            let per_page = per_page.min(self.max_per_page);
+            let pages = ((self.txs.len() + per_page - 1) / per_page).max(1);
+            if page as usize > pages {
+                return Some(Err(Error::new(
+                    crate::error::Code::InternalError,
+                    Some(format!(
+                        "page should be within [1, {}] range, given {}",
+                        pages, page
+                    )),
+                )));
+            }
+            let start = (page as usize - 1) * per_page;
+            let end = (start + per_page).min(self.txs.len());
+            let response = serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": "",
+                "result": {
+                    "txs": &self.txs[start..end],
+                    "total_count": self.txs.len().to_string(),
+                },
+            });
+            Some(R::Response::from_string(response.to_string()))
+        }
+    }
+
+    // Simulates a transaction working its way through a node: it is only
+    // found by `/tx_search` from the third search onwards, and the commit for
+    // its block only becomes canonical from the second request onwards.
+    struct TxLifecycleMatcher {
+        inner: MockRequestMethodMatcher,
+        tx: serde_json::Value,
+        commit: serde_json::Value,
+        searches: AtomicUsize,
+        commits: AtomicUsize,
+    }
+
+    impl TxLifecycleMatcher {
+        fn new(broadcast_tx_sync: String) -> Self {
+            let txs: serde_json::Value =
+                serde_json::from_str(&fixture("tx_search_no_prove")).unwrap();
+            Self {
+                inner: MockRequestMethodMatcher::default()
+                    .map(Method::BroadcastTxSync, Ok(broadcast_tx_sync)),
+                tx: txs["result"]["txs"][0].clone(),
+                commit: serde_json::from_str(&fixture("commit")).unwrap(),
+                searches: AtomicUsize::new(0),
+                commits: AtomicUsize::new(0),
+            }
+        }
+    }
+
+    impl MockRequestMatcher for TxLifecycleMatcher {
+        fn response_for<R>(&self, request: R) -> Option<Result<R::Response>>
+        where
+            R: Request,
+        {
+            let result = match request.method() {
+                Method::TxSearch => {
+                    let found = self.searches.fetch_add(1, Ordering::SeqCst) >= 2;
+                    let txs = if found { vec![self.tx.clone()] } else { vec![] };
+                    serde_json::json!({ "txs": txs, "total_count": txs.len().to_string() })
+                }
+                Method::Commit => {
+                    let mut commit = self.commit["result"].clone();
+                    commit["canonical"] = (self.commits.fetch_add(1, Ordering::SeqCst) >= 1).into();
+                    commit
+                }
+                _ => return self.inner.response_for(request),
+            };
+            let response = serde_json::json!({ "jsonrpc": "2.0", "id": "", "result": result });
+            Some(R::Response::from_string(response.to_string()))
+        }
+    }
+
+    fn txs(count: u8) -> Vec<Transaction> {
+        (0..count).map(|i| Transaction::from(vec![i])).collect()
+    }
+
+    async fn server_version_for(version: &str) -> Result<semver::Version> {
+        let status = fixture("status").replace(
+            "\"version\": \"0.30.1\"",
+            &format!("\"version\": \"{}\"", version),
+        );
+        let matcher = MockRequestMethodMatcher::default().map(Method::Status, Ok(status));
+        let (client, driver) = MockClient::new(matcher);
+        let driver_hdl = tokio::spawn(async move { driver.run().await });
+
+        let version = client.server_version().await;
+
+        client.close();
+        driver_hdl.await.unwrap().unwrap();
+        version
+    }
+
+    #[tokio::test]
+    async fn server_version_parsing() {
+        let version = server_version_for("0.30.1").await.unwrap();
+        assert_eq!(semver::Version::new(0, 30, 1), version);
+
+        let version = server_version_for("v0.34.9").await.unwrap();
+        assert_eq!(semver::Version::new(0, 34, 9), version);
+
+        let version = server_version_for("0.34.0-rc6").await.unwrap();
+        assert_eq!((0, 34, 0), (version.major, version.minor, version.patch));
+        assert_eq!("rc6", version.pre.as_str());
+
+        assert!(server_version_for("unknown").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn version_gated_method_support() {
+        let status = fixture("status");
+        let matcher = MockRequestMethodMatcher::default().map(Method::Status, Ok(status));
+        let (client, driver) = MockClient::new(matcher);
+        let driver_hdl = tokio::spawn(async move { driver.run().await });
+
+        // The fixture reports version 0.30.1.
+        assert!(client.supports(Method::Status).await.unwrap());
+        assert!(!client.supports(Method::BroadcastEvidence).await.unwrap());
+
+        client.close();
+        driver_hdl.await.unwrap().unwrap();
+    }
+
+    #[tokio::test]
+    async fn broadcasts_accept_raw_bytes() {
+        let matcher = MockRequestMethodMatcher::default()
+            .map(Method::BroadcastTxAsync, Ok(fixture("broadcast_tx_async")))
+            .map(Method::BroadcastTxSync, Ok(fixture("broadcast_tx_sync")))
+            .map(
+                Method::BroadcastTxCommit,
+                Ok(fixture("broadcast_tx_commit")),
+            );
+        let (client, driver) = MockClient::new(matcher);
+        let driver_hdl = tokio::spawn(async move { driver.run().await });
+
+        let bytes: &[u8] = b"key=value";
+        client.broadcast_tx_async(bytes).await.unwrap();
+        client.broadcast_tx_sync(bytes).await.unwrap();
+        client.broadcast_tx_commit(bytes).await.unwrap();
+        client.broadcast_tx_sync(b"key=value").await.unwrap();
+        client.broadcast_tx_sync(bytes.to_vec()).await.unwrap();
+        client
+            .broadcast_tx_sync(Transaction::from(bytes.to_vec()))
+            .await
+            .unwrap();
+
+        client.close();
+        driver_hdl.await.unwrap().unwrap();
+    }
+
+    #[tokio::test]
+    async fn broadcast_tx_with_progress_reports_each_state() {
+        use crate::endpoint::broadcast::TxProgress;
+
+        let broadcast_tx_sync = fixture("broadcast_tx_sync");
+        let (client, driver) = MockClient::new(TxLifecycleMatcher::new(broadcast_tx_sync));
+        let driver_hdl = tokio::spawn(async move { driver.run().await });
+
+        time::pause();
+        let mut progress = Vec::new();
+        let tx = client
+            .broadcast_tx_with_progress(b"tx", Duration::from_secs(10), |p| {
+                progress.push(match p {
+                    TxProgress::CheckTx(response) => format!("CheckTx {}", response.code.value()),
+                    TxProgress::Included(tx) => format!("Included at {}", tx.height),
+                    TxProgress::Committed { height, .. } => format!("Committed at {}", height),
+                })
+            })
+            .await
+            .unwrap();
+        assert_eq!(
+            vec!["CheckTx 0", "Included at 11", "Committed at 11"],
+            progress
+        );
+        assert_eq!(Height::from(11_u32), tx.height);
+        assert_eq!(3, client.matcher().searches.load(Ordering::SeqCst));
+        assert_eq!(2, client.matcher().commits.load(Ordering::SeqCst));
+
+        // The transaction is never found within the timeout.
+        client.matcher().searches.store(0, Ordering::SeqCst);
+        let err = client
+            .broadcast_tx_with_progress(b"tx", Duration::from_millis(700), |_| ())
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("to be included in a block"));
+        time::resume();
+
+        client.close();
+        driver_hdl.await.unwrap().unwrap();
+
+        // Nothing is awaited if the transaction fails `CheckTx`.
+        let rejected = fixture("broadcast_tx_sync").replace(r#""code": "0""#, r#""code": "5""#);
+        let (client, _driver) = MockClient::new(TxLifecycleMatcher::new(rejected));
+        let mut progress = 0;
+        let err = client
+            .broadcast_tx_with_progress(b"tx", Duration::from_secs(10), |_| progress += 1)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("CheckTx failed"));
+        assert_eq!(1, progress);
+        assert_eq!(0, client.matcher().searches.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn broadcast_many_bounds_concurrency() {
+        time::pause();
+        let client = SlowBroadcastClient::default();
+
+        let results = client.broadcast_many(txs(20), 4).await;
+
+        assert_eq!(4, client.max_in_flight.load(Ordering::SeqCst));
+        assert_eq!(20, results.len());
+        for (tx, result) in txs(20).into_iter().zip(results) {
+            assert_eq!(
+                serde_json::to_value(&tx).unwrap(),
+                result.unwrap().log.to_string()
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn broadcast_many_backs_off_when_mempool_is_full() {
+        time::pause();
+        let client = SlowBroadcastClient {
+            mempool_full: 2,
+            ..Default::default()
+        };
+        let results = client.broadcast_many(txs(3), 1).await;
+        assert!(results[0].is_err());
+        assert!(results[1].is_err());
+        assert!(results[2].is_ok());
+
+        let client = SlowBroadcastClient {
+            mempool_full: 2,
+            ..Default::default()
+        };
+        let results = client
+            .broadcast_many_with_backoff(txs(3), 1, Duration::from_millis(50), 2)
+            .await;
+        assert!(results.iter().all(|r| r.is_ok()));
+    }
+
+    #[tokio::test]
+    async fn tx_search_stream_fetches_pages_lazily() {
+        let matcher = PagedTxSearchMatcher::new(&fixture("tx_search_no_prove"));
+        let (client, driver) = MockClient::new(matcher);
+        let driver_hdl = tokio::spawn(async move { driver.run().await });
+
+        let mut txs =
+            client.tx_search_stream(Query::from(EventType::Tx), false, crate::Order::Ascending);
+        assert!(client.matcher().pages_requested().is_empty());
+
+        let mut heights = Vec::new();
+        for _ in 0..6 {
+            heights.push(txs.next().await.unwrap().unwrap().height);
+        }
+        assert_eq!(vec![1, 2], client.matcher().pages_requested());
+
+        // The third page is only fetched once we consume beyond the second.
+        heights.push(txs.next().await.unwrap().unwrap().height);
+        assert_eq!(vec![1, 2, 3], client.matcher().pages_requested());
+        heights.push(txs.next().await.unwrap().unwrap().height);
+        assert!(txs.next().await.is_none());
+        assert_eq!(vec![1, 2, 3], client.matcher().pages_requested());
+        assert_eq!(8, heights.len());
+
+        drop(txs);
+        client.close();
+        driver_hdl.await.unwrap().unwrap();
+    }
+
+    #[tokio::test]
+    async fn tx_search_chunked_respects_node_page_size() {
+        let fixture = fixture("tx_search_no_prove");
+        let (client, driver) = MockClient::new(PagedTxSearchMatcher::new(&fixture));
+        let driver_hdl = tokio::spawn(async move { driver.run().await });
+        let expected: Vec<_> = client
+            .matcher
+            .txs
+            .iter()
+            .map(|tx| tx["hash"].as_str().unwrap().to_owned())
+            .collect();
+        let search = |page, per_page, max_per_page| {
+            client.tx_search_chunked(
+                Query::from(EventType::Tx),
+                false,
+                page,
+                per_page,
+                crate::Order::Ascending,
+                max_per_page,
+            )
+        };
+        let hashes = |response: crate::endpoint::tx_search::Response| -> Vec<String> {
+            response.txs.iter().map(|tx| tx.hash.to_string()).collect()
+        };
+
+        // The node's maximum page size (3) is discovered from its response to
+        // the first request, after which all of the results are fetched.
+        let response = search(1, 10, 100).await.unwrap();
+        assert_eq!(8, response.total_count);
+        assert_eq!(expected, hashes(response));
+        assert_eq!(vec![1, 1, 2, 3], client.matcher().pages_requested());
+
+        // With the maximum configured, later pages span the node's pages.
+        client.matcher().pages_requested.lock().unwrap().clear();
+        let response = search(2, 4, 3).await.unwrap();
+        assert_eq!(expected[4..], hashes(response)[..]);
+        assert_eq!(vec![2, 3], client.matcher().pages_requested());
+
+        assert!(search(0, 4, 3).await.is_err());
+        client.close();
+        driver_hdl.await.unwrap().unwrap();
+    }
+
+    #[tokio::test]
+    async fn tx_search_chunked_stops_after_a_full_last_page() {
+        let fixture = fixture("tx_search_no_prove");
+        let matcher = PagedTxSearchMatcher::new(&fixture).with_max_per_page(4);
+        let (client, driver) = MockClient::new(matcher);
+        let driver_hdl = tokio::spawn(async move { driver.run().await });
+
+        // The 8 results fill exactly two of the node's pages, so there is no
+        // third page to request, even though more results were asked for.
+        let response = client
+            .tx_search_chunked(
+                Query::from(EventType::Tx),
+                false,
+                1,
+                10,
+                crate::Order::Ascending,
+                4,
+            )
+            .await
+            .unwrap();
+        assert_eq!(8, response.total_count);
+        assert_eq!(8, response.txs.len());
+        assert_eq!(vec![1, 2], client.matcher().pages_requested());
+
+        // Pages past the last are rejected, as by Tendermint.
+        let err = client
+            .tx_search(
+                Query::from(EventType::Tx),
+                false,
+                3,
+                4,
+                crate::Order::Ascending,
+            )
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("page should be within [1, 2] range, given 3"),
+            "unexpected error: {}",
+            err
+        );
+
+        client.close();
+        driver_hdl.await.unwrap().unwrap();
+    }
+
+    #[tokio::test]
+    async fn tx_search_per_page_is_validated() {
+        let fixture = fixture("tx_search_no_prove");
+        let (client, _driver) = MockClient::new(PagedTxSearchMatcher::new(&fixture));
+        let search = |per_page| {
+            client.tx_search(
+                Query::from(EventType::Tx),
+                false,
+                1,
+                per_page,
+                crate::Order::Ascending,
+            )
+        };
+
+        for &per_page in &[0, 101] {
+            let err = search(per_page).await.unwrap_err();
+            assert!(
+                err.to_string().contains(&format!(
+                    "per_page must be between 1 and 100, but was {}",
+                    per_page
+                )),
+                "unexpected error: {}",
+                err
+            );
+        }
+        assert!(client.matcher().pages_requested().is_empty());
+
+        search(100).await.unwrap();
+        assert_eq!(vec![1], client.matcher().pages_requested());
+    }
+
+    #[tokio::test]
+    async fn validators_page_limit_is_enforced() {
+        // The node reports more validators than it ever returns.
+        let mut fixture: serde_json::Value = serde_json::from_str(&fixture("validators")).unwrap();
+        fixture["result"]["total"] = "1000001".into();
+        let client = counting_transport(vec![(Method::Validators, Ok(fixture.to_string()))]);
+
+        let err = client.all_validators(42_u32, 3).await.unwrap_err();
+        assert!(
+            err.to_string().contains("fetched 3 pages of validators"),
+            "unexpected error: {}",
+            err
+        );
+        assert_eq!(3, transport_requests(&client).len());
+
+        client.clear_request_durations();
+        client.validators(42_u32, Paging::All).await.unwrap_err();
+        assert_eq!(
+            crate::paging::DEFAULT_MAX_PAGES,
+            transport_requests(&client).len()
+        );
+    }
+
+    // Responds to `/validators` with the validators fixture, at the requested
+    // height, and to other requests via `inner`.
+    fn validators_at_height(inner: MockRequestMethodMatcher) -> HeightRecordingMatcher {
+        let validators: serde_json::Value = serde_json::from_str(&fixture("validators")).unwrap();
+        HeightRecordingMatcher::new(inner, Method::Validators).responding_with(move |params| {
+            let mut result = validators["result"].clone();
+            result["block_height"] = params["height"].clone();
+            result
+        })
+    }
+
+    #[tokio::test]
+    async fn latest_validators_resolves_height_from_status() {
+        let matcher = validators_at_height(
+            MockRequestMethodMatcher::default().map(Method::Status, Ok(fixture("status"))),
+        );
+        let heights = matcher.heights();
+        let (client, driver) = MockClient::new(matcher);
+        let driver_hdl = tokio::spawn(async move { driver.run().await });
+
+        let response = client.latest_validators(Paging::Default).await.unwrap();
+        assert_eq!(Height::from(410744_u32), response.block_height);
+        assert!(!response.validators.is_empty());
+        assert_eq!(vec![serde_json::json!("410744")], *heights.lock().unwrap());
+
+        client.close();
+        driver_hdl.await.unwrap().unwrap();
+    }
+
+    #[tokio::test]
+    async fn light_block_is_assembled_from_components() {
+        let matcher = validators_at_height(
+            MockRequestMethodMatcher::default().map(Method::Commit, Ok(fixture("commit"))),
+        );
+        let heights = matcher.heights();
+        let (client, driver) = MockClient::new(matcher);
+        let driver_hdl = tokio::spawn(async move { driver.run().await });
+
+        let light_block = client.light_block(10_u32).await.unwrap();
+        assert_eq!(Height::from(10_u32), light_block.height());
+        assert_eq!(65, light_block.validators.validators().len());
+        assert_eq!(65, light_block.next_validators.validators().len());
+        // The validator sets are fetched concurrently.
+        let mut heights = heights.lock().unwrap().clone();
+        heights.sort_by(|a, b| a.as_str().cmp(&b.as_str()));
+        assert_eq!(
+            vec![serde_json::json!("10"), serde_json::json!("11")],
+            heights
+        );
+
+        client.close();
+        driver_hdl.await.unwrap().unwrap();
+    }
+
+    #[tokio::test]
+    async fn commits_are_streamed_in_order_of_height() {
+        let commit: serde_json::Value = serde_json::from_str(&fixture("commit")).unwrap();
+        let matcher = HeightRecordingMatcher::new(Default::default(), Method::Commit)
+            .responding_with(move |params| {
+                let mut result = commit["result"].clone();
+                result["signed_header"]["header"]["height"] = params["height"].clone();
+                result["signed_header"]["commit"]["height"] = params["height"].clone();
+                result
+            });
+        let (client, _driver) = MockClient::new(matcher);
+
+        let heights: Vec<_> = client
+            .commits(3_u32, 7_u32, 3)
+            .unwrap()
+            .map(|commit| commit.unwrap().signed_header.header.height.value())
+            .collect()
+            .await;
+        assert_eq!(vec![3, 4, 5, 6, 7], heights);
+
+        let single: Vec<_> = client.commits(5_u32, 5_u32, 1).unwrap().collect().await;
+        assert_eq!(1, single.len());
+        assert!(client.commits(7_u32, 3_u32, 1).is_err());
+    }
+
+    #[tokio::test]
+    async fn blockchain_headers_in_ascending_order() {
+        let fixture: serde_json::Value = serde_json::from_str(&fixture("blockchain")).unwrap();
+        let meta = fixture["result"]["block_metas"][0].clone();
+        // Responds as a node whose latest block is at height 35 would, with
+        // at most 20 headers (the highest first).
+        let matcher = HeightRecordingMatcher::new(Default::default(), Method::Blockchain)
+            .recording(&["minHeight", "maxHeight"])
+            .responding_with(move |params| {
+                let param = |name: &str| -> u64 { params[name].as_str().unwrap().parse().unwrap() };
+                let max = param("maxHeight").min(35);
+                let min = param("minHeight").max(max.saturating_sub(19));
+                let metas: Vec<_> = (min..=max)
+                    .rev()
+                    .map(|height| {
+                        let mut meta = meta.clone();
+                        meta["header"]["height"] = height.to_string().into();
+                        meta
+                    })
+                    .collect();
+                serde_json::json!({ "last_height": "35", "block_metas": metas })
+            });
+        let ranges = matcher.heights();
+        let (client, _driver) = MockClient::new(matcher);
+        let heights = |response: crate::endpoint::blockchain::Response| -> Vec<u64> {
+            response
+                .block_metas
+                .iter()
+                .map(|meta| meta.header.height.value())
+                .collect()
+        };
+
+        // Spanning two windows.
+        let response = client.blockchain_asc(5_u32, 30_u32).await.unwrap();
+        assert_eq!(35, response.last_height.value());
+        assert_eq!((5..=30).collect::<Vec<_>>(), heights(response));
+        assert_eq!(
+            vec![
+                serde_json::json!(["5", "24"]),
+                serde_json::json!(["25", "30"])
+            ],
+            *ranges.lock().unwrap()
+        );
+
+        // Beyond the latest block.
+        ranges.lock().unwrap().clear();
+        let response = client.blockchain_asc(10_u32, 100_u32).await.unwrap();
+        assert_eq!((10..=35).collect::<Vec<_>>(), heights(response));
+        assert_eq!(
+            vec![
+                serde_json::json!(["10", "29"]),
+                serde_json::json!(["30", "49"])
+            ],
+            *ranges.lock().unwrap()
+        );
+
+        assert!(client.blockchain_asc(7_u32, 3_u32).await.is_err());
+    }
+
+    fn abci_query_response(code: u32, value: &[u8]) -> String {
+        format!(
+            r#"{{"jsonrpc":"2.0","id":"","result":{{"response":{{"code":{},"log":"","info":"","index":"0","key":"","value":"{}","height":"10","codespace":""}}}}}}"#,
+            code,
+            String::from_utf8(subtle_encoding::base64::encode(value)).unwrap()
+        )
+    }
+
+    // Fails `/abci_query` requests for proofs with the given error, recording
+    // whether each request asked for a proof.
+    struct ProofFailingMatcher {
+        error: Error,
+        proves: std::sync::Mutex<Vec<bool>>,
+    }
+
+    impl MockRequestMatcher for ProofFailingMatcher {
+        fn response_for<R>(&self, request: R) -> Option<Result<R::Response>>
+        where
+            R: Request,
+        {
+            let params = serde_json::to_value(&request).unwrap();
+            let prove = params["prove"].as_bool().unwrap();
+            self.proves.lock().unwrap().push(prove);
+            if prove {
+                return Some(Err(self.error.clone()));
+            }
+            Some(R::Response::from_string(abci_query_response(0, b"value")))
+        }
+    }
+
+    #[tokio::test]
+    async fn abci_query_falls_back_without_proof() {
+        let error = Error::server_error("failed to generate proof: key not found in tree");
+        let (client, driver) = MockClient::new(ProofFailingMatcher {
+            error: error.clone(),
+            proves: Default::default(),
+        });
+        let driver_hdl = tokio::spawn(async move { driver.run().await });
+
+        let query = client
+            .abci_query_with_proof(None, "balance/addr", None, true)
+            .await
+            .unwrap();
+        assert!(query.proof_dropped);
+        assert_eq!(b"value".to_vec(), query.response.value);
+        assert!(query.response.proof.is_none());
+        assert_eq!(vec![true, false], *client.matcher().proves.lock().unwrap());
+
+        // Without the fallback, the error is returned as-is.
+        let err = client
+            .abci_query_with_proof(None, "balance/addr", None, false)
+            .await
+            .unwrap_err();
+        assert_eq!(error, err);
+        client.close();
+        driver_hdl.await.unwrap().unwrap();
+
+        // Errors unrelated to proofs are not retried.
+        let (client, _driver) = MockClient::new(ProofFailingMatcher {
+            error: Error::server_error("unknown query path"),
+            proves: Default::default(),
+        });
+        client
+            .abci_query_with_proof(None, "balance/addr", None, true)
+            .await
+            .unwrap_err();
+        assert_eq!(vec![true], *client.matcher().proves.lock().unwrap());
+    }
+
+    #[tokio::test]
+    async fn abci_query_decoded_json() {
+        #[derive(Debug, PartialEq, serde::Deserialize)]
+        struct Balance {
+            denom: String,
+            amount: u64,
+        }
+
+        let response = abci_query_response(0, br#"{"denom":"fra","amount":42}"#);
+        let matcher = MockRequestMethodMatcher::default().map(Method::AbciQuery, Ok(response));
+        let (client, driver) = MockClient::new(matcher);
+        let driver_hdl = tokio::spawn(async move { driver.run().await });
+
+        let balance: Balance = client
+            .abci_query_decoded(None, "balance/addr", None)
+            .await
+            .unwrap();
+        assert_eq!(
+            Balance {
+                denom: "fra".to_string(),
+                amount: 42
+            },
+            balance
+        );
+
+        client.close();
+        driver_hdl.await.unwrap().unwrap();
+    }
+
+    #[tokio::test]
+    async fn abci_query_decoded_proto() {
+        use prost::Message;
+        use tendermint_proto::version::Consensus;
+
+        let expected = Consensus { block: 11, app: 1 };
+        let mut value = Vec::new();
+        expected.encode(&mut value).unwrap();
+        let matcher = MockRequestMethodMatcher::default()
+            .map(Method::AbciQuery, Ok(abci_query_response(0, &value)));
+        let (client, driver) = MockClient::new(matcher);
+        let driver_hdl = tokio::spawn(async move { driver.run().await });
+
+        let consensus: Consensus = client
+            .abci_query_decoded_proto(None, "version", None)
+            .await
+            .unwrap();
+        assert_eq!(expected, consensus);
+
+        client.close();
+        driver_hdl.await.unwrap().unwrap();
+    }
+
+    #[tokio::test]
+    async fn abci_query_decoded_fails_for_failed_query() {
+        let matcher = MockRequestMethodMatcher::default()
+            .map(Method::AbciQuery, Ok(abci_query_response(1, b"{}")));
+        let (client, driver) = MockClient::new(matcher);
+        let driver_hdl = tokio::spawn(async move { driver.run().await });
+
+        let result: Result<serde_json::Value> =
+            client.abci_query_decoded(None, "balance/addr", None).await;
+        assert!(result.is_err());
+
+        client.close();
+        driver_hdl.await.unwrap().unwrap();
+    }
+
+    #[tokio::test]
+    async fn sequential_requests_share_deadline() {
+        use crate::endpoint::broadcast::tx_sync;
+
+        time::pause();
+        // Each request takes roughly 100ms.
+        let client = SlowBroadcastClient::default();
+        let deadline = Instant::now() + Duration::from_millis(150);
+
+        let mut txs = txs(2).into_iter();
+        client
+            .perform_with_deadline(tx_sync::Request::new(txs.next().unwrap()), deadline)
+            .await
+            .unwrap();
+        let err = client
+            .perform_with_deadline(tx_sync::Request::new(txs.next().unwrap()), deadline)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("deadline elapsed"));
+        // We gave up at the deadline rather than waiting for the response.
+        assert!(Instant::now() < deadline + Duration::from_millis(40));
+    }
+
+    #[tokio::test]
+    async fn query_condition_limit_is_opt_in() {
+        let fixture = fixture("tx_search_no_prove");
+        let query = Query::from(EventType::Tx)
+            .and_eq("transfer.sender", "AddrA")
+            .and_eq("transfer.recipient", "AddrB");
+
+        // Without a limit, any number of conditions is accepted.
+        let (client, driver) = MockClient::new(PagedTxSearchMatcher::new(&fixture));
+        let driver_hdl = tokio::spawn(async move { driver.run().await });
+        assert_eq!(None, client.max_query_conditions());
+        client.subscribe(query.clone()).await.unwrap();
+        client.unsubscribe(query.clone()).await.unwrap();
+        client
+            .tx_search(query.clone(), false, 1, 10, crate::Order::Ascending)
+            .await
+            .unwrap();
+        client.close();
+        driver_hdl.await.unwrap().unwrap();
+
+        let (client, driver) = MockClient::new(PagedTxSearchMatcher::new(&fixture));
+        let client = client.with_max_query_conditions(2);
+        let driver_hdl = tokio::spawn(async move { driver.run().await });
+        for err in vec![
+            client.subscribe(query.clone()).await.unwrap_err(),
+            client.unsubscribe(query.clone()).await.unwrap_err(),
+            client
+                .tx_search(query.clone(), false, 1, 10, crate::Order::Ascending)
+                .await
+                .unwrap_err(),
+        ] {
+            assert!(
+                err.to_string()
+                    .contains("query has 3 conditions, exceeding the maximum of 2"),
+                "unexpected error: {}",
+                err
+            );
+        }
+        assert!(client.matcher().pages_requested().is_empty());
+        client.close();
+        driver_hdl.await.unwrap().unwrap();
+    }
+
+    #[tokio::test]
+    async fn wait_until_healthy_polls_with_paused_clock() {
+        let matcher = FlakyHealthMatcher {
+            failures: AtomicUsize::new(3),
+            health: fixture("health"),
+        };
+        let (client, driver) = MockClient::new(matcher);
+        let driver_hdl = tokio::spawn(async move { driver.run().await });
+
+        time::pause();
+        let start = Instant::now();
+        client
+            .wait_until_healthy(Duration::from_secs(5))
+            .await
+            .unwrap();
+        // Three failed attempts means we must have slept three times.
+        let elapsed = start.elapsed();
+        assert!(elapsed >= HEALTH_POLL_INTERVAL * 3);
+        assert!(elapsed < HEALTH_POLL_INTERVAL * 4);
+        time::resume();
+
+        client.close();
+        driver_hdl.await.unwrap().unwrap();
+    }
+
+    #[tokio::test]
+    async fn poll_until_retries_until_a_value_is_produced() {
+        let matcher = FlakyHealthMatcher {
+            failures: AtomicUsize::new(2),
+            health: fixture("health"),
+        };
+        let (client, driver) = MockClient::new(matcher);
+        let driver_hdl = tokio::spawn(async move { driver.run().await });
+
+        time::pause();
+        let start = Instant::now();
+        let interval = Duration::from_millis(100);
+        let attempts = &AtomicUsize::new(0);
+        let mock = &client;
+        let attempt = client
+            .poll_until(
+                move || async move {
+                    let attempt = attempts.fetch_add(1, Ordering::SeqCst) + 1;
+                    Ok(mock.health().await.ok().map(|_| attempt))
+                },
+                Duration::from_secs(5),
+                interval,
+            )
+            .await
+            .unwrap();
+        // The mock only succeeds on the third attempt.
+        assert_eq!(3, attempt);
+        let elapsed = start.elapsed();
+        assert!(elapsed >= interval * 2);
+        assert!(elapsed < interval * 3);
+
+        // Polling gives up once the timeout has elapsed...
+        let err = client
+            .poll_until(
+                || async { Ok(None::<()>) },
+                Duration::from_millis(250),
+                interval,
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(ErrorKind::Timeout, err.kind());
+        assert!(err.to_string().contains("timed out polling after 250ms"));
+
+        // ...or as soon as the operation fails.
+        let attempts = &AtomicUsize::new(0);
+        let err = client
+            .poll_until(
+                move || async move {
+                    attempts.fetch_add(1, Ordering::SeqCst);
+                    mock.perform(crate::endpoint::status::Request).await?;
+                    Ok(Some(()))
+                },
+                Duration::from_secs(5),
+                interval,
+            )
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("no matching response"));
+        assert_eq!(1, attempts.load(Ordering::SeqCst));
+        time::resume();
+
+        client.close();
+        driver_hdl.await.unwrap().unwrap();
+    }
+
+    #[tokio::test]
+    async fn wait_until_healthy_times_out_with_paused_clock() {
+        let matcher = FlakyHealthMatcher {
+            failures: AtomicUsize::new(usize::MAX),
+            health: fixture("health"),
+        };
+        let (client, driver) = MockClient::new(matcher);
+        let driver_hdl = tokio::spawn(async move { driver.run().await });
+
+        time::pause();
+        let start = Instant::now();
+        let timeout = Duration::from_secs(1);
+        assert!(client.wait_until_healthy(timeout).await.is_err());
+        assert!(start.elapsed() <= timeout);
+        time::resume();
+
+        client.close();
+        driver_hdl.await.unwrap().unwrap();
+    }
+
+    #[tokio::test]
+    async fn zero_height_is_rejected() {
+        let matcher = MockRequestMethodMatcher::default()
+            .map(Method::Block, Ok(fixture("block")))
+            .map(Method::BlockResults, Ok(fixture("block_results")))
+            .map(Method::Commit, Ok(fixture("commit")));
+        let (client, driver) = MockClient::new(matcher);
+        let driver_hdl = tokio::spawn(async move { driver.run().await });
+
+        let err = client.block(Height::from(0_u32)).await.unwrap_err();
+        assert!(err.to_string().contains("use `latest_block`"));
+        let err = client.block_results(0_u32).await.unwrap_err();
+        assert!(err.to_string().contains("use `latest_block_results`"));
+        let err = client.commit(0_u32).await.unwrap_err();
+        assert!(err.to_string().contains("use `latest_commit`"));
+
+        // The latest height is only ever requested explicitly.
+        assert_eq!(
+            Height::from(10_u32),
+            client.latest_block().await.unwrap().block.header.height
+        );
+        assert_eq!(
+            Height::from(1814_u32),
+            client.latest_block_results().await.unwrap().height
+        );
+        client.latest_commit().await.unwrap();
+        client.block(1_u32).await.unwrap();
+
+        client.close();
+        driver_hdl.await.unwrap().unwrap();
+    }
+
+    #[tokio::test]
+    async fn mock_client_block_evidence() {
+        let matcher = MockRequestMethodMatcher::default()
+            .map(Method::Block, Ok(fixture("block_with_evidences")));
+        let (client, driver) = MockClient::new(matcher);
+        let driver_hdl = tokio::spawn(async move { driver.run().await });
+
+        let evidence = client.block_evidence(10_u32).await.unwrap();
+        assert_eq!(1, evidence.len());
+        assert!(matches!(
+            evidence[0],
+            tendermint::evidence::Evidence::DuplicateVote(_)
+        ));
+
+        client.close();
+        driver_hdl.await.unwrap().unwrap();
+    }
+}
@@ -0,0 +1,195 @@
+//! Application state transitions, as assembled by
+//! [`crate::Client::app_hash_transition`].
+
+use crate::endpoint::{block, block_results};
+use crate::{Error, Result};
+use tendermint::block::{Height, Id};
+use tendermint::{AppHash, Hash};
+
+/// The transition of the application's state effected by executing a block,
+/// along with the results of checking that the block, the following block
+/// and the results of executing the block (as reported by `/block_results`)
+/// are consistent with one another.
+///
+/// Since the application's state after executing a block is only recorded in
+/// the header of the following block, the transition is authenticated by the
+/// following block's header chaining to the block (via its `last_block_id`),
+/// while the reported results are authenticated by the following block's
+/// header committing to them (via its `last_results_hash`).
+#[derive(Clone, Debug)]
+pub struct AppHashTransition {
+    /// The height of the block
+    pub height: Height,
+
+    /// The ID of the block
+    pub block_id: Id,
+
+    /// The application's state before executing the block, as recorded in
+    /// the block's header
+    pub app_hash_before: AppHash,
+
+    /// The application's state after executing the block, as recorded in the
+    /// header of the following block
+    pub app_hash_after: AppHash,
+
+    /// The hash of the results of executing the block, as computed from its
+    /// `/block_results` (see [`block_results::Response::results_hash`])
+    pub results_hash: Hash,
+
+    /// Whether the header of the following block refers to the block as its
+    /// predecessor
+    pub chains_to_next: bool,
+
+    /// Whether the header of the following block commits to the results of
+    /// executing the block (i.e. `results_hash` is its `last_results_hash`)
+    pub results_match: bool,
+}
+
+impl AppHashTransition {
+    /// Check the given block, the block following it and the results of
+    /// executing the block against one another.
+    ///
+    /// Fails if the responses are not for consecutive heights.
+    pub fn from_responses(
+        block: block::Response,
+        next: block::Response,
+        results: &block_results::Response,
+    ) -> Result<Self> {
+        let header = block.block.header;
+        let next_header = next.block.header;
+        if next_header.height.value() != header.height.value() + 1
+            || results.height != header.height
+        {
+            return Err(Error::client_internal_error(format!(
+                "expected blocks at heights {} and {}, and results at height {}, \
+                 but got blocks at heights {} and {}, and results at height {}",
+                header.height,
+                header.height.increment(),
+                header.height,
+                header.height,
+                next_header.height,
+                results.height
+            )));
+        }
+        let results_hash = results.results_hash();
+        Ok(Self {
+            height: header.height,
+            chains_to_next: next_header.last_block_id.as_ref() == Some(&block.block_id),
+            block_id: block.block_id,
+            app_hash_before: header.app_hash,
+            app_hash_after: next_header.app_hash,
+            results_match: next_header.last_results_hash == Some(results_hash),
+            results_hash,
+        })
+    }
+
+    /// Whether the block, the following block and the results of executing
+    /// the block are consistent with one another.
+    pub fn is_consistent(&self) -> bool {
+        self.chains_to_next && self.results_match
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::client::{Client, MockClient, MockRequestMatcher};
+    use crate::{Method, Request, Response};
+
+    fn fixture(name: &str) -> serde_json::Value {
+        serde_json::from_str(&crate::test_support::fixture(name)).unwrap()
+    }
+
+    // The hash of the results in the `block_results` fixture.
+    const RESULTS_HASH: &str = "C7374859987292154D5917339F39A66E8032C7AD03BAAE5B24A43FB3EC234B28";
+
+    // Responds with the `block` fixture (at height 10) for height 10, with
+    // the given block for height 11, and with the `block_results` fixture
+    // for height 10.
+    struct TripleMatcher {
+        next: serde_json::Value,
+        results: serde_json::Value,
+    }
+
+    impl TripleMatcher {
+        // A consistent triple.
+        fn new() -> Self {
+            let block = fixture("block");
+            let mut next = block.clone();
+            let header = &mut next["result"]["block"]["header"];
+            header["height"] = "11".into();
+            header["last_block_id"] = block["result"]["block_id"].clone();
+            header["last_results_hash"] = RESULTS_HASH.into();
+            header["app_hash"] = "0000000000000001".into();
+            let mut results = fixture("block_results");
+            results["result"]["height"] = "10".into();
+            Self { next, results }
+        }
+    }
+
+    impl MockRequestMatcher for TripleMatcher {
+        fn response_for<R>(&self, request: R) -> Option<Result<R::Response>>
+        where
+            R: Request,
+        {
+            let method = request.method();
+            let request: serde_json::Value = serde_json::from_str(&request.into_json()).unwrap();
+            let response = match (method, request["params"]["height"].as_str()) {
+                (Method::Block, Some("10")) => fixture("block"),
+                (Method::Block, Some("11")) => self.next.clone(),
+                (Method::BlockResults, Some("10")) => self.results.clone(),
+                _ => return None,
+            };
+            Some(R::Response::from_string(response.to_string()))
+        }
+    }
+
+    #[tokio::test]
+    async fn consistent_transition() {
+        let (client, _driver) = MockClient::new(TripleMatcher::new());
+        let transition = client.app_hash_transition(10_u32).await.unwrap();
+        assert_eq!(10, transition.height.value());
+        assert_eq!(RESULTS_HASH, transition.results_hash.to_string());
+        assert_eq!(vec![0_u8; 8], transition.app_hash_before.value());
+        assert_eq!(
+            vec![0, 0, 0, 0, 0, 0, 0, 1_u8],
+            transition.app_hash_after.value()
+        );
+        assert!(transition.chains_to_next);
+        assert!(transition.results_match);
+        assert!(transition.is_consistent());
+    }
+
+    #[tokio::test]
+    async fn inconsistent_transitions() {
+        // The results differ from those committed to by the next block.
+        let mut matcher = TripleMatcher::new();
+        matcher.results["result"]["txs_results"][1]["gasUsed"] = "99165".into();
+        let (client, _driver) = MockClient::new(matcher);
+        let transition = client.app_hash_transition(10_u32).await.unwrap();
+        assert!(transition.chains_to_next);
+        assert!(!transition.results_match);
+        assert!(!transition.is_consistent());
+
+        // The next block follows a different block.
+        let mut matcher = TripleMatcher::new();
+        matcher.next["result"]["block"]["header"]["last_block_id"] =
+            fixture("block")["result"]["block"]["header"]["last_block_id"].clone();
+        let (client, _driver) = MockClient::new(matcher);
+        let transition = client.app_hash_transition(10_u32).await.unwrap();
+        assert!(!transition.chains_to_next);
+        assert!(transition.results_match);
+        assert!(!transition.is_consistent());
+
+        // The results are for a different height.
+        let mut matcher = TripleMatcher::new();
+        matcher.results["result"]["height"] = "11".into();
+        let (client, _driver) = MockClient::new(matcher);
+        let err = client.app_hash_transition(10_u32).await.unwrap_err();
+        assert!(
+            err.to_string().contains("results at height 11"),
+            "unexpected error: {}",
+            err
+        );
+    }
+}
@@ -0,0 +1,290 @@
+//! A client decorator which caches responses for immutable heights.
+
+use crate::client::{Client, Subscription, SubscriptionClient};
+use crate::query::Query;
+use crate::{Error, Method, Result, SimpleRequest};
+use async_trait::async_trait;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+
+/// A [`Client`] which caches the responses to `/block`, `/block_results` and
+/// `/commit` requests for explicit heights, since these never change once the
+/// block at that height has been committed.
+///
+/// Requests for the latest height (e.g. [`Client::latest_block`]) are never
+/// cached, and neither are any other requests, nor non-canonical commits
+/// (i.e. those for the latest block, which may still change). Up to the configured number of
+/// responses are retained, with the least recently used being evicted first.
+/// Errors are not cached. Subscriptions are passed through to the wrapped
+/// client.
+///
+/// Clones share the same cache.
+///
+/// ## Examples
+///
+/// ```rust,ignore
+/// use tendermint_rpc::{CachingClient, Client, HttpClient};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let client = CachingClient::new(HttpClient::new("http://127.0.0.1:26657").unwrap(), 100);
+///
+///     // Only the first of these requests is sent to the node.
+///     let block = client.block(5_u32).await.unwrap();
+///     let same_block = client.block(5_u32).await.unwrap();
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct CachingClient<C> {
+    inner: C,
+    cache: Arc<Mutex<Cache>>,
+}
+
+impl<C> CachingClient<C> {
+    /// Wrap the given client, caching up to `capacity` responses.
+    pub fn new(inner: C, capacity: usize) -> Self {
+        Self {
+            inner,
+            cache: Arc::new(Mutex::new(Cache::new(capacity))),
+        }
+    }
+
+    /// The number of responses currently cached.
+    pub fn cached_responses(&self) -> usize {
+        self.cache.lock().unwrap().entries.len()
+    }
+
+    /// Discard all cached responses.
+    pub fn clear_cache(&self) {
+        self.cache.lock().unwrap().clear();
+    }
+
+    /// A reference to the wrapped client.
+    pub fn inner(&self) -> &C {
+        &self.inner
+    }
+
+    /// Unwrap the underlying client.
+    pub fn into_inner(self) -> C {
+        self.inner
+    }
+}
+
+#[cfg_attr(not(feature = "unsend"), async_trait)]
+#[cfg_attr(feature = "unsend", async_trait(?Send))]
+impl<C> Client for CachingClient<C>
+where
+    C: Client + Send + Sync,
+{
+    fn max_query_conditions(&self) -> Option<usize> {
+        self.inner.max_query_conditions()
+    }
+
+    async fn perform<R>(&self, request: R) -> Result<R::Response>
+    where
+        R: SimpleRequest,
+    {
+        let key = match cache_key(&request)? {
+            Some(key) => key,
+            None => return self.inner.perform(request).await,
+        };
+        let cached = self.cache.lock().unwrap().get(&key);
+        if let Some(json) = cached {
+            return serde_json::from_str(&json).map_err(Error::parse_error);
+        }
+        let response = self.inner.perform(request).await?;
+        let json = serde_json::to_value(&response).map_err(Error::parse_error)?;
+        if is_immutable(&json) {
+            self.cache.lock().unwrap().insert(key, json.to_string());
+        }
+        Ok(response)
+    }
+}
+
+#[cfg_attr(not(feature = "unsend"), async_trait)]
+#[cfg_attr(feature = "unsend", async_trait(?Send))]
+impl<C> SubscriptionClient for CachingClient<C>
+where
+    C: SubscriptionClient + Send + Sync,
+{
+    async fn subscribe(&self, query: Query) -> Result<Subscription> {
+        self.inner.subscribe(query).await
+    }
+
+    async fn unsubscribe(&self, query: Query) -> Result<()> {
+        self.inner.unsubscribe(query).await
+    }
+
+    fn close(self) -> Result<()> {
+        self.inner.close()
+    }
+}
+
+/// Identifies a cacheable request by its method and parameters.
+type CacheKey = (Method, String);
+
+/// The cache key for the given request, if its response is immutable (i.e.
+/// it is a height-specific read request for an explicit height).
+fn cache_key<R: SimpleRequest>(request: &R) -> Result<Option<CacheKey>> {
+    match request.method() {
+        Method::Block | Method::BlockResults | Method::Commit => (),
+        _ => return Ok(None),
+    }
+    let params = serde_json::to_value(request).map_err(Error::parse_error)?;
+    if params.get("height").map_or(true, |h| h.is_null()) {
+        return Ok(None);
+    }
+    Ok(Some((request.method(), params.to_string())))
+}
+
+/// Whether the given serialized response is final. Commits for the latest
+/// block are not canonical, as the commit included in the next block may
+/// contain different signatures.
+fn is_immutable(response: &serde_json::Value) -> bool {
+    response.get("canonical") != Some(&serde_json::Value::Bool(false))
+}
+
+/// A least recently used cache of serialized responses.
+#[derive(Debug)]
+struct Cache {
+    capacity: usize,
+    // Each response, along with the time at which it was last used.
+    entries: HashMap<CacheKey, (u64, String)>,
+    // Keys by the time at which they were last used.
+    order: BTreeMap<u64, CacheKey>,
+    clock: u64,
+}
+
+impl Cache {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: HashMap::new(),
+            order: BTreeMap::new(),
+            clock: 0,
+        }
+    }
+
+    fn get(&mut self, key: &CacheKey) -> Option<String> {
+        let now = self.tick();
+        let (last_used, json) = self.entries.get_mut(key)?;
+        let previous = std::mem::replace(last_used, now);
+        let json = json.clone();
+        self.order.remove(&previous);
+        self.order.insert(now, key.clone());
+        Some(json)
+    }
+
+    fn insert(&mut self, key: CacheKey, json: String) {
+        if self.capacity == 0 {
+            return;
+        }
+        let now = self.tick();
+        if let Some((previous, _)) = self.entries.insert(key.clone(), (now, json)) {
+            self.order.remove(&previous);
+        }
+        self.order.insert(now, key);
+        if self.entries.len() > self.capacity {
+            let oldest = *self.order.keys().next().unwrap();
+            if let Some(evicted) = self.order.remove(&oldest) {
+                self.entries.remove(&evicted);
+            }
+        }
+    }
+
+    fn tick(&mut self) -> u64 {
+        self.clock += 1;
+        self.clock
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+        self.order.clear();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::client::{MockClient, MockRequestMethodMatcher, TimedClient};
+    use crate::test_support::{counting_transport, fixture};
+
+    fn caching_client(
+        capacity: usize,
+    ) -> CachingClient<TimedClient<MockClient<MockRequestMethodMatcher>>> {
+        let transport = counting_transport(vec![
+            (Method::Block, Ok(fixture("block"))),
+            (Method::Commit, Ok(fixture("commit"))),
+        ]);
+        CachingClient::new(transport, capacity)
+    }
+
+    fn transport_requests<C>(client: &CachingClient<TimedClient<C>>) -> Vec<Method> {
+        crate::test_support::transport_requests(client.inner())
+    }
+
+    #[tokio::test]
+    async fn non_canonical_commits_are_not_cached() {
+        let commit = fixture("commit").replace("\"canonical\": true", "\"canonical\": false");
+        let client = CachingClient::new(counting_transport(vec![(Method::Commit, Ok(commit))]), 10);
+        assert!(!client.commit(5_u32).await.unwrap().canonical);
+        client.commit(5_u32).await.unwrap();
+        assert_eq!(0, client.cached_responses());
+        assert_eq!(
+            vec![Method::Commit, Method::Commit],
+            transport_requests(&client)
+        );
+    }
+
+    #[tokio::test]
+    async fn responses_for_explicit_heights_are_cached() {
+        let client = caching_client(10);
+        let block = client.block(5_u32).await.unwrap();
+        let cached = client.block(5_u32).await.unwrap();
+        assert_eq!(block.block_id, cached.block_id);
+        assert_eq!(block.block.header, cached.block.header);
+        assert_eq!(vec![Method::Block], transport_requests(&client));
+
+        // Other heights are cached separately.
+        client.block(6_u32).await.unwrap();
+        assert_eq!(2, transport_requests(&client).len());
+        assert_eq!(2, client.cached_responses());
+
+        // Requests for the latest height always reach the transport.
+        client.latest_block().await.unwrap();
+        client.latest_block().await.unwrap();
+        assert_eq!(4, transport_requests(&client).len());
+        assert_eq!(2, client.cached_responses());
+
+        client.clear_cache();
+        client.block(5_u32).await.unwrap();
+        assert_eq!(5, transport_requests(&client).len());
+    }
+
+    #[tokio::test]
+    async fn least_recently_used_responses_are_evicted() {
+        let client = caching_client(2);
+        client.block(5_u32).await.unwrap();
+        client.commit(5_u32).await.unwrap();
+        client.block(5_u32).await.unwrap();
+        client.block(6_u32).await.unwrap();
+        assert_eq!(2, client.cached_responses());
+        assert_eq!(3, transport_requests(&client).len());
+
+        // The commit was evicted, but the more recently used block was not.
+        client.block(5_u32).await.unwrap();
+        client.commit(5_u32).await.unwrap();
+        assert_eq!(
+            vec![Method::Block, Method::Commit, Method::Block, Method::Commit],
+            transport_requests(&client)
+        );
+
+        // Errors are not cached, and nor is anything with no capacity.
+        client.block_results(5_u32).await.unwrap_err();
+        let client = caching_client(0);
+        client.block(5_u32).await.unwrap();
+        client.block(5_u32).await.unwrap();
+        assert_eq!(0, client.cached_responses());
+        assert_eq!(2, transport_requests(&client).len());
+    }
+}
@@ -0,0 +1,174 @@
+//! A client decorator which reads ABCI application state as of the last
+//! finalized block.
+
+use crate::client::pinned::{specifies_height, with_height};
+use crate::client::{Client, Subscription, SubscriptionClient};
+use crate::query::Query;
+use crate::{Error, Method, Result, SimpleRequest};
+use async_trait::async_trait;
+use std::convert::TryFrom;
+use tendermint::block::Height;
+
+/// A [`Client`] which sends `/abci_query` requests that do not specify a
+/// height at the height preceding the latest block height (as reported by
+/// the node's `/status`), rather than leaving the node to query its latest
+/// state, which some nodes report before it is stable.
+///
+/// This makes for stable reads at the cost of an additional `/status`
+/// request per query, and of reading state one block behind. Queries which
+/// explicitly specify a height are sent as-is, as are queries made while the
+/// node's latest block is the first. All other requests, and subscriptions,
+/// are passed through to the wrapped client.
+///
+/// ## Examples
+///
+/// ```rust,ignore
+/// use tendermint_rpc::{Client, FinalizedQueryClient, HttpClient};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let client = FinalizedQueryClient::new(HttpClient::new("http://127.0.0.1:26657").unwrap());
+///
+///     // Queries the application's state as of the block before the latest.
+///     let response = client
+///         .abci_query(Some("/store/bank/key".parse().unwrap()), b"key".to_vec(), None, false)
+///         .await
+///         .unwrap();
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct FinalizedQueryClient<C> {
+    inner: C,
+}
+
+impl<C> FinalizedQueryClient<C> {
+    /// Wrap the given client.
+    pub fn new(inner: C) -> Self {
+        Self { inner }
+    }
+
+    /// A reference to the wrapped client.
+    pub fn inner(&self) -> &C {
+        &self.inner
+    }
+
+    /// Unwrap the underlying client.
+    pub fn into_inner(self) -> C {
+        self.inner
+    }
+}
+
+impl<C> FinalizedQueryClient<C>
+where
+    C: Client + Sync,
+{
+    /// The height of the last finalized block, i.e. the one preceding the
+    /// node's latest block, if any.
+    async fn finalized_height(&self) -> Result<Option<Height>> {
+        let latest = self.inner.status().await?.sync_info.latest_block_height;
+        match latest.value().saturating_sub(1) {
+            0 => Ok(None),
+            h => Height::try_from(h)
+                .map(Some)
+                .map_err(|e| Error::client_internal_error(e.to_string())),
+        }
+    }
+}
+
+#[cfg_attr(not(feature = "unsend"), async_trait)]
+#[cfg_attr(feature = "unsend", async_trait(?Send))]
+impl<C> Client for FinalizedQueryClient<C>
+where
+    C: Client + Send + Sync,
+{
+    fn max_query_conditions(&self) -> Option<usize> {
+        self.inner.max_query_conditions()
+    }
+
+    async fn perform<R>(&self, request: R) -> Result<R::Response>
+    where
+        R: SimpleRequest,
+    {
+        if request.method() != Method::AbciQuery || specifies_height(&request)? {
+            return self.inner.perform(request).await;
+        }
+        let request = match self.finalized_height().await? {
+            Some(height) => with_height(request, height)?,
+            None => request,
+        };
+        self.inner.perform(request).await
+    }
+}
+
+#[cfg_attr(not(feature = "unsend"), async_trait)]
+#[cfg_attr(feature = "unsend", async_trait(?Send))]
+impl<C> SubscriptionClient for FinalizedQueryClient<C>
+where
+    C: SubscriptionClient + Send + Sync,
+{
+    async fn subscribe(&self, query: Query) -> Result<Subscription> {
+        self.inner.subscribe(query).await
+    }
+
+    async fn unsubscribe(&self, query: Query) -> Result<()> {
+        self.inner.unsubscribe(query).await
+    }
+
+    fn close(self) -> Result<()> {
+        self.inner.close()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::client::{MockClient, MockRequestMethodMatcher};
+    use crate::test_support::{fixture, HeightRecordingMatcher};
+
+    // Responds to `/status` requests with the given latest block height, and
+    // to `/abci_query` requests, recording the height parameter of each.
+    fn query_height_matcher(latest: u64) -> HeightRecordingMatcher {
+        let status = fixture("status").replace(
+            "\"latest_block_height\": \"410744\"",
+            &format!("\"latest_block_height\": \"{}\"", latest),
+        );
+        HeightRecordingMatcher::new(
+            MockRequestMethodMatcher::default()
+                .map(Method::Status, Ok(status))
+                .map(Method::AbciQuery, Ok(fixture("abci_query"))),
+            Method::AbciQuery,
+        )
+    }
+
+    #[tokio::test]
+    async fn height_less_queries_read_finalized_state() {
+        let matcher = query_height_matcher(410744);
+        let heights = matcher.heights();
+        let (client, _driver) = MockClient::new(matcher);
+        let client = FinalizedQueryClient::new(client);
+
+        client
+            .abci_query(None, b"key".to_vec(), None, false)
+            .await
+            .unwrap();
+        client
+            .abci_query(None, b"key".to_vec(), Some(Height::from(10_u32)), false)
+            .await
+            .unwrap();
+        assert_eq!(
+            vec![serde_json::json!("410743"), serde_json::json!("10")],
+            *heights.lock().unwrap()
+        );
+
+        // There is no finalized block before the first.
+        let matcher = query_height_matcher(1);
+        let heights = matcher.heights();
+        let (client, _driver) = MockClient::new(matcher);
+        let client = FinalizedQueryClient::new(client);
+        client
+            .abci_query(None, b"key".to_vec(), None, false)
+            .await
+            .unwrap();
+        assert_eq!(vec![serde_json::Value::Null], *heights.lock().unwrap());
+    }
+}
@@ -0,0 +1,318 @@
+//! Following the chain, either by polling (detecting reorganizations) or
+//! via a subscription (filling in any gaps).
+
+use crate::client::{Client, ClientStream, Subscription};
+use crate::endpoint::blockchain;
+use crate::event::EventData;
+use crate::{Error, Result};
+use futures::stream::{self, StreamExt};
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use tendermint::block::{Block, Height};
+use tendermint::Hash;
+
+/// The maximum number of recent heights a [`ChainFollower`] can track, which
+/// is the most block headers `/blockchain` returns at once.
+const MAX_WINDOW: u64 = blockchain::MAX_BLOCKCHAIN_HEADERS;
+
+/// A change in the chain observed by a [`ChainFollower`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FollowEvent {
+    /// A block was seen at a height for the first time.
+    NewBlock {
+        /// The height of the block
+        height: Height,
+        /// The hash of the block
+        hash: Hash,
+    },
+
+    /// The block at a previously seen height changed, i.e. the chain was
+    /// reorganized.
+    Reorg {
+        /// The height at which the block changed
+        height: Height,
+        /// The hash of the block previously seen at this height
+        old_hash: Hash,
+        /// The hash of the block now at this height
+        new_hash: Hash,
+    },
+}
+
+/// Follows the chain by polling a [`Client`], tracking the hash of the block
+/// at each of the most recent heights so as to detect when the block at a
+/// previously seen height changes.
+///
+/// Each call to [`ChainFollower::poll`] fetches the headers of the blocks at
+/// the most recent heights (10 by default, see [`ChainFollower::with_window`])
+/// and reports, in order of height, the blocks that were not seen before and
+/// those that changed since they were last seen.
+///
+/// ## Examples
+///
+/// ```rust,ignore
+/// use tendermint_rpc::{ChainFollower, FollowEvent, HttpClient};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let client = HttpClient::new("http://127.0.0.1:26657").unwrap();
+///     let mut follower = ChainFollower::new(client);
+///     loop {
+///         for event in follower.poll().await.unwrap() {
+///             if let FollowEvent::Reorg { height, .. } = event {
+///                 println!("Chain reorganized at height {}", height);
+///             }
+///         }
+///         tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+///     }
+/// }
+/// ```
+#[derive(Debug)]
+pub struct ChainFollower<C> {
+    client: C,
+    window: u64,
+    seen: BTreeMap<Height, Hash>,
+}
+
+impl<C> ChainFollower<C> {
+    /// Follow the chain via the given client.
+    pub fn new(client: C) -> Self {
+        Self {
+            client,
+            window: 10,
+            seen: BTreeMap::new(),
+        }
+    }
+
+    /// Check the given number of most recent heights (at most 20) for changes
+    /// on each poll.
+    ///
+    /// Defaults to 10.
+    pub fn with_window(mut self, heights: u64) -> Self {
+        self.window = heights.max(1).min(MAX_WINDOW);
+        self
+    }
+
+    /// Record the hash of the block at the given height, returning the
+    /// resulting event, if any.
+    ///
+    /// This is used by [`ChainFollower::poll`], but can also be used to feed
+    /// in blocks obtained by other means (e.g. via a subscription).
+    pub fn observe(&mut self, height: Height, hash: Hash) -> Option<FollowEvent> {
+        match self.seen.insert(height, hash) {
+            None => Some(FollowEvent::NewBlock { height, hash }),
+            Some(old_hash) if old_hash != hash => Some(FollowEvent::Reorg {
+                height,
+                old_hash,
+                new_hash: hash,
+            }),
+            Some(_) => None,
+        }
+    }
+
+    /// The hash of the block last seen at the given height, if it is still
+    /// being tracked.
+    pub fn hash_at(&self, height: Height) -> Option<Hash> {
+        self.seen.get(&height).copied()
+    }
+
+    /// Unwrap the underlying client.
+    pub fn into_inner(self) -> C {
+        self.client
+    }
+
+    // Stop tracking heights below the given one.
+    fn forget_below(&mut self, height: Height) {
+        self.seen = self.seen.split_off(&height);
+    }
+}
+
+impl<C> ChainFollower<C>
+where
+    C: Client + Sync,
+{
+    /// Fetch the headers of the most recent blocks, returning any new blocks
+    /// and reorganizations observed since the last poll, in order of height.
+    pub async fn poll(&mut self) -> Result<Vec<FollowEvent>> {
+        let latest = self.client.status().await?.sync_info.latest_block_height;
+        let min = Height::try_from(latest.value().saturating_sub(self.window - 1).max(1))
+            .map_err(|e| Error::client_internal_error(e.to_string()))?;
+        let mut metas = self.client.blockchain(min, latest).await?.block_metas;
+        metas.sort_by_key(|meta| meta.header.height);
+
+        self.forget_below(min);
+        Ok(metas
+            .into_iter()
+            .filter(|meta| meta.header.height >= min)
+            .filter_map(|meta| self.observe(meta.header.height, meta.block_id.hash))
+            .collect())
+    }
+}
+
+/// The state of a stream produced by [`follow_blocks`].
+struct BlockFollowerState {
+    subscription: Subscription,
+    // The height of the next block to be produced, once known.
+    next: Option<u64>,
+    // A block received via the subscription, which is held back until the
+    // blocks preceding it have been fetched.
+    pending: Option<Block>,
+    done: bool,
+}
+
+/// Produce the blocks of the `NewBlock` events received via the given
+/// subscription, fetching any blocks skipped between them via the given
+/// client (see [`crate::SubscriptionClient::block_follower`]).
+pub(crate) fn follow_blocks<C>(
+    client: &C,
+    subscription: Subscription,
+) -> ClientStream<'_, Result<Block>>
+where
+    C: Client + Sync,
+{
+    let state = BlockFollowerState {
+        subscription,
+        next: None,
+        pending: None,
+        done: false,
+    };
+    Box::pin(stream::unfold(state, move |mut state| async move {
+        loop {
+            if state.done {
+                return None;
+            }
+            if let Some(block) = state.pending.take() {
+                let height = block.header.height.value();
+                let next = state.next.unwrap_or(height);
+                if next >= height {
+                    state.next = Some(height + 1);
+                    return Some((Ok(block), state));
+                }
+                state.pending = Some(block);
+                let missing = match Height::try_from(next) {
+                    Ok(next) => client.block(next).await.map(|response| response.block),
+                    Err(e) => Err(Error::client_internal_error(e.to_string())),
+                };
+                if missing.is_ok() {
+                    state.next = Some(next + 1);
+                } else {
+                    state.done = true;
+                }
+                return Some((missing, state));
+            }
+            match state.subscription.next().await? {
+                Ok(event) => {
+                    if let EventData::NewBlock {
+                        block: Some(block), ..
+                    } = event.data
+                    {
+                        // The first block received determines where the
+                        // stream starts, and blocks which were already
+                        // produced are not produced again.
+                        let height = block.header.height.value();
+                        if state.next.map_or(true, |next| height >= next) {
+                            state.next.get_or_insert(height);
+                            state.pending = Some(block);
+                        }
+                    }
+                }
+                Err(e) => return Some((Err(e), state)),
+            }
+        }
+    }))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::client::{MockClient, MockRequestMethodMatcher};
+    use crate::test_support::fixture;
+    use crate::Method;
+
+    fn hash(byte: u8) -> Hash {
+        Hash::Sha256([byte; 32])
+    }
+
+    #[test]
+    fn reorgs_are_detected() {
+        let mut follower = ChainFollower::new(());
+        let height = |h: u32| Height::from(h);
+
+        let events: Vec<_> = vec![
+            (height(1), hash(1)),
+            (height(2), hash(2)),
+            (height(2), hash(2)),
+            (height(3), hash(3)),
+            // The blocks at heights 2 and 3 are replaced.
+            (height(2), hash(22)),
+            (height(3), hash(33)),
+            (height(4), hash(4)),
+        ]
+        .into_iter()
+        .filter_map(|(h, hash)| follower.observe(h, hash))
+        .collect();
+
+        assert_eq!(
+            vec![
+                FollowEvent::NewBlock {
+                    height: height(1),
+                    hash: hash(1)
+                },
+                FollowEvent::NewBlock {
+                    height: height(2),
+                    hash: hash(2)
+                },
+                FollowEvent::NewBlock {
+                    height: height(3),
+                    hash: hash(3)
+                },
+                FollowEvent::Reorg {
+                    height: height(2),
+                    old_hash: hash(2),
+                    new_hash: hash(22)
+                },
+                FollowEvent::Reorg {
+                    height: height(3),
+                    old_hash: hash(3),
+                    new_hash: hash(33)
+                },
+                FollowEvent::NewBlock {
+                    height: height(4),
+                    hash: hash(4)
+                },
+            ],
+            events
+        );
+        assert_eq!(Some(hash(22)), follower.hash_at(height(2)));
+    }
+
+    #[tokio::test]
+    async fn polling_reports_only_changes() {
+        // The blockchain fixture covers heights 1 to 10.
+        let status = fixture("status").replace("\"410744\"", "\"10\"");
+        let (client, _driver) = MockClient::new(
+            MockRequestMethodMatcher::default()
+                .map(Method::Status, Ok(status))
+                .map(Method::Blockchain, Ok(fixture("blockchain"))),
+        );
+        let mut follower = ChainFollower::new(client).with_window(5);
+
+        let events = follower.poll().await.unwrap();
+        assert_eq!(5, events.len());
+        assert!(events
+            .iter()
+            .all(|e| matches!(e, FollowEvent::NewBlock { .. })));
+        let heights: Vec<_> = events
+            .iter()
+            .map(|e| match e {
+                FollowEvent::NewBlock { height, .. } => *height,
+                FollowEvent::Reorg { height, .. } => *height,
+            })
+            .collect();
+        let mut sorted = heights.clone();
+        sorted.sort();
+        assert_eq!(sorted, heights);
+
+        // Nothing changed in the meantime.
+        assert!(follower.poll().await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,235 @@
+//! A client decorator which refuses reads while the node is catching up.
+
+use crate::client::{Client, Subscription, SubscriptionClient};
+use crate::error::ErrorKind;
+use crate::query::Query;
+use crate::{Error, Method, Result, SimpleRequest};
+use async_trait::async_trait;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// A [`Client`] which refuses to perform read requests while the node is
+/// still catching up with the rest of the network (as reported by the
+/// `catching_up` field of its `/status`), so that applications which must
+/// not act on stale data never see any.
+///
+/// Refused requests fail with an [`ErrorKind::Unavailable`] error, and so may
+/// be retried later. Broadcasts (which nodes accept while catching up) and
+/// requests for the node's `/status` and `/health` are always passed through,
+/// as are subscriptions.
+///
+/// The node's status is checked at most once per check interval (see
+/// [`HealthGatedClient::with_check_interval`]), and clones share the most
+/// recent status.
+///
+/// ## Examples
+///
+/// ```rust,ignore
+/// use std::time::Duration;
+/// use tendermint_rpc::{Client, HealthGatedClient, HttpClient};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let client = HealthGatedClient::new(HttpClient::new("http://127.0.0.1:26657").unwrap())
+///         .with_check_interval(Duration::from_secs(5));
+///
+///     // Fails if the node is still syncing.
+///     let block = client.latest_block().await.unwrap();
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct HealthGatedClient<C> {
+    inner: C,
+    check_interval: Duration,
+    // When the node's status was last checked, and whether it was catching
+    // up at the time.
+    last_check: Arc<Mutex<Option<(Instant, bool)>>>,
+}
+
+impl<C> HealthGatedClient<C> {
+    /// Wrap the given client.
+    pub fn new(inner: C) -> Self {
+        Self {
+            inner,
+            check_interval: Duration::from_secs(1),
+            last_check: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Check the node's status again only once the given interval has
+    /// elapsed since it was last checked, reusing the previous result in the
+    /// meantime. A zero interval checks the node's status before every read
+    /// request.
+    ///
+    /// Defaults to 1 second.
+    pub fn with_check_interval(mut self, interval: Duration) -> Self {
+        self.check_interval = interval;
+        self
+    }
+
+    /// A reference to the wrapped client.
+    pub fn inner(&self) -> &C {
+        &self.inner
+    }
+
+    /// Unwrap the underlying client.
+    pub fn into_inner(self) -> C {
+        self.inner
+    }
+}
+
+impl<C> HealthGatedClient<C>
+where
+    C: Client + Sync,
+{
+    /// Whether the node is catching up, as of its most recent status (which
+    /// is fetched if it is older than the check interval).
+    async fn catching_up(&self) -> Result<bool> {
+        let cached = *self.last_check.lock().unwrap();
+        if let Some((checked_at, catching_up)) = cached {
+            if checked_at.elapsed() < self.check_interval {
+                return Ok(catching_up);
+            }
+        }
+        let catching_up = self.inner.status().await?.sync_info.catching_up;
+        *self.last_check.lock().unwrap() = Some((Instant::now(), catching_up));
+        Ok(catching_up)
+    }
+}
+
+#[cfg_attr(not(feature = "unsend"), async_trait)]
+#[cfg_attr(feature = "unsend", async_trait(?Send))]
+impl<C> Client for HealthGatedClient<C>
+where
+    C: Client + Send + Sync,
+{
+    fn max_query_conditions(&self) -> Option<usize> {
+        self.inner.max_query_conditions()
+    }
+
+    async fn perform<R>(&self, request: R) -> Result<R::Response>
+    where
+        R: SimpleRequest,
+    {
+        let method = request.method();
+        if is_gated(method) && self.catching_up().await? {
+            return Err(Error::client_internal_error(format!(
+                "refusing to perform {} request while the node is catching up",
+                method
+            ))
+            .with_kind(ErrorKind::Unavailable));
+        }
+        self.inner.perform(request).await
+    }
+}
+
+#[cfg_attr(not(feature = "unsend"), async_trait)]
+#[cfg_attr(feature = "unsend", async_trait(?Send))]
+impl<C> SubscriptionClient for HealthGatedClient<C>
+where
+    C: SubscriptionClient + Send + Sync,
+{
+    async fn subscribe(&self, query: Query) -> Result<Subscription> {
+        self.inner.subscribe(query).await
+    }
+
+    async fn unsubscribe(&self, query: Query) -> Result<()> {
+        self.inner.unsubscribe(query).await
+    }
+
+    fn close(self) -> Result<()> {
+        self.inner.close()
+    }
+}
+
+/// Whether requests with the given method are refused while the node is
+/// catching up.
+fn is_gated(method: Method) -> bool {
+    !matches!(
+        method,
+        Method::BroadcastEvidence
+            | Method::BroadcastTxAsync
+            | Method::BroadcastTxSync
+            | Method::BroadcastTxCommit
+            | Method::Health
+            | Method::Status
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::client::{MockClient, MockRequestMethodMatcher, TimedClient};
+    use crate::test_support::{counting_transport, fixture};
+    use tendermint::abci::Transaction;
+    use tokio::time;
+
+    fn gated_client(
+        catching_up: bool,
+    ) -> HealthGatedClient<TimedClient<MockClient<MockRequestMethodMatcher>>> {
+        let status = fixture("status").replace(
+            "\"catching_up\": false",
+            &format!("\"catching_up\": {}", catching_up),
+        );
+        HealthGatedClient::new(counting_transport(vec![
+            (Method::Status, Ok(status)),
+            (Method::Block, Ok(fixture("block"))),
+            (Method::BroadcastTxSync, Ok(fixture("broadcast_tx_sync"))),
+        ]))
+    }
+
+    fn transport_requests<C>(client: &HealthGatedClient<TimedClient<C>>) -> Vec<Method> {
+        crate::test_support::transport_requests(client.inner())
+    }
+
+    #[tokio::test]
+    async fn reads_are_refused_while_catching_up() {
+        let client = gated_client(true);
+        let err = client.latest_block().await.unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("refusing to perform block request while the node is catching up"),
+            "unexpected error: {}",
+            err
+        );
+        assert_eq!(ErrorKind::Unavailable, err.kind());
+
+        // Broadcasts and the status itself are passed through.
+        client
+            .broadcast_tx_sync(Transaction::from(b"tx".to_vec()))
+            .await
+            .unwrap();
+        assert!(client.status().await.unwrap().sync_info.catching_up);
+        assert_eq!(
+            vec![Method::Status, Method::BroadcastTxSync, Method::Status],
+            transport_requests(&client)
+        );
+    }
+
+    #[tokio::test]
+    async fn reads_are_allowed_once_caught_up() {
+        time::pause();
+        let client = gated_client(false);
+        client.latest_block().await.unwrap();
+        client.latest_block().await.unwrap();
+        // The status is only checked once per interval.
+        assert_eq!(
+            vec![Method::Status, Method::Block, Method::Block],
+            transport_requests(&client)
+        );
+
+        time::advance(Duration::from_millis(1001)).await;
+        client.latest_block().await.unwrap();
+        assert_eq!(5, transport_requests(&client).len());
+
+        // Without an interval, the status is checked before every read.
+        let client = gated_client(false).with_check_interval(Duration::default());
+        client.latest_block().await.unwrap();
+        client.latest_block().await.unwrap();
+        assert_eq!(
+            vec![Method::Status, Method::Block, Method::Status, Method::Block],
+            transport_requests(&client)
+        );
+    }
+}
@@ -0,0 +1,27 @@
+//! Light blocks, as assembled by [`crate::Client::light_block`].
+
+use tendermint::block::signed_header::SignedHeader;
+use tendermint::block::Height;
+use tendermint::validator;
+
+/// The data required by a light client to verify the block at a given
+/// height: its signed header, along with the validator sets for that height
+/// and the next.
+#[derive(Clone, Debug)]
+pub struct LightBlock {
+    /// Header and commit of the block
+    pub signed_header: SignedHeader,
+
+    /// Validator set at the block's height
+    pub validators: validator::Set,
+
+    /// Validator set at the height following the block's
+    pub next_validators: validator::Set,
+}
+
+impl LightBlock {
+    /// The height of the block.
+    pub fn height(&self) -> Height {
+        self.signed_header.header.height
+    }
+}
@@ -0,0 +1,253 @@
+//! A client which spreads requests across several endpoints.
+
+use crate::client::Client;
+use crate::error::Code;
+use crate::{Error, Method, Result, SimpleRequest};
+use async_trait::async_trait;
+use futures::future::join_all;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// A [`Client`] which dispatches requests to several endpoints (e.g. a number
+/// of [`crate::HttpClient`]s), both to spread load across them and to fail
+/// over when one of them cannot be reached.
+///
+/// Each request is sent to the next endpoint in round-robin order. If that
+/// endpoint cannot be reached (i.e. the request fails with an HTTP or
+/// WebSocket transport error), the request is retried against the remaining
+/// endpoints in turn. Any other error (e.g. an error returned by the node
+/// itself) is returned immediately, as another endpoint would be expected to
+/// respond in the same way.
+///
+/// Broadcasts are instead sent to all endpoints concurrently, and succeed if
+/// at least [`MultiClient::broadcast_quorum`] of them accept the transaction.
+///
+/// ## Examples
+///
+/// ```rust,ignore
+/// use tendermint_rpc::{Client, HttpClient, MultiClient};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let client = MultiClient::new(vec![
+///         HttpClient::new("http://node-1:26657").unwrap(),
+///         HttpClient::new("http://node-2:26657").unwrap(),
+///     ])
+///     .unwrap()
+///     .with_broadcast_quorum(2);
+///
+///     let status = client.status().await.unwrap();
+///     println!("Latest height: {}", status.sync_info.latest_block_height);
+/// }
+/// ```
+#[derive(Debug)]
+pub struct MultiClient<C> {
+    clients: Vec<C>,
+    next: AtomicUsize,
+    broadcast_quorum: usize,
+}
+
+impl<C> MultiClient<C> {
+    /// Dispatch requests across the given clients.
+    ///
+    /// Fails if no clients are given.
+    pub fn new(clients: Vec<C>) -> Result<Self> {
+        if clients.is_empty() {
+            return Err(Error::invalid_params(
+                "at least one client is required to construct a MultiClient",
+            ));
+        }
+        Ok(Self {
+            clients,
+            next: AtomicUsize::new(0),
+            broadcast_quorum: 1,
+        })
+    }
+
+    /// Require broadcasts to be accepted by at least `quorum` endpoints
+    /// (capped at the number of endpoints) in order to succeed.
+    ///
+    /// Defaults to 1.
+    pub fn with_broadcast_quorum(mut self, quorum: usize) -> Self {
+        self.broadcast_quorum = quorum.max(1);
+        self
+    }
+
+    /// The number of endpoints which must accept a broadcast for it to
+    /// succeed.
+    pub fn broadcast_quorum(&self) -> usize {
+        self.broadcast_quorum.min(self.clients.len())
+    }
+
+    /// The wrapped clients, in round-robin order.
+    pub fn clients(&self) -> &[C] {
+        &self.clients
+    }
+
+    /// Unwrap the underlying clients.
+    pub fn into_inner(self) -> Vec<C> {
+        self.clients
+    }
+}
+
+impl<C> MultiClient<C>
+where
+    C: Client + Send + Sync,
+{
+    async fn perform_with_failover<R>(&self, params: serde_json::Value) -> Result<R::Response>
+    where
+        R: SimpleRequest,
+    {
+        let start = self.next.fetch_add(1, Ordering::Relaxed);
+        let mut last_err = None;
+        for i in 0..self.clients.len() {
+            let client = &self.clients[(start + i) % self.clients.len()];
+            match client.perform(request_from::<R>(&params)?).await {
+                Err(e) if is_connection_error(&e) => last_err = Some(e),
+                res => return res,
+            }
+        }
+        Err(last_err.unwrap())
+    }
+
+    async fn broadcast<R>(&self, params: serde_json::Value) -> Result<R::Response>
+    where
+        R: SimpleRequest,
+    {
+        let requests = self
+            .clients
+            .iter()
+            .map(|client| async { client.perform(request_from::<R>(&params)?).await });
+        let (mut accepted, mut rejected): (Vec<_>, Vec<_>) = join_all(requests)
+            .await
+            .into_iter()
+            .partition(Result::is_ok);
+        let quorum = self.broadcast_quorum();
+        if accepted.len() >= quorum {
+            return accepted.swap_remove(0);
+        }
+        let cause = rejected.swap_remove(0).err().unwrap();
+        Err(Error::client_internal_error(format!(
+            "broadcast accepted by {} of {} endpoints, but a quorum of {} is required: {}",
+            accepted.len(),
+            self.clients.len(),
+            quorum,
+            cause
+        )))
+    }
+}
+
+#[cfg_attr(not(feature = "unsend"), async_trait)]
+#[cfg_attr(feature = "unsend", async_trait(?Send))]
+impl<C> Client for MultiClient<C>
+where
+    C: Client + Send + Sync,
+{
+    /// The strictest of the endpoints' limits.
+    fn max_query_conditions(&self) -> Option<usize> {
+        self.clients
+            .iter()
+            .filter_map(|client| client.max_query_conditions())
+            .min()
+    }
+
+    async fn perform<R>(&self, request: R) -> Result<R::Response>
+    where
+        R: SimpleRequest,
+    {
+        // Requests are not `Clone`, so each attempt is made with a copy
+        // deserialized from the original's parameters.
+        let method = request.method();
+        let params = serde_json::to_value(&request).map_err(Error::parse_error)?;
+        match method {
+            Method::BroadcastTxAsync | Method::BroadcastTxSync | Method::BroadcastTxCommit => {
+                self.broadcast::<R>(params).await
+            }
+            _ => self.perform_with_failover::<R>(params).await,
+        }
+    }
+}
+
+fn request_from<R: SimpleRequest>(params: &serde_json::Value) -> Result<R> {
+    serde_json::from_value(params.clone()).map_err(Error::parse_error)
+}
+
+/// Whether the given error indicates that the endpoint could not be reached,
+/// rather than that it responded with an error.
+fn is_connection_error(e: &Error) -> bool {
+    matches!(e.code(), Code::HttpError | Code::WebSocketError)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::client::{MockClient, MockRequestMethodMatcher, TimedClient};
+    use crate::test_support::{counting_transport, fixture, transport_requests};
+    use tendermint::abci::Transaction;
+
+    type Endpoint = TimedClient<MockClient<MockRequestMethodMatcher>>;
+
+    // A healthy endpoint only responds to `/health` and `/broadcast_tx_sync`,
+    // whereas a failing one cannot be reached at all.
+    fn endpoint(healthy: bool) -> Endpoint {
+        if healthy {
+            counting_transport(vec![
+                (Method::Health, Ok(fixture("health"))),
+                (Method::BroadcastTxSync, Ok(fixture("broadcast_tx_sync"))),
+            ])
+        } else {
+            counting_transport(
+                [Method::Health, Method::Status, Method::BroadcastTxSync]
+                    .iter()
+                    .map(|method| (*method, Err(Error::http_error("connection refused")))),
+            )
+        }
+    }
+
+    fn requests_received(client: &MultiClient<Endpoint>) -> Vec<usize> {
+        client
+            .clients()
+            .iter()
+            .map(|c| transport_requests(c).len())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn requests_fail_over_to_healthy_endpoints() {
+        let client = MultiClient::new(vec![endpoint(false), endpoint(true)]).unwrap();
+        for _ in 0..4 {
+            client.health().await.unwrap();
+        }
+        // Every other request starts with the failing endpoint.
+        assert_eq!(vec![2, 4], requests_received(&client));
+
+        // Errors from the node itself are not retried.
+        let err = client.status().await.unwrap_err();
+        assert!(err.to_string().contains("no matching response"));
+        assert_eq!(vec![3, 5], requests_received(&client));
+        client.status().await.unwrap_err();
+        assert_eq!(vec![3, 6], requests_received(&client));
+
+        // If no endpoint can be reached, the last error is returned.
+        let client = MultiClient::new(vec![endpoint(false), endpoint(false)]).unwrap();
+        let err = client.health().await.unwrap_err();
+        assert_eq!(Code::HttpError, err.code());
+        assert_eq!(vec![1, 1], requests_received(&client));
+    }
+
+    #[tokio::test]
+    async fn broadcasts_reach_all_endpoints() {
+        let tx = || Transaction::from(b"tx".to_vec());
+        let client = MultiClient::new(vec![endpoint(false), endpoint(true)]).unwrap();
+        client.broadcast_tx_sync(tx()).await.unwrap();
+        assert_eq!(vec![1, 1], requests_received(&client));
+
+        let client = client.with_broadcast_quorum(2);
+        let err = client.broadcast_tx_sync(tx()).await.unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("broadcast accepted by 1 of 2 endpoints, but a quorum of 2 is required"));
+        assert_eq!(vec![2, 2], requests_received(&client));
+
+        assert!(MultiClient::<Endpoint>::new(Vec::new()).is_err());
+    }
+}
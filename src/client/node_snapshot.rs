@@ -0,0 +1,75 @@
+//! Summaries of a node's state, as assembled by
+//! [`crate::Client::node_snapshot`].
+
+use crate::endpoint::{abci_info, status};
+use tendermint::block::Height;
+use tendermint::chain;
+
+/// A summary of the state of a node and of the application it runs, as
+/// reported by its `/status` and `/abci_info` endpoints.
+#[derive(Clone, Debug)]
+pub struct NodeSnapshot {
+    /// The ID of the chain the node belongs to
+    pub chain_id: chain::Id,
+
+    /// The height of the latest block available from the node
+    pub latest_block_height: Height,
+
+    /// The height of the earliest block available from the node (see
+    /// [`status::SyncInfo::available_height_range`])
+    pub earliest_block_height: Height,
+
+    /// Whether the node is still catching up with the rest of the network
+    pub catching_up: bool,
+
+    /// The name of the application, as reported by it
+    pub app_name: String,
+
+    /// The version of the application's protocol
+    pub app_version: u64,
+}
+
+impl NodeSnapshot {
+    /// Assemble a snapshot from the node's status and ABCI information.
+    pub fn from_responses(status: status::Response, abci_info: abci_info::AbciInfo) -> Self {
+        let (earliest_block_height, latest_block_height) =
+            status.sync_info.available_height_range();
+        Self {
+            chain_id: status.node_info.network,
+            latest_block_height,
+            earliest_block_height,
+            catching_up: status.sync_info.catching_up,
+            app_name: abci_info.data,
+            app_version: abci_info.app_version,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::client::{Client, MockClient, MockRequestMethodMatcher};
+    use crate::test_support::fixture;
+    use crate::Method;
+
+    #[tokio::test]
+    async fn snapshot_from_status_and_abci_info() {
+        let (client, _driver) = MockClient::new(
+            MockRequestMethodMatcher::default()
+                .map(Method::Status, Ok(fixture("status_pruned")))
+                .map(Method::AbciInfo, Ok(fixture("abci_info"))),
+        );
+        let snapshot = client.node_snapshot().await.unwrap();
+        assert_eq!("cosmoshub-2", snapshot.chain_id.as_str());
+        assert_eq!(410744_u64, snapshot.latest_block_height.value());
+        assert_eq!(400001_u64, snapshot.earliest_block_height.value());
+        assert!(!snapshot.catching_up);
+        assert_eq!("GaiaApp", snapshot.app_name);
+        assert_eq!(1, snapshot.app_version);
+
+        // Both responses are required.
+        let (client, _driver) = MockClient::new(
+            MockRequestMethodMatcher::default().map(Method::Status, Ok(fixture("status"))),
+        );
+        assert!(client.node_snapshot().await.is_err());
+    }
+}
@@ -0,0 +1,198 @@
+//! A client decorator which pins reads to a single block height.
+
+use crate::client::{Client, Subscription, SubscriptionClient};
+use crate::query::Query;
+use crate::{Error, Method, Result, SimpleRequest};
+use async_trait::async_trait;
+use tendermint::block::Height;
+
+/// A [`Client`] which pins all read requests that do not specify a height to
+/// a single block height, so that multiple reads (e.g. several `abci_query`
+/// calls followed by a `block` call) all observe the same snapshot of the
+/// chain, even if new blocks are committed in the meantime.
+///
+/// Requests that explicitly specify a height are sent as-is, as are requests
+/// that are not height-specific (e.g. broadcasts, `/status` and
+/// `/tx_search`). Subscriptions are passed through to the wrapped client.
+///
+/// ## Examples
+///
+/// ```rust,ignore
+/// use tendermint_rpc::{Client, HttpClient, PinnedHeightClient};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let client = HttpClient::new("http://127.0.0.1:26657").unwrap();
+///     let pinned = PinnedHeightClient::new(client).await.unwrap();
+///
+///     // Both of these refer to the same block, regardless of whether a new
+///     // block is committed between the two calls.
+///     let block = pinned.latest_block().await.unwrap();
+///     let commit = pinned.latest_commit().await.unwrap();
+///     assert_eq!(block.block.header.height, commit.signed_header.header.height);
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct PinnedHeightClient<C> {
+    inner: C,
+    height: Height,
+}
+
+impl<C> PinnedHeightClient<C>
+where
+    C: Client + Sync,
+{
+    /// Wrap the given client, pinning reads to the latest block height
+    /// reported by its `/status` endpoint at the time of construction.
+    pub async fn new(inner: C) -> Result<Self> {
+        let height = inner.status().await?.sync_info.latest_block_height;
+        Ok(Self::with_height(inner, height))
+    }
+}
+
+impl<C> PinnedHeightClient<C> {
+    /// Wrap the given client, pinning reads to the given block height.
+    pub fn with_height(inner: C, height: Height) -> Self {
+        Self { inner, height }
+    }
+
+    /// The height to which reads are pinned.
+    pub fn height(&self) -> Height {
+        self.height
+    }
+
+    /// A reference to the wrapped client.
+    pub fn inner(&self) -> &C {
+        &self.inner
+    }
+
+    /// Unwrap the underlying client.
+    pub fn into_inner(self) -> C {
+        self.inner
+    }
+}
+
+#[cfg_attr(not(feature = "unsend"), async_trait)]
+#[cfg_attr(feature = "unsend", async_trait(?Send))]
+impl<C> Client for PinnedHeightClient<C>
+where
+    C: Client + Send + Sync,
+{
+    fn max_query_conditions(&self) -> Option<usize> {
+        self.inner.max_query_conditions()
+    }
+
+    async fn perform<R>(&self, request: R) -> Result<R::Response>
+    where
+        R: SimpleRequest,
+    {
+        let request = pin_height(request, self.height)?;
+        self.inner.perform(request).await
+    }
+}
+
+#[cfg_attr(not(feature = "unsend"), async_trait)]
+#[cfg_attr(feature = "unsend", async_trait(?Send))]
+impl<C> SubscriptionClient for PinnedHeightClient<C>
+where
+    C: SubscriptionClient + Send + Sync,
+{
+    async fn subscribe(&self, query: Query) -> Result<Subscription> {
+        self.inner.subscribe(query).await
+    }
+
+    async fn unsubscribe(&self, query: Query) -> Result<()> {
+        self.inner.unsubscribe(query).await
+    }
+
+    fn close(self) -> Result<()> {
+        self.inner.close()
+    }
+}
+
+/// Set the height of the given request to `height` if it is a height-specific
+/// read request which does not yet specify a height.
+fn pin_height<R: SimpleRequest>(request: R, height: Height) -> Result<R> {
+    match request.method() {
+        Method::AbciQuery
+        | Method::Block
+        | Method::BlockResults
+        | Method::Commit
+        | Method::Validators => (),
+        _ => return Ok(request),
+    }
+    if specifies_height(&request)? {
+        return Ok(request);
+    }
+    with_height(request, height)
+}
+
+/// Whether the given request has a height parameter which is set.
+pub(crate) fn specifies_height<R: SimpleRequest>(request: &R) -> Result<bool> {
+    let params = serde_json::to_value(request).map_err(Error::parse_error)?;
+    Ok(params.get("height").map_or(false, |h| !h.is_null()))
+}
+
+/// The given request, with its height parameter set to `height`.
+pub(crate) fn with_height<R: SimpleRequest>(request: R, height: Height) -> Result<R> {
+    let mut params = serde_json::to_value(&request).map_err(Error::parse_error)?;
+    params["height"] = serde_json::to_value(height).map_err(Error::parse_error)?;
+    serde_json::from_value(params).map_err(Error::parse_error)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::client::{MockClient, MockRequestMethodMatcher};
+    use crate::test_support::{fixture, HeightRecordingMatcher};
+
+    #[tokio::test]
+    async fn height_less_reads_are_pinned() {
+        let matcher = HeightRecordingMatcher::new(
+            MockRequestMethodMatcher::default()
+                .map(Method::Status, Ok(fixture("status")))
+                .map(Method::Block, Ok(fixture("block"))),
+            Method::Block,
+        );
+        let heights = matcher.heights();
+        let (client, driver) = MockClient::new(matcher);
+        let driver_hdl = tokio::spawn(async move { driver.run().await });
+
+        let pinned = PinnedHeightClient::new(client).await.unwrap();
+        assert_eq!(Height::from(410744_u32), pinned.height());
+
+        pinned.latest_block().await.unwrap();
+        pinned.block(Height::from(10_u32)).await.unwrap();
+        pinned
+            .perform(crate::endpoint::block::Request::default())
+            .await
+            .unwrap();
+
+        let client = pinned.into_inner();
+        assert_eq!(
+            vec![
+                serde_json::json!("410744"),
+                serde_json::json!("10"),
+                serde_json::json!("410744"),
+            ],
+            *heights.lock().unwrap()
+        );
+
+        client.close();
+        driver_hdl.await.unwrap().unwrap();
+    }
+
+    #[test]
+    fn only_height_less_reads_are_pinned() {
+        let height = Height::from(42_u32);
+        let request = crate::endpoint::status::Request;
+        assert_eq!(request.clone(), pin_height(request, height).unwrap());
+
+        let request =
+            crate::endpoint::validators::Request::new(Some(Height::from(7_u32)), None, None);
+        assert_eq!(request.clone(), pin_height(request, height).unwrap());
+
+        let request = crate::endpoint::commit::Request::default();
+        assert_eq!(Some(height), pin_height(request, height).unwrap().height);
+    }
+}
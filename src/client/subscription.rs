@@ -1,22 +1,83 @@
 //! Subscription- and subscription management-related functionality.
 
+use crate::client::follower::follow_blocks;
 use crate::client::sync::{ChannelRx, ChannelTx};
-use crate::event::Event;
-use crate::query::Query;
+use crate::client::{Client, ClientStream};
+use crate::event::{Event, EventData};
+use crate::query::{EventType, Query};
 use crate::Result;
 use async_trait::async_trait;
 use futures::task::{Context, Poll};
 use futures::Stream;
-use pin_project::pin_project;
+use pin_project::{pin_project, pinned_drop};
+use sha2::{Digest, Sha256};
+use std::collections::{HashSet, VecDeque};
+use std::fmt;
 use std::pin::Pin;
+use tendermint::block::Block;
 
 /// A client that exclusively provides [`Event`] subscription capabilities,
 /// without any other RPC method support.
-#[async_trait]
+#[cfg_attr(not(feature = "unsend"), async_trait)]
+#[cfg_attr(feature = "unsend", async_trait(?Send))]
 pub trait SubscriptionClient {
     /// `/subscribe`: subscribe to receive events produced by the given query.
+    ///
+    /// Queries containing local-only conditions (see
+    /// [`Query::validate_for_server`]), or more conditions than the client
+    /// allows (if it is configured with a limit), are rejected.
     async fn subscribe(&self, query: Query) -> Result<Subscription>;
 
+    /// As per [`SubscriptionClient::subscribe`], but the subscription first
+    /// produces an event for the latest block, provided that it matches the
+    /// query (as it does for a query for all `NewBlock` events), rather than
+    /// only producing events from the next block onwards.
+    ///
+    /// The latest block is fetched once the subscription has been
+    /// established, so that no block can be missed in between. If the first
+    /// event received is for the same block, it is dropped. The injected
+    /// event carries the block, but not the results of `BeginBlock` and
+    /// `EndBlock`.
+    async fn subscribe_with_current(&self, query: Query) -> Result<Subscription>
+    where
+        Self: Client + Sync,
+    {
+        let subscription = self.subscribe(query.clone()).await?;
+        let event = Event {
+            query: String::new(),
+            data: EventData::NewBlock {
+                block: Some(self.latest_block().await?.block),
+                result_begin_block: None,
+                result_end_block: None,
+            },
+            events: None,
+        };
+        if !query.matches_event(&event) {
+            return Ok(subscription);
+        }
+        Ok(subscription.starting_with(Event {
+            query: query.to_string(),
+            ..event
+        }))
+    }
+
+    /// Subscribe to `NewBlock` events, producing a contiguous stream of the
+    /// blocks they carry, in order of height.
+    ///
+    /// Whenever a block's height is more than one greater than that of the
+    /// previous block (e.g. because the subscription lagged), the blocks in
+    /// between are fetched via `/block` before it is produced. The stream
+    /// starts with the block of the first event received, and blocks at
+    /// heights which were already produced are skipped. The stream ends if a
+    /// missing block cannot be fetched, or when the subscription ends.
+    async fn block_follower<'a>(&'a self) -> Result<ClientStream<'a, Result<Block>>>
+    where
+        Self: Client + Sync + Sized,
+    {
+        let subscription = self.subscribe(EventType::NewBlock.into()).await?;
+        Ok(follow_blocks(self, subscription))
+    }
+
     /// `/unsubscribe`: unsubscribe from events relating to the given query.
     ///
     /// This method is particularly useful when you want to terminate multiple
@@ -61,7 +122,12 @@ pub(crate) type SubscriptionRx = ChannelRx<Result<Event>>;
 ///     }
 /// }
 /// ```
-#[pin_project]
+///
+/// Dropping a subscription stops the production of its events. Once the last
+/// subscription to a query has been dropped, the client unsubscribes from the
+/// query (on a best-effort basis), so that the remote endpoint stops tracking
+/// it too.
+#[pin_project(PinnedDrop)]
 #[derive(Debug)]
 pub struct Subscription {
     // A unique identifier for this subscription.
@@ -71,19 +137,101 @@ pub struct Subscription {
     // Our internal result event receiver for this subscription.
     #[pin]
     rx: SubscriptionRx,
+    // Identifiers of recently produced events, if deduplication is enabled.
+    dedup: Option<DedupWindow>,
+    // An event to produce before any received events.
+    first: Option<Event>,
+    // The identifier of the `first` event, which the next received event
+    // duplicates if it has the same identifier.
+    duplicate_of_first: Option<EventId>,
+    // Invoked when the subscription is dropped, e.g. to notify the transport.
+    on_drop: Option<DropHook>,
+}
+
+#[pinned_drop]
+impl PinnedDrop for Subscription {
+    fn drop(self: Pin<&mut Self>) {
+        if let Some(hook) = self.project().on_drop.take() {
+            (hook.0)();
+        }
+    }
+}
+
+struct DropHook(Box<dyn FnOnce() + Send + Sync>);
+
+impl fmt::Debug for DropHook {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("DropHook")
+    }
 }
 
 impl Stream for Subscription {
     type Item = Result<Event>;
 
     fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
-        self.project().rx.poll_next(cx)
+        let mut this = self.project();
+        if let Some(ev) = this.first.take() {
+            return Poll::Ready(Some(Ok(ev)));
+        }
+        loop {
+            match this.rx.as_mut().poll_next(cx) {
+                Poll::Ready(Some(Ok(ev))) => {
+                    if let Some(id) = this.duplicate_of_first.take() {
+                        if EventId::of(&ev).as_ref() == Some(&id) {
+                            continue;
+                        }
+                    }
+                    if let Some(dedup) = this.dedup.as_mut() {
+                        if !dedup.insert(&ev) {
+                            continue;
+                        }
+                    }
+                    return Poll::Ready(Some(Ok(ev)));
+                }
+                other => return other,
+            }
+        }
     }
 }
 
 impl Subscription {
     pub(crate) fn new(id: String, query: Query, rx: SubscriptionRx) -> Self {
-        Self { id, query, rx }
+        Self {
+            id,
+            query,
+            rx,
+            dedup: None,
+            first: None,
+            duplicate_of_first: None,
+            on_drop: None,
+        }
+    }
+
+    /// Invoke the given function when this subscription is dropped.
+    pub(crate) fn on_drop(mut self, f: impl FnOnce() + Send + Sync + 'static) -> Self {
+        self.on_drop = Some(DropHook(Box::new(f)));
+        self
+    }
+
+    /// Produce the given event before any received events, dropping the next
+    /// received event if it duplicates it.
+    pub(crate) fn starting_with(mut self, event: Event) -> Self {
+        self.duplicate_of_first = EventId::of(&event);
+        self.first = Some(event);
+        self
+    }
+
+    /// Drop events which duplicate any of the last `window` distinct events
+    /// produced by this subscription.
+    ///
+    /// This is useful when the same event may be delivered more than once,
+    /// e.g. when connecting through a load-balanced pool of nodes. `NewBlock`
+    /// events are identified by the block's hash, and `Tx` events by the
+    /// transaction's hash and height. Events which cannot be identified in
+    /// this way (e.g. `NewBlock` events without a block) are never dropped.
+    pub fn deduplicate(mut self, window: usize) -> Self {
+        self.dedup = Some(DedupWindow::new(window));
+        self
     }
 
     /// Return this subscription's ID for informational purposes.
@@ -95,3 +243,202 @@ impl Subscription {
         &self.query
     }
 }
+
+/// The identifier of an event for the purposes of deduplication.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum EventId {
+    Block(Vec<u8>),
+    Tx(i64, Vec<u8>),
+}
+
+impl EventId {
+    fn of(ev: &Event) -> Option<Self> {
+        match &ev.data {
+            EventData::NewBlock {
+                block: Some(block), ..
+            } => Some(EventId::Block(block.header.hash().as_bytes().to_vec())),
+            EventData::Tx { tx_result } => Some(EventId::Tx(
+                tx_result.height,
+                Sha256::digest(&tx_result.tx).to_vec(),
+            )),
+            _ => None,
+        }
+    }
+}
+
+/// A bounded window of the identifiers of recently seen events.
+#[derive(Debug)]
+struct DedupWindow {
+    capacity: usize,
+    seen: HashSet<EventId>,
+    order: VecDeque<EventId>,
+}
+
+impl DedupWindow {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            seen: HashSet::new(),
+            order: VecDeque::new(),
+        }
+    }
+
+    /// Record the given event, returning `false` if it has been seen within
+    /// the window.
+    fn insert(&mut self, ev: &Event) -> bool {
+        let id = match EventId::of(ev) {
+            Some(id) => id,
+            None => return true,
+        };
+        if self.capacity == 0 {
+            return true;
+        }
+        if self.seen.contains(&id) {
+            return false;
+        }
+        if self.order.len() == self.capacity {
+            if let Some(oldest) = self.order.pop_front() {
+                self.seen.remove(&oldest);
+            }
+        }
+        self.seen.insert(id.clone());
+        self.order.push_back(id);
+        true
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::client::sync::unbounded;
+    use crate::client::{MockClient, MockRequestMethodMatcher};
+    use crate::test_support::{fixture, HeightRecordingMatcher};
+    use crate::{Method, Response};
+    use futures::StreamExt;
+    use tendermint::block::Height;
+
+    fn read_event(name: &str) -> Event {
+        Event::from_string(fixture(name)).unwrap()
+    }
+
+    #[tokio::test]
+    async fn duplicate_events_are_dropped() {
+        let (tx, rx) = unbounded();
+        let mut subs =
+            Subscription::new("sub".to_string(), EventType::NewBlock.into(), rx).deduplicate(2);
+
+        let first = read_event("event_new_block_1");
+        let second = read_event("event_new_block_2");
+        let third = read_event("event_new_block_3");
+        for ev in vec![&first, &first, &second, &first, &third, &first] {
+            tx.send(Ok(ev.clone())).unwrap();
+        }
+        drop(tx);
+
+        // The last duplicate of the first event falls outside the window.
+        let received: Vec<Event> = subs.by_ref().map(Result::unwrap).collect().await;
+        assert_eq!(vec![first.clone(), second, third, first], received);
+    }
+
+    #[tokio::test]
+    async fn block_follower_fills_gaps() {
+        let block: serde_json::Value = serde_json::from_str(&fixture("block")).unwrap();
+        let matcher = HeightRecordingMatcher::new(Default::default(), Method::Block)
+            .responding_with(move |params| {
+                let height: u64 = params["height"].as_str().unwrap().parse().unwrap();
+                let mut result = block["result"].clone();
+                result["block"]["header"]["height"] = height.to_string().into();
+                result["block"]["last_commit"]["height"] = (height - 1).to_string().into();
+                result
+            });
+        let heights = matcher.heights();
+        let (client, driver) = MockClient::new(matcher);
+        let driver_hdl = tokio::spawn(async move { driver.run().await });
+        let event_at = |mut event: Event, height: u32| {
+            if let EventData::NewBlock {
+                block: Some(block), ..
+            } = &mut event.data
+            {
+                block.header.height = Height::from(height);
+            }
+            event
+        };
+
+        let mut blocks = client.block_follower().await.unwrap();
+        // The events skip height 1609, repeat height 1610, and then skip
+        // heights 1611 and 1612.
+        let first = read_event("event_new_block_1");
+        let third = read_event("event_new_block_3");
+        client.publish(&first);
+        client.publish(&third);
+        client.publish(&third);
+        client.publish(&event_at(third, 1613));
+
+        let mut heights = Vec::new();
+        for _ in 0..6 {
+            let block = blocks.next().await.unwrap().unwrap();
+            heights.push(block.header.height.value());
+        }
+        assert_eq!(vec![1608, 1609, 1610, 1611, 1612, 1613], heights);
+        assert_eq!(
+            vec![
+                serde_json::json!("1609"),
+                serde_json::json!("1611"),
+                serde_json::json!("1612")
+            ],
+            *heights.lock().unwrap()
+        );
+
+        drop(blocks);
+        client.close();
+        driver_hdl.await.unwrap().unwrap();
+    }
+
+    #[tokio::test]
+    async fn subscription_starts_with_current_block() {
+        let matcher = MockRequestMethodMatcher::default().map(Method::Block, Ok(fixture("block")));
+        let (client, driver) = MockClient::new(matcher);
+        let driver_hdl = tokio::spawn(async move { driver.run().await });
+
+        let current = client.latest_block().await.unwrap().block;
+        let mut subs = client
+            .subscribe_with_current(EventType::NewBlock.into())
+            .await
+            .unwrap();
+
+        // The node publishes the current block again, followed by new ones.
+        let mut duplicate = read_event("event_new_block_1");
+        duplicate.data = EventData::NewBlock {
+            block: Some(current.clone()),
+            result_begin_block: None,
+            result_end_block: None,
+        };
+        let next = read_event("event_new_block_2");
+        client.publish(&duplicate);
+        client.publish(&next);
+
+        let first = subs.next().await.unwrap().unwrap();
+        assert_eq!(Query::from(EventType::NewBlock).to_string(), first.query);
+        match first.data {
+            EventData::NewBlock { block, .. } => assert_eq!(Some(current), block),
+            other => panic!("unexpected event data: {:?}", other),
+        }
+        assert_eq!(next, subs.next().await.unwrap().unwrap());
+
+        // The current block is not injected into subscriptions which it does
+        // not match.
+        let mut subs = client
+            .subscribe_with_current(EventType::Tx.into())
+            .await
+            .unwrap();
+        let live = Event {
+            query: Query::from(EventType::Tx).to_string(),
+            ..next
+        };
+        client.publish(&live);
+        assert_eq!(live, subs.next().await.unwrap().unwrap());
+
+        client.close();
+        driver_hdl.await.unwrap().unwrap();
+    }
+}
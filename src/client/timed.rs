@@ -0,0 +1,244 @@
+//! A client decorator which records how long each request takes.
+
+use crate::client::{Client, Subscription, SubscriptionClient};
+use crate::query::Query;
+use crate::{Method, Result, SimpleRequest};
+use async_trait::async_trait;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// The number of request durations a [`TimedClient`] retains by default.
+pub const DEFAULT_DURATION_CAPACITY: usize = 1024;
+
+/// The duration of a single request performed via a [`TimedClient`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RequestDuration {
+    /// The request's method
+    pub method: Method,
+
+    /// How long it took to receive a response (or error)
+    pub duration: Duration,
+}
+
+/// A [`Client`] which records the duration of each request it performs, e.g.
+/// so that tests against a local node can assert that endpoints respond
+/// within a given latency budget.
+///
+/// Only the durations of the most recent requests are retained (see
+/// [`TimedClient::with_capacity`]), so that a long-lived client does not
+/// accumulate them indefinitely. Clones share the same record of durations.
+///
+/// Only available with the `timed-client` feature.
+///
+/// ## Examples
+///
+/// ```rust,ignore
+/// use std::time::Duration;
+/// use tendermint_rpc::{Client, HttpClient, TimedClient};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let client = TimedClient::new(HttpClient::new("http://127.0.0.1:26657").unwrap());
+///
+///     client.status().await.unwrap();
+///     assert!(client.last_request_duration().unwrap() < Duration::from_millis(100));
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct TimedClient<C> {
+    inner: C,
+    capacity: usize,
+    durations: Arc<Mutex<VecDeque<RequestDuration>>>,
+}
+
+impl<C> TimedClient<C> {
+    /// Wrap the given client.
+    pub fn new(inner: C) -> Self {
+        Self {
+            inner,
+            capacity: DEFAULT_DURATION_CAPACITY,
+            durations: Arc::new(Mutex::new(VecDeque::new())),
+        }
+    }
+
+    /// Retain the durations of at most the given number of the most recent
+    /// requests, forgetting older ones.
+    ///
+    /// Defaults to [`DEFAULT_DURATION_CAPACITY`].
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    /// The duration of the most recently completed request, if any.
+    pub fn last_request_duration(&self) -> Option<Duration> {
+        self.durations.lock().unwrap().back().map(|d| d.duration)
+    }
+
+    /// The durations of the requests completed so far (up to the client's
+    /// capacity), in order of completion.
+    pub fn request_durations(&self) -> Vec<RequestDuration> {
+        self.durations.lock().unwrap().iter().copied().collect()
+    }
+
+    /// Take the durations recorded so far (as per
+    /// [`TimedClient::request_durations`]), forgetting them.
+    pub fn take_request_durations(&self) -> Vec<RequestDuration> {
+        self.durations.lock().unwrap().drain(..).collect()
+    }
+
+    /// Forget all durations recorded so far.
+    pub fn clear_request_durations(&self) {
+        self.durations.lock().unwrap().clear();
+    }
+
+    /// A reference to the wrapped client.
+    pub fn inner(&self) -> &C {
+        &self.inner
+    }
+
+    /// Unwrap the underlying client.
+    pub fn into_inner(self) -> C {
+        self.inner
+    }
+}
+
+#[cfg_attr(not(feature = "unsend"), async_trait)]
+#[cfg_attr(feature = "unsend", async_trait(?Send))]
+impl<C> Client for TimedClient<C>
+where
+    C: Client + Send + Sync,
+{
+    fn max_query_conditions(&self) -> Option<usize> {
+        self.inner.max_query_conditions()
+    }
+
+    async fn perform<R>(&self, request: R) -> Result<R::Response>
+    where
+        R: SimpleRequest,
+    {
+        let method = request.method();
+        let start = Instant::now();
+        let result = self.inner.perform(request).await;
+        let mut durations = self.durations.lock().unwrap();
+        durations.push_back(RequestDuration {
+            method,
+            duration: start.elapsed(),
+        });
+        while durations.len() > self.capacity {
+            durations.pop_front();
+        }
+        drop(durations);
+        result
+    }
+}
+
+#[cfg_attr(not(feature = "unsend"), async_trait)]
+#[cfg_attr(feature = "unsend", async_trait(?Send))]
+impl<C> SubscriptionClient for TimedClient<C>
+where
+    C: SubscriptionClient + Send + Sync,
+{
+    async fn subscribe(&self, query: Query) -> Result<Subscription> {
+        self.inner.subscribe(query).await
+    }
+
+    async fn unsubscribe(&self, query: Query) -> Result<()> {
+        self.inner.unsubscribe(query).await
+    }
+
+    fn close(self) -> Result<()> {
+        self.inner.close()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::client::{MockClient, MockRequestMethodMatcher};
+    use crate::test_support::fixture;
+    use tokio::time;
+
+    // Delays each request by the given duration before performing it.
+    struct DelayedClient<C> {
+        inner: C,
+        delay: Duration,
+    }
+
+    #[cfg_attr(not(feature = "unsend"), async_trait)]
+    #[cfg_attr(feature = "unsend", async_trait(?Send))]
+    impl<C: Client + Send + Sync> Client for DelayedClient<C> {
+        async fn perform<R>(&self, request: R) -> Result<R::Response>
+        where
+            R: SimpleRequest,
+        {
+            time::sleep(self.delay).await;
+            self.inner.perform(request).await
+        }
+    }
+
+    #[tokio::test]
+    async fn request_durations_are_recorded() {
+        time::pause();
+        let health = fixture("health");
+        let (mock, driver) =
+            MockClient::new(MockRequestMethodMatcher::default().map(Method::Health, Ok(health)));
+        let driver_hdl = tokio::spawn(async move { driver.run().await });
+
+        let client = TimedClient::new(DelayedClient {
+            inner: mock,
+            delay: Duration::from_millis(250),
+        });
+        assert_eq!(None, client.last_request_duration());
+
+        client.health().await.unwrap();
+        let duration = client.last_request_duration().unwrap();
+        assert!(duration >= Duration::from_millis(250));
+        assert!(duration < Duration::from_millis(300));
+
+        // Failed requests are recorded too.
+        client.status().await.unwrap_err();
+        let durations = client.request_durations();
+        assert_eq!(
+            vec![Method::Health, Method::Status],
+            durations.iter().map(|d| d.method).collect::<Vec<_>>()
+        );
+
+        client.clear_request_durations();
+        assert!(client.request_durations().is_empty());
+
+        client.into_inner().inner.close();
+        driver_hdl.await.unwrap().unwrap();
+    }
+
+    #[tokio::test]
+    async fn request_durations_are_bounded() {
+        let health = fixture("health");
+        let (mock, driver) =
+            MockClient::new(MockRequestMethodMatcher::default().map(Method::Health, Ok(health)));
+        let driver_hdl = tokio::spawn(async move { driver.run().await });
+
+        let client = TimedClient::new(mock).with_capacity(2);
+        client.health().await.unwrap();
+        client.status().await.unwrap_err();
+        client.health().await.unwrap();
+        // Only the most recent requests are retained.
+        assert_eq!(
+            vec![Method::Status, Method::Health],
+            client
+                .request_durations()
+                .iter()
+                .map(|d| d.method)
+                .collect::<Vec<_>>()
+        );
+
+        assert_eq!(2, client.take_request_durations().len());
+        assert!(client.request_durations().is_empty());
+        assert_eq!(None, client.last_request_duration());
+
+        client.into_inner().close();
+        driver_hdl.await.unwrap().unwrap();
+    }
+}
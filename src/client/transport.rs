@@ -3,7 +3,13 @@
 pub mod mock;
 mod router;
 
+#[cfg(feature = "wasm-client")]
+pub mod fetch;
 #[cfg(feature = "http-client")]
 pub mod http;
+#[cfg(any(feature = "http-client", feature = "wasm-client"))]
+pub mod http_url;
+#[cfg(feature = "http-client")]
+pub mod interceptor;
 #[cfg(feature = "websocket-client")]
 pub mod websocket;
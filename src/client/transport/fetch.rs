@@ -0,0 +1,152 @@
+//! HTTP-based transport for Tendermint RPC Client, using the `fetch` API
+//! available in browsers and other JavaScript runtimes.
+
+use crate::client::Client;
+use crate::error::ErrorKind;
+use crate::response::Wrapper;
+use crate::{CompatMode, Error, Response, Result, SimpleRequest};
+use async_trait::async_trait;
+use std::convert::TryInto;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+
+use super::http_url::HttpClientUrl;
+
+#[wasm_bindgen]
+extern "C" {
+    // The global `fetch` function, which (unlike `Window::fetch`) is also
+    // available in web workers and Node.js.
+    #[wasm_bindgen(js_name = fetch)]
+    fn fetch_with_request(request: &web_sys::Request) -> js_sys::Promise;
+}
+
+/// A JSON-RPC/HTTP Tendermint RPC client (implements [`crate::Client`]) for
+/// use when compiling to WebAssembly, which sends requests via the `fetch`
+/// API of the JavaScript runtime it is running in (e.g. a browser).
+///
+/// Requests and responses are handled in the same way as by
+/// [`crate::HttpClient`], except that connections (including any proxying,
+/// redirects and TLS) are managed by the JavaScript runtime.
+///
+/// Does not provide [`crate::event::Event`] subscription facilities.
+///
+/// The futures returned by the client are not `Send`, as JavaScript values
+/// cannot be sent between threads, which is why this feature implies the
+/// `unsend` feature. Client helpers which rely on Tokio's timer (e.g.
+/// [`Client::wait_until_healthy`]) are not usable in browsers.
+///
+/// ## Examples
+///
+/// ```rust,ignore
+/// use tendermint_rpc::{Client, FetchClient};
+///
+/// wasm_bindgen_futures::spawn_local(async {
+///     let client = FetchClient::new("http://127.0.0.1:26657").unwrap();
+///
+///     let status = client.status().await.unwrap();
+///     web_sys::console::log_1(&status.sync_info.latest_block_height.to_string().into());
+/// });
+/// ```
+#[derive(Debug, Clone)]
+pub struct FetchClient {
+    url: HttpClientUrl,
+    compat: CompatMode,
+}
+
+impl FetchClient {
+    /// Construct a new Tendermint RPC client sending requests to the given
+    /// URL.
+    pub fn new<U>(url: U) -> Result<Self>
+    where
+        U: TryInto<HttpClientUrl, Error = Error>,
+    {
+        Ok(Self {
+            url: url.try_into()?,
+            compat: CompatMode::default(),
+        })
+    }
+
+    /// Encode requests in the form expected by nodes speaking the given
+    /// version of the RPC protocol (see [`CompatMode`]).
+    ///
+    /// Defaults to [`CompatMode::V0_34`].
+    pub fn compat_mode(mut self, compat: CompatMode) -> Self {
+        self.compat = compat;
+        self
+    }
+
+    /// Perform the given request, returning the full JSON-RPC response
+    /// envelope (including its `id` and `jsonrpc` version) rather than only
+    /// its result.
+    ///
+    /// Unlike [`Client::perform`], this does not fail if the node responds
+    /// with an error; see [`Wrapper::into_result`] for extracting the result.
+    pub async fn perform_wrapped<R>(&self, request: R) -> Result<Wrapper<R::Response>>
+    where
+        R: SimpleRequest,
+    {
+        Wrapper::from_string(self.send(request).await?)
+    }
+
+    /// Send the given request, returning the body of the response.
+    async fn send<R>(&self, request: R) -> Result<String>
+    where
+        R: SimpleRequest,
+    {
+        let url = self.url.0.to_string();
+        let request_body = request.into_json_formatted(self.compat, false);
+        tracing::debug!("Outgoing request: {}", request_body);
+
+        let mut init = web_sys::RequestInit::new();
+        init.method("POST")
+            .body(Some(&JsValue::from_str(&request_body)));
+        let request = web_sys::Request::new_with_str_and_init(&url, &init)
+            .map_err(|e| js_error(format!("failed to build request to {}", url), e))?;
+        request
+            .headers()
+            .set("Content-Type", "application/json")
+            .map_err(|e| js_error(format!("failed to build request to {}", url), e))?;
+
+        let response: web_sys::Response = JsFuture::from(fetch_with_request(&request))
+            .await
+            .and_then(|response| response.dyn_into())
+            .map_err(|e| js_error(format!("failed to send request to {}", url), e))?;
+        if let Some(kind) = ErrorKind::from_http_status(response.status()) {
+            return Err(Error::http_error(format!(
+                "received {} {} from {}",
+                response.status(),
+                response.status_text(),
+                url
+            ))
+            .with_kind(kind));
+        }
+        let response_body = response
+            .text()
+            .map(JsFuture::from)
+            .map_err(|e| js_error(format!("failed to read response from {}", url), e))?
+            .await
+            .map_err(|e| js_error(format!("failed to read response from {}", url), e))?
+            .as_string()
+            .ok_or_else(|| {
+                Error::http_error(format!("received a non-text response from {}", url))
+            })?;
+        tracing::debug!("Incoming response: {}", response_body);
+        Ok(response_body)
+    }
+}
+
+#[async_trait(?Send)]
+impl Client for FetchClient {
+    async fn perform<R>(&self, request: R) -> Result<R::Response>
+    where
+        R: SimpleRequest,
+    {
+        R::Response::from_string(self.send(request).await?)
+    }
+}
+
+/// An HTTP error for the given JavaScript exception.
+fn js_error(context: String, e: JsValue) -> Error {
+    Error::http_error(format!("{}: {:?}", context, e))
+}
@@ -1,21 +1,55 @@
 //! HTTP-based transport for Tendermint RPC Client.
 
 use crate::client::Client;
-use crate::{Error, Result, Scheme, SimpleRequest, Url};
+use crate::response::Wrapper;
+use crate::{CompatMode, Error, Result, SimpleRequest};
 use async_trait::async_trait;
 use std::convert::{TryFrom, TryInto};
-use std::str::FromStr;
-use tendermint::net;
+use std::sync::Arc;
+use std::time::Duration;
+
+pub use super::http_url::HttpClientUrl;
+use super::interceptor::RequestInterceptor;
 
 /// A JSON-RPC/HTTP Tendermint RPC client (implements [`crate::Client`]).
 ///
 /// Supports both HTTP and HTTPS connections to Tendermint RPC endpoints, and
 /// allows for the use of HTTP proxies (see [`HttpClient::new_with_proxy`] for
-/// details).
+/// details). Further configuration is available via [`HttpClient::builder`].
 ///
 /// Does not provide [`crate::event::Event`] subscription facilities (see
 /// [`crate::WebSocketClient`] for a client that does).
 ///
+/// The client is `Send + Sync`, and is cheap to clone: clones share the same
+/// underlying connection pool, so a single client can be cloned into as many
+/// asynchronous tasks as needed.
+///
+/// ### HTTP/2
+///
+/// Over HTTPS, HTTP/2 is used if the server supports it (as negotiated via
+/// ALPN), falling back to HTTP/1.1 otherwise. Over plain HTTP, HTTP/1.1 is
+/// used unless [`HttpClientBuilder::http2_prior_knowledge`] is enabled.
+///
+/// ### Idle connections
+///
+/// Connections are kept open between requests so that they can be reused,
+/// but servers (and load balancers in front of them) eventually close idle
+/// connections, which may only be noticed when a request is next sent over
+/// one. To avoid this, [`HttpClientBuilder::pool_idle_timeout`] should be
+/// shorter than the server's own idle timeout.
+///
+/// ### Redirects
+///
+/// By default, HTTP redirects are not followed, and result in an error. See
+/// [`HttpClientBuilder::max_redirects`] to follow them.
+///
+/// ### Interceptors
+///
+/// Requests and responses can be inspected or modified (e.g. to attach
+/// credentials) by interceptors added via [`HttpClientBuilder::interceptor`].
+/// Redirects are followed with the request as intercepted, and interceptors
+/// only see the final response.
+///
 /// ## Examples
 ///
 /// ```rust,ignore
@@ -36,6 +70,7 @@ use tendermint::net;
 #[derive(Debug, Clone)]
 pub struct HttpClient {
     inner: sealed::HttpClient,
+    max_query_conditions: Option<usize>,
 }
 
 impl HttpClient {
@@ -45,14 +80,7 @@ impl HttpClient {
     where
         U: TryInto<HttpClientUrl, Error = Error>,
     {
-        let url = url.try_into()?;
-        Ok(Self {
-            inner: if url.0.is_secure() {
-                sealed::HttpClient::new_https(url.try_into()?)
-            } else {
-                sealed::HttpClient::new_http(url.try_into()?)
-            },
-        })
+        Self::builder(url.try_into()?).build()
     }
 
     /// Construct a new Tendermint RPC HTTP/S client connecting to the given
@@ -67,79 +95,283 @@ impl HttpClient {
         U: TryInto<HttpClientUrl, Error = Error>,
         P: TryInto<HttpClientUrl, Error = Error>,
     {
-        let url = url.try_into()?;
-        let proxy_url = proxy_url.try_into()?;
-        Ok(Self {
-            inner: if proxy_url.0.is_secure() {
-                sealed::HttpClient::new_https_proxy(url.try_into()?, proxy_url.try_into()?)?
-            } else {
-                sealed::HttpClient::new_http_proxy(url.try_into()?, proxy_url.try_into()?)?
-            },
-        })
+        Self::builder(url.try_into()?)
+            .proxy_url(proxy_url.try_into()?)
+            .build()
     }
-}
 
-#[async_trait]
-impl Client for HttpClient {
-    async fn perform<R>(&self, request: R) -> Result<R::Response>
-    where
-        R: SimpleRequest,
-    {
-        self.inner.perform(request).await
+    /// Initiate a builder for a Tendermint RPC HTTP/S client connecting to
+    /// the given URL, allowing for further configuration of the client.
+    pub fn builder(url: HttpClientUrl) -> HttpClientBuilder {
+        HttpClientBuilder::new(url)
     }
 }
 
-/// A URL limited to use with HTTP clients.
+/// A builder for [`HttpClient`]s, allowing for configuration of the client
+/// beyond that which is available through [`HttpClient::new`].
+///
+/// ## Examples
 ///
-/// Facilitates useful type conversions and inferences.
+/// ```rust,ignore
+/// use std::time::Duration;
+/// use tendermint_rpc::HttpClient;
+///
+/// let client = HttpClient::builder("http://127.0.0.1:26657".parse().unwrap())
+///     .connect_timeout(Duration::from_secs(5))
+///     .build()
+///     .unwrap();
+/// ```
 #[derive(Debug, Clone)]
-pub struct HttpClientUrl(Url);
-
-impl TryFrom<Url> for HttpClientUrl {
-    type Error = Error;
+pub struct HttpClientBuilder {
+    url: HttpClientUrl,
+    proxy_url: Option<HttpClientUrl>,
+    config: sealed::Config,
+}
 
-    fn try_from(value: Url) -> Result<Self> {
-        match value.scheme() {
-            Scheme::Http | Scheme::Https => Ok(Self(value)),
-            _ => Err(Error::invalid_params(&format!(
-                "cannot use URL {} with HTTP clients",
-                value
-            ))),
+impl HttpClientBuilder {
+    /// Initiate a builder for a client connecting to the given URL.
+    pub fn new(url: HttpClientUrl) -> Self {
+        Self {
+            url,
+            proxy_url: None,
+            config: sealed::Config::default(),
         }
     }
-}
 
-impl FromStr for HttpClientUrl {
-    type Err = Error;
+    /// Submit requests via the HTTP/S proxy at the given URL.
+    ///
+    /// If the RPC endpoint is secured (HTTPS), the proxy will automatically
+    /// attempt to connect using the [HTTP CONNECT] method.
+    ///
+    /// [HTTP CONNECT]: https://en.wikipedia.org/wiki/HTTP_tunnel
+    pub fn proxy_url(mut self, proxy_url: HttpClientUrl) -> Self {
+        self.proxy_url = Some(proxy_url);
+        self
+    }
 
-    fn from_str(s: &str) -> Result<Self> {
-        let url: Url = s.parse()?;
-        url.try_into()
+    /// Limit the time spent establishing a TCP connection to the RPC endpoint
+    /// (or the proxy, if one is configured).
+    ///
+    /// This is independent of how long a request may take once connected. By
+    /// default, connection attempts are only limited by the operating system's
+    /// own TCP timeout, which can be several minutes for unreachable hosts.
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.config.connect_timeout = Some(timeout);
+        self
     }
-}
 
-impl TryFrom<&str> for HttpClientUrl {
-    type Error = Error;
+    /// Whether to disable Nagle's algorithm (i.e. set `TCP_NODELAY`) on
+    /// connections to the RPC endpoint (or the proxy, if one is configured),
+    /// trading bandwidth efficiency for lower latency.
+    ///
+    /// Defaults to `false`.
+    pub fn tcp_nodelay(mut self, nodelay: bool) -> Self {
+        self.config.tcp_nodelay = nodelay;
+        self
+    }
+
+    /// Enable TCP keepalive on connections to the RPC endpoint (or the proxy,
+    /// if one is configured), sending the first keepalive probe after a
+    /// connection has been idle for the given duration.
+    ///
+    /// Defaults to disabled.
+    pub fn tcp_keepalive(mut self, idle: Duration) -> Self {
+        self.config.tcp_keepalive = Some(idle);
+        self
+    }
+
+    /// Close connections which have been idle for longer than the given
+    /// duration, rather than reusing them for later requests.
+    ///
+    /// Expired connections are closed in the background, rather than only
+    /// being discarded when the next request is made. Setting this lower than
+    /// the server's idle timeout avoids sending a request over a connection
+    /// the server has just closed.
+    ///
+    /// Defaults to 90 seconds.
+    pub fn pool_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.config.pool_idle_timeout = Some(timeout);
+        self
+    }
+
+    /// Keep at most the given number of idle connections open for reuse.
+    ///
+    /// Set this to 0 to close every connection once its request completes.
+    ///
+    /// Defaults to no limit.
+    pub fn pool_max_idle(mut self, max: usize) -> Self {
+        self.config.pool_max_idle = Some(max);
+        self
+    }
+
+    /// Speak HTTP/2 directly over plain HTTP connections to the RPC endpoint
+    /// (i.e. "h2c with prior knowledge"), multiplexing concurrent requests
+    /// over a single connection.
+    ///
+    /// There is no way to fall back to HTTP/1.1 in this mode, so it should
+    /// only be enabled if the endpoint (or a reverse proxy in front of it) is
+    /// known to support HTTP/2. It has no effect on HTTPS connections, over
+    /// which HTTP/2 is negotiated automatically, nor on connections via a
+    /// proxy.
+    ///
+    /// Defaults to `false`.
+    pub fn http2_prior_knowledge(mut self, enable: bool) -> Self {
+        self.config.http2_prior_knowledge = enable;
+        self
+    }
+
+    /// Encode requests in the form expected by nodes speaking the given
+    /// version of the RPC protocol (see [`CompatMode`]).
+    ///
+    /// Defaults to [`CompatMode::V0_34`].
+    pub fn compat_mode(mut self, compat: CompatMode) -> Self {
+        self.config.compat = compat;
+        self
+    }
+
+    /// Send requests as pretty-printed rather than compact JSON, e.g. to make
+    /// them easier to inspect in logs or captured traffic. Outgoing requests
+    /// are logged at the `debug` level in either case.
+    ///
+    /// Defaults to `false`, as compact requests use less bandwidth.
+    pub fn pretty_print_requests(mut self, pretty: bool) -> Self {
+        self.config.pretty_requests = pretty;
+        self
+    }
+
+    /// Follow up to the given number of HTTP redirects per request.
+    ///
+    /// Each redirected request is re-sent using the `POST` method, with the
+    /// same body and headers as the original. Redirects to a different host
+    /// (or port) result in an error unless explicitly allowed via
+    /// [`HttpClientBuilder::allow_cross_host_redirects`].
+    ///
+    /// Defaults to 0, i.e. redirects are not followed.
+    pub fn max_redirects(mut self, max: usize) -> Self {
+        self.config.max_redirects = max;
+        self
+    }
+
+    /// Whether to follow redirects to hosts other than the one to which the
+    /// original request was sent. Only has an effect if redirects are
+    /// followed (see [`HttpClientBuilder::max_redirects`]).
+    ///
+    /// Defaults to `false`.
+    pub fn allow_cross_host_redirects(mut self, allow: bool) -> Self {
+        self.config.allow_cross_host_redirects = allow;
+        self
+    }
 
-    fn try_from(value: &str) -> Result<Self> {
-        value.parse()
+    /// Whether to check that the ID of each response matches that of the
+    /// request, failing the request otherwise. This guards against accepting
+    /// a response intended for another request, e.g. from a misbehaving
+    /// caching proxy.
+    ///
+    /// Defaults to `false`, as some proxies rewrite response IDs.
+    pub fn strict_response_ids(mut self, strict: bool) -> Self {
+        self.config.strict_response_ids = strict;
+        self
+    }
+
+    /// Whether to reject responses containing both a `result` and an `error`
+    /// outright (see [`Response::from_string_strict`]), rather than treating
+    /// them as errors and only logging a warning.
+    ///
+    /// Defaults to `false`.
+    ///
+    /// [`Response::from_string_strict`]: crate::Response::from_string_strict
+    pub fn strict_responses(mut self, strict: bool) -> Self {
+        self.config.strict_responses = strict;
+        self
+    }
+
+    /// Fail requests whose responses have bodies larger than the given number
+    /// of bytes, rather than buffering them in full.
+    ///
+    /// The limit applies to the body as received, regardless of whether its
+    /// size is announced up front (via `Content-Length`) or it is streamed
+    /// (e.g. with `Transfer-Encoding: chunked`, as by some proxies).
+    ///
+    /// Defaults to no limit.
+    pub fn max_response_size(mut self, max: usize) -> Self {
+        self.config.max_response_size = Some(max);
+        self
+    }
+
+    /// Reject queries (e.g. for `/tx_search`) with more than the given number
+    /// of conditions without sending them (see
+    /// [`Client::max_query_conditions`]), e.g. to match a limit enforced by
+    /// a proxy in front of the node.
+    ///
+    /// Defaults to no limit.
+    pub fn max_query_conditions(mut self, max: usize) -> Self {
+        self.config.max_query_conditions = Some(max);
+        self
+    }
+
+    /// Add the given interceptor to the end of the chain of interceptors
+    /// invoked for each request and response (see [`RequestInterceptor`]).
+    pub fn interceptor<I>(mut self, interceptor: I) -> Self
+    where
+        I: RequestInterceptor + 'static,
+    {
+        self.config.interceptors.push(Arc::new(interceptor));
+        self
+    }
+
+    /// Construct the client.
+    pub fn build(self) -> Result<HttpClient> {
+        let config = &self.config;
+        let inner = match self.proxy_url {
+            None if self.url.0.is_secure() => {
+                sealed::HttpClient::new_https(self.url.try_into()?, config)
+            }
+            None => sealed::HttpClient::new_http(self.url.try_into()?, config),
+            Some(proxy_url) if proxy_url.0.is_secure() => sealed::HttpClient::new_https_proxy(
+                self.url.try_into()?,
+                proxy_url.try_into()?,
+                config,
+            )?,
+            Some(proxy_url) => sealed::HttpClient::new_http_proxy(
+                self.url.try_into()?,
+                proxy_url.try_into()?,
+                config,
+            )?,
+        };
+        Ok(HttpClient {
+            inner,
+            max_query_conditions: self.config.max_query_conditions,
+        })
     }
 }
 
-impl TryFrom<net::Address> for HttpClientUrl {
-    type Error = Error;
+impl HttpClient {
+    /// Perform the given request, returning the full JSON-RPC response
+    /// envelope (including its `id` and `jsonrpc` version) rather than only
+    /// its result.
+    ///
+    /// Unlike [`Client::perform`], this does not fail if the node responds
+    /// with an error; see [`Wrapper::into_result`] for extracting the result.
+    pub async fn perform_wrapped<R>(&self, request: R) -> Result<Wrapper<R::Response>>
+    where
+        R: SimpleRequest,
+    {
+        self.inner.perform_wrapped(request).await
+    }
+}
 
-    fn try_from(value: net::Address) -> Result<Self> {
-        match value {
-            net::Address::Tcp {
-                peer_id: _,
-                host,
-                port,
-            } => format!("http://{}:{}", host, port).parse(),
-            net::Address::Unix { .. } => Err(Error::invalid_params(
-                "only TCP-based node addresses are supported",
-            )),
-        }
+#[cfg_attr(not(feature = "unsend"), async_trait)]
+#[cfg_attr(feature = "unsend", async_trait(?Send))]
+impl Client for HttpClient {
+    fn max_query_conditions(&self) -> Option<usize> {
+        self.max_query_conditions
+    }
+
+    async fn perform<R>(&self, request: R) -> Result<R::Response>
+    where
+        R: SimpleRequest,
+    {
+        self.inner.perform(request).await
     }
 }
 
@@ -152,25 +384,53 @@ impl TryFrom<HttpClientUrl> for hyper::Uri {
 }
 
 mod sealed {
-    use crate::{Error, Response, Result, SimpleRequest};
-    use hyper::body::Buf;
+    use crate::client::transport::interceptor::{IncomingResponse, Interceptors, OutgoingRequest};
+    use crate::error::ErrorKind;
+    use crate::response::Wrapper;
+    use crate::{CompatMode, Error, Response, Result, SimpleRequest};
+    use hyper::body::{Bytes, HttpBody};
     use hyper::client::connect::Connect;
     use hyper::client::HttpConnector;
     use hyper::{header, Uri};
     use hyper_proxy::{Intercept, Proxy, ProxyConnector};
     use hyper_rustls::HttpsConnector;
-    use std::io::Read;
+    use std::time::Duration;
+
+    /// Configuration shared by all variants of our `hyper`-based client.
+    #[derive(Debug, Clone, Default)]
+    pub struct Config {
+        pub connect_timeout: Option<Duration>,
+        pub tcp_nodelay: bool,
+        pub tcp_keepalive: Option<Duration>,
+        pub compat: CompatMode,
+        pub http2_prior_knowledge: bool,
+        pub max_redirects: usize,
+        pub allow_cross_host_redirects: bool,
+        pub pretty_requests: bool,
+        pub pool_idle_timeout: Option<Duration>,
+        pub pool_max_idle: Option<usize>,
+        pub strict_response_ids: bool,
+        pub strict_responses: bool,
+        pub max_response_size: Option<usize>,
+        pub max_query_conditions: Option<usize>,
+        pub interceptors: Interceptors,
+    }
 
     /// A wrapper for a `hyper`-based client, generic over the connector type.
     #[derive(Debug, Clone)]
     pub struct HyperClient<C> {
         uri: Uri,
         inner: hyper::Client<C>,
+        config: Config,
     }
 
     impl<C> HyperClient<C> {
-        pub fn new(uri: Uri, inner: hyper::Client<C>) -> Self {
-            Self { uri, inner }
+        pub fn new(uri: Uri, inner: hyper::Client<C>, config: &Config) -> Self {
+            Self {
+                uri,
+                inner,
+                config: config.clone(),
+            }
         }
     }
 
@@ -182,11 +442,71 @@ mod sealed {
         where
             R: SimpleRequest,
         {
-            let request = self.build_request(request)?;
-            let response = self.inner.request(request).await?;
-            let response_body = response_to_string(response).await?;
-            tracing::debug!("Incoming response: {}", response_body);
-            R::Response::from_string(&response_body)
+            let response = self.send(request).await?;
+            if self.config.strict_responses {
+                R::Response::from_string_strict(response)
+            } else {
+                R::Response::from_string(response)
+            }
+        }
+
+        pub async fn perform_wrapped<R>(&self, request: R) -> Result<Wrapper<R::Response>>
+        where
+            R: SimpleRequest,
+        {
+            Wrapper::from_string(self.send(request).await?)
+        }
+
+        /// Send the given request, returning the body of the response.
+        async fn send<R>(&self, request: R) -> Result<Bytes>
+        where
+            R: SimpleRequest,
+        {
+            let method = request.method();
+            let outgoing = self.outgoing(request)?;
+            tracing::debug!("Outgoing request: {}", outgoing.body);
+            let mut uri = self.uri.clone();
+            let mut redirects = 0;
+            let response = loop {
+                let request = build_request_to(&uri, &outgoing)?;
+                let response = self.inner.request(request).await.map_err(|e| {
+                    if e.is_connect() {
+                        Error::http_error(format!("failed to connect to {}: {}", uri, e))
+                            .with_source(e)
+                    } else {
+                        e.into()
+                    }
+                })?;
+                if !response.status().is_redirection() {
+                    break response;
+                }
+                uri = self.redirect_target(&uri, &response, redirects)?;
+                redirects += 1;
+                tracing::debug!("Following redirect to {}", uri);
+            };
+            let (parts, body) = response.into_parts();
+            let mut incoming = IncomingResponse {
+                method,
+                status: parts.status,
+                headers: parts.headers,
+                body: read_body(body, self.config.max_response_size, &uri).await?,
+            };
+            self.config.interceptors.after_receive(&mut incoming)?;
+            if let Some(kind) = ErrorKind::from_http_status(incoming.status.as_u16()) {
+                return Err(Error::http_error(format!(
+                    "received {} from {}",
+                    incoming.status, uri
+                ))
+                .with_kind(kind));
+            }
+            tracing::debug!(
+                "Incoming response: {}",
+                String::from_utf8_lossy(&incoming.body)
+            );
+            if self.config.strict_response_ids {
+                check_response_id(&outgoing, &incoming.body, &uri)?;
+            }
+            Ok(incoming.body.into())
         }
     }
 
@@ -196,25 +516,127 @@ mod sealed {
             &self,
             request: R,
         ) -> Result<hyper::Request<hyper::Body>> {
-            let request_body = request.into_json();
-
-            let mut request = hyper::Request::builder()
-                .method("POST")
-                .uri(&self.uri)
-                .body(hyper::Body::from(request_body.into_bytes()))?;
-
-            {
-                let headers = request.headers_mut();
-                headers.insert(header::CONTENT_TYPE, "application/json".parse().unwrap());
-                headers.insert(
-                    header::USER_AGENT,
-                    format!("tendermint.rs/{}", env!("CARGO_PKG_VERSION"))
-                        .parse()
-                        .unwrap(),
-                );
+            build_request_to(&self.uri, &self.outgoing(request)?)
+        }
+
+        /// Serialize the given request, passing it through the configured
+        /// interceptors.
+        fn outgoing<R: SimpleRequest>(&self, request: R) -> Result<OutgoingRequest> {
+            let mut headers = header::HeaderMap::new();
+            headers.insert(header::CONTENT_TYPE, "application/json".parse().unwrap());
+            headers.insert(
+                header::USER_AGENT,
+                format!("tendermint.rs/{}", env!("CARGO_PKG_VERSION"))
+                    .parse()
+                    .unwrap(),
+            );
+            let mut outgoing = OutgoingRequest {
+                method: request.method(),
+                headers,
+                body: request.into_json_formatted(self.config.compat, self.config.pretty_requests),
+            };
+            self.config.interceptors.before_send(&mut outgoing)?;
+            Ok(outgoing)
+        }
+
+        /// Determine where the given redirect response (the `redirects`th
+        /// received for this request) sends us, if we are to follow it.
+        fn redirect_target(
+            &self,
+            uri: &Uri,
+            response: &hyper::Response<hyper::Body>,
+            redirects: usize,
+        ) -> Result<Uri> {
+            let status = response.status();
+            if redirects >= self.config.max_redirects {
+                return Err(Error::http_error(format!(
+                    "received redirect ({}) from {} after following {} of at most {} redirects",
+                    status, uri, redirects, self.config.max_redirects
+                ))
+                .with_kind(ErrorKind::Client));
+            }
+            let location = response
+                .headers()
+                .get(header::LOCATION)
+                .and_then(|location| location.to_str().ok())
+                .ok_or_else(|| {
+                    Error::http_error(format!(
+                        "received redirect ({}) from {} without a valid location",
+                        status, uri
+                    ))
+                    .with_kind(ErrorKind::Client)
+                })?;
+            // The location may be relative to the URI we requested.
+            let target: Uri = url::Url::parse(&uri.to_string())?
+                .join(location)?
+                .to_string()
+                .parse()?;
+            if target.authority() != uri.authority() && !self.config.allow_cross_host_redirects {
+                return Err(Error::http_error(format!(
+                    "refusing to follow redirect ({}) from {} to a different host: {}",
+                    status, uri, target
+                ))
+                .with_kind(ErrorKind::Client));
             }
+            Ok(target)
+        }
+    }
+
+    /// Build a Tendermint RPC request with the given (serialized) body and
+    /// headers, to be sent to the given URI.
+    fn build_request_to(
+        uri: &Uri,
+        outgoing: &OutgoingRequest,
+    ) -> Result<hyper::Request<hyper::Body>> {
+        let mut request = hyper::Request::builder()
+            .method("POST")
+            .uri(uri)
+            .body(hyper::Body::from(outgoing.body.clone()))?;
+        *request.headers_mut() = outgoing.headers.clone();
+        Ok(request)
+    }
 
-            Ok(request)
+    /// Read the given response body in full, whether or not its length is
+    /// known up front, failing once it exceeds the given size.
+    async fn read_body(
+        mut body: hyper::Body,
+        max_size: Option<usize>,
+        uri: &Uri,
+    ) -> Result<Vec<u8>> {
+        let max_size = max_size.unwrap_or(usize::MAX);
+        let mut buf = Vec::new();
+        while let Some(chunk) = body.data().await {
+            let chunk = chunk?;
+            if buf.len() + chunk.len() > max_size {
+                return Err(Error::http_error(format!(
+                    "response from {} exceeds the maximum size of {} bytes",
+                    uri, max_size
+                ))
+                .with_kind(ErrorKind::Client));
+            }
+            buf.extend_from_slice(&chunk);
+        }
+        Ok(buf)
+    }
+
+    /// Ensure that the ID of the given response body matches that of the
+    /// given request. Bodies which cannot be parsed are left for the caller
+    /// to reject.
+    fn check_response_id(outgoing: &OutgoingRequest, body: &[u8], uri: &Uri) -> Result<()> {
+        let id_of = |json: &[u8]| {
+            serde_json::from_slice::<serde_json::Value>(json)
+                .ok()
+                .map(|value| value["id"].clone())
+        };
+        match (id_of(outgoing.body.as_bytes()), id_of(body)) {
+            (Some(expected), Some(actual)) if expected != actual => {
+                Err(Error::http_error(format!(
+                    "response ID {} from {} does not match request ID {}",
+                    actual, uri, expected
+                ))
+                .with_kind(ErrorKind::Server))
+            }
+            _ => Ok(()),
         }
     }
 
@@ -232,33 +654,41 @@ mod sealed {
     }
 
     impl HttpClient {
-        pub fn new_http(uri: Uri) -> Self {
-            Self::Http(HyperClient::new(uri, hyper::Client::new()))
+        pub fn new_http(uri: Uri, config: &Config) -> Self {
+            Self::Http(HyperClient::new(
+                uri,
+                client_builder(config)
+                    .http2_only(config.http2_prior_knowledge)
+                    .build(http_connector(config)),
+                config,
+            ))
         }
 
-        pub fn new_https(uri: Uri) -> Self {
+        pub fn new_https(uri: Uri, config: &Config) -> Self {
             Self::Https(HyperClient::new(
                 uri,
-                hyper::Client::builder().build(HttpsConnector::with_native_roots()),
+                client_builder(config).build(https_connector(config)),
+                config,
             ))
         }
 
-        pub fn new_http_proxy(uri: Uri, proxy_uri: Uri) -> Result<Self> {
+        pub fn new_http_proxy(uri: Uri, proxy_uri: Uri, config: &Config) -> Result<Self> {
             let proxy = Proxy::new(Intercept::All, proxy_uri);
-            let proxy_connector = ProxyConnector::from_proxy(HttpConnector::new(), proxy)?;
+            let proxy_connector = ProxyConnector::from_proxy(http_connector(config), proxy)?;
             Ok(Self::HttpProxy(HyperClient::new(
                 uri,
-                hyper::Client::builder().build(proxy_connector),
+                client_builder(config).build(proxy_connector),
+                config,
             )))
         }
 
-        pub fn new_https_proxy(uri: Uri, proxy_uri: Uri) -> Result<Self> {
+        pub fn new_https_proxy(uri: Uri, proxy_uri: Uri, config: &Config) -> Result<Self> {
             let proxy = Proxy::new(Intercept::All, proxy_uri);
-            let proxy_connector =
-                ProxyConnector::from_proxy(HttpsConnector::with_native_roots(), proxy)?;
+            let proxy_connector = ProxyConnector::from_proxy(https_connector(config), proxy)?;
             Ok(Self::HttpsProxy(HyperClient::new(
                 uri,
-                hyper::Client::builder().build(proxy_connector),
+                client_builder(config).build(proxy_connector),
+                config,
             )))
         }
 
@@ -273,15 +703,709 @@ mod sealed {
                 HttpClient::HttpsProxy(c) => c.perform(request).await,
             }
         }
+
+        pub async fn perform_wrapped<R>(&self, request: R) -> Result<Wrapper<R::Response>>
+        where
+            R: SimpleRequest,
+        {
+            match self {
+                HttpClient::Http(c) => c.perform_wrapped(request).await,
+                HttpClient::Https(c) => c.perform_wrapped(request).await,
+                HttpClient::HttpProxy(c) => c.perform_wrapped(request).await,
+                HttpClient::HttpsProxy(c) => c.perform_wrapped(request).await,
+            }
+        }
+    }
+
+    fn client_builder(config: &Config) -> hyper::client::Builder {
+        let mut builder = hyper::Client::builder();
+        if let Some(timeout) = config.pool_idle_timeout {
+            builder.pool_idle_timeout(timeout);
+        }
+        if let Some(max) = config.pool_max_idle {
+            builder.pool_max_idle_per_host(max);
+        }
+        builder
+    }
+
+    fn http_connector(config: &Config) -> HttpConnector {
+        let mut connector = HttpConnector::new();
+        connector.set_connect_timeout(config.connect_timeout);
+        connector.set_nodelay(config.tcp_nodelay);
+        connector.set_keepalive(config.tcp_keepalive);
+        connector
+    }
+
+    /// Equivalent to [`HttpsConnector::with_native_roots`], but wrapping our
+    /// own configured [`HttpConnector`].
+    fn https_connector(config: &Config) -> HttpsConnector<HttpConnector> {
+        let mut http = http_connector(config);
+        // The TLS layer is responsible for enforcing the scheme.
+        http.enforce_http(false);
+        let mut tls = rustls::ClientConfig::new();
+        tls.root_store = match rustls_native_certs::load_native_certs() {
+            Ok(store) | Err((Some(store), _)) => store,
+            Err((None, e)) => Err(e).expect("cannot access native certificate store"),
+        };
+        tls.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+        HttpsConnector::from((http, tls))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::client::sync::{unbounded, ChannelRx};
+    use crate::client::transport::interceptor::{IncomingResponse, OutgoingRequest};
+    use crate::test_support::{fixture, hanging_listener};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::time::Instant;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{TcpListener, TcpStream};
+
+    // Serves HTTP requests, redirecting those for `/` to `location` and
+    // responding to those for `/rpc` with a successful `/health` response,
+    // provided the original request body reached it.
+    fn serve_redirects(listener: TcpListener, location: String) {
+        tokio::spawn(async move {
+            loop {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let (head, body) = read_request(&mut stream).await;
+                let response = match head.split_whitespace().nth(1).unwrap() {
+                    "/" => format!(
+                        "HTTP/1.1 307 Temporary Redirect\r\nLocation: {}\r\n\
+                         Content-Length: 0\r\nConnection: close\r\n\r\n",
+                        location
+                    )
+                    .into_bytes(),
+                    "/rpc" if body.contains("\"health\"") => {
+                        json_response("200 OK", r#"{"jsonrpc":"2.0","id":"","result":{}}"#)
+                    }
+                    _ => json_response("400 Bad Request", ""),
+                };
+                stream.write_all(&response).await.unwrap();
+                stream.shutdown().await.unwrap();
+            }
+        });
+    }
+
+    // Reads a single HTTP request from the stream, returning its head (the
+    // request line and headers) and body.
+    async fn read_request(stream: &mut TcpStream) -> (String, String) {
+        let mut buf = Vec::new();
+        let mut chunk = [0_u8; 1024];
+        let header_end = loop {
+            let n = stream.read(&mut chunk).await.unwrap();
+            buf.extend_from_slice(&chunk[..n]);
+            if let Some(pos) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+                break pos + 4;
+            }
+        };
+        let head = String::from_utf8_lossy(&buf[..header_end]).to_string();
+        let content_length = head
+            .lines()
+            .filter_map(|line| line.split_once(':'))
+            .find(|(name, _)| name.eq_ignore_ascii_case("content-length"))
+            .map(|(_, value)| value.trim().parse::<usize>().unwrap())
+            .unwrap_or(0);
+        while buf.len() < header_end + content_length {
+            let n = stream.read(&mut chunk).await.unwrap();
+            buf.extend_from_slice(&chunk[..n]);
+        }
+        let body = String::from_utf8_lossy(&buf[header_end..]).to_string();
+        (head, body)
+    }
+
+    // Serves HTTP requests, writing back whatever raw response `respond`
+    // returns given the head and body of each, and returns the server's URL.
+    async fn serve<F>(mut respond: F) -> HttpClientUrl
+    where
+        F: FnMut(&str, &str) -> Vec<u8> + Send + 'static,
+    {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap())
+            .parse()
+            .unwrap();
+        tokio::spawn(async move {
+            loop {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let (head, body) = read_request(&mut stream).await;
+                let response = respond(&head, &body);
+                // The client may have given up on the response.
+                let _ = stream.write_all(&response).await;
+                let _ = stream.shutdown().await;
+            }
+        });
+        url
+    }
+
+    // An HTTP response with the given status and JSON body.
+    fn json_response(status: &str, body: &str) -> Vec<u8> {
+        format!(
+            "HTTP/1.1 {}\r\nContent-Type: application/json\r\n\
+             Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+            status,
+            body.len(),
+            body
+        )
+        .into_bytes()
+    }
+
+    // Serves HTTP requests with the JSON returned by `respond` given the body
+    // of each, returning the server's URL and the bodies of the requests it
+    // has received.
+    async fn serve_json<F>(mut respond: F) -> (HttpClientUrl, Arc<Mutex<Vec<String>>>)
+    where
+        F: FnMut(&str) -> String + Send + 'static,
+    {
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let received = requests.clone();
+        let url = serve(move |_, body| {
+            received.lock().unwrap().push(body.to_string());
+            json_response("200 OK", &respond(body))
+        })
+        .await;
+        (url, requests)
+    }
+
+    // The given JSON-RPC response, with the ID of the given request, as a node
+    // would respond.
+    fn respond_to(request: &str, response: &str) -> serde_json::Value {
+        let request: serde_json::Value = serde_json::from_str(request).unwrap();
+        let mut response: serde_json::Value = serde_json::from_str(response).unwrap();
+        response["id"] = request["id"].clone();
+        response
+    }
+
+    async fn redirecting_server(location: impl FnOnce(u16) -> String) -> HttpClientUrl {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        serve_redirects(listener, location(port));
+        format!("http://127.0.0.1:{}/", port).parse().unwrap()
+    }
+
+    #[tokio::test]
+    async fn tx_search_sends_query_param() {
+        let (url, requests) = serve_json(|_| fixture("tx_search_no_prove")).await;
+
+        let query = crate::query::Query::from(crate::query::EventType::Tx)
+            .and_eq("transfer.sender", "it's \"quoted\"");
+        let client = HttpClient::new(url).unwrap();
+        client
+            .tx_search(query.clone(), false, 1, 10, crate::Order::Ascending)
+            .await
+            .unwrap();
+
+        let body: serde_json::Value = serde_json::from_str(&requests.lock().unwrap()[0]).unwrap();
+        assert_eq!(query.to_param(), body["params"]["query"].as_str().unwrap());
+    }
+
+    #[tokio::test]
+    async fn perform_wrapped_returns_envelope() {
+        let (url, requests) =
+            serve_json(|request| respond_to(request, &fixture("health")).to_string()).await;
+
+        let client = HttpClient::new(url).unwrap();
+        let wrapper = client
+            .perform_wrapped(crate::endpoint::health::Request)
+            .await
+            .unwrap();
+        let request: serde_json::Value =
+            serde_json::from_str(&requests.lock().unwrap()[0]).unwrap();
+        assert_eq!(request["id"].as_str().unwrap(), wrapper.id().to_string());
+        wrapper.version().ensure_supported().unwrap();
+        assert!(wrapper.error().is_none());
+        wrapper.into_result().unwrap();
+    }
+
+    #[tokio::test]
+    async fn strict_response_ids_reject_mismatched_responses() {
+        use crate::error::ErrorKind;
+
+        // Responds to the first request with its ID, and to the rest with a
+        // different ID, as a misbehaving caching proxy might.
+        let mut responses = 0;
+        let (url, _) = serve_json(move |request| {
+            responses += 1;
+            let mut response = respond_to(request, &fixture("health"));
+            if responses > 1 {
+                response["id"] = "some-other-request".into();
+            }
+            response.to_string()
+        })
+        .await;
+
+        let client = HttpClient::builder(url.clone())
+            .strict_response_ids(true)
+            .build()
+            .unwrap();
+        client.health().await.unwrap();
+        let err = client.health().await.unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("response ID \"some-other-request\" from"),
+            "unexpected error: {}",
+            err
+        );
+        assert!(err.to_string().contains("does not match request ID"));
+        assert_eq!(ErrorKind::Server, err.kind());
+
+        // Mismatched IDs are accepted by default.
+        HttpClient::new(url).unwrap().health().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn strict_responses_reject_ambiguous_responses() {
+        // Responds with both a result and an error, as misbehaving
+        // middleware might.
+        let response = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": "",
+            "result": {},
+            "error": { "code": -32603, "message": "Internal error" },
+        })
+        .to_string();
+        let (url, _) = serve_json(move |request| respond_to(request, &response).to_string()).await;
+
+        let err = HttpClient::builder(url.clone())
+            .strict_responses(true)
+            .build()
+            .unwrap()
+            .health()
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("both 'result' and 'error'"),
+            "unexpected error: {}",
+            err
+        );
+
+        // By default, the error is returned.
+        let err = HttpClient::new(url).unwrap().health().await.unwrap_err();
+        assert!(
+            err.to_string().contains("Internal error"),
+            "unexpected error: {}",
+            err
+        );
+    }
+
+    // Adds the given header to requests.
+    #[derive(Debug)]
+    struct AddHeader(&'static str, &'static str);
+
+    impl RequestInterceptor for AddHeader {
+        fn before_send(&self, request: &mut OutgoingRequest) -> Result<()> {
+            request
+                .headers
+                .insert(self.0, http::HeaderValue::from_static(self.1));
+            Ok(())
+        }
+    }
+
+    // Records the requests and responses it sees in a log shared with other
+    // interceptors.
+    #[derive(Debug)]
+    struct Record(&'static str, Arc<std::sync::Mutex<Vec<String>>>);
+
+    impl RequestInterceptor for Record {
+        fn before_send(&self, request: &mut OutgoingRequest) -> Result<()> {
+            let api_key = request
+                .headers
+                .get("x-api-key")
+                .map(|v| v.to_str().unwrap().to_string());
+            self.1.lock().unwrap().push(format!(
+                "{}: {} x-api-key={:?}",
+                self.0, request.method, api_key
+            ));
+            Ok(())
+        }
+
+        fn after_receive(&self, response: &mut IncomingResponse) -> Result<()> {
+            self.1.lock().unwrap().push(format!(
+                "{}: {} {}",
+                self.0,
+                response.method,
+                response.status.as_u16()
+            ));
+            Ok(())
+        }
+    }
+
+    #[derive(Debug)]
+    struct Reject;
+
+    impl RequestInterceptor for Reject {
+        fn after_receive(&self, _response: &mut IncomingResponse) -> Result<()> {
+            Err(Error::client_internal_error("rejected".to_string()))
+        }
+    }
+
+    #[tokio::test]
+    async fn interceptors_modify_requests_and_see_responses() {
+        // Responds successfully only if the header added by the interceptor
+        // reached it.
+        let url = serve(|head, _| {
+            let authorized =
+                head.lines()
+                    .filter_map(|line| line.split_once(':'))
+                    .any(|(name, value)| {
+                        name.eq_ignore_ascii_case("x-api-key") && value.trim() == "secret"
+                    });
+            if authorized {
+                json_response("200 OK", &fixture("health"))
+            } else {
+                json_response("401 Unauthorized", "")
+            }
+        })
+        .await;
+
+        let log = Arc::default();
+        let client = HttpClient::builder(url.clone())
+            .interceptor(Record("first", Arc::clone(&log)))
+            .interceptor(AddHeader("x-api-key", "secret"))
+            .interceptor(Record("last", Arc::clone(&log)))
+            .build()
+            .unwrap();
+        client.health().await.unwrap();
+        // Requests pass through the chain in order, and responses in reverse.
+        assert_eq!(
+            vec![
+                "first: health x-api-key=None",
+                "last: health x-api-key=Some(\"secret\")",
+                "last: health 200",
+                "first: health 200",
+            ],
+            *log.lock().unwrap()
+        );
+
+        // Without the interceptor, the header never reaches the server.
+        HttpClient::new(url.clone())
+            .unwrap()
+            .health()
+            .await
+            .unwrap_err();
+
+        // Interceptors can fail requests.
+        let client = HttpClient::builder(url)
+            .interceptor(AddHeader("x-api-key", "secret"))
+            .interceptor(Reject)
+            .build()
+            .unwrap();
+        let err = client.health().await.unwrap_err();
+        assert!(
+            err.to_string().contains("rejected"),
+            "unexpected error: {}",
+            err
+        );
+    }
+
+    #[tokio::test]
+    async fn requests_are_compact_unless_pretty_printed() {
+        let (url, requests) = serve_json(|_| fixture("health")).await;
+
+        let builder = HttpClient::builder(url.clone());
+        assert!(!builder.config.pretty_requests);
+        builder.build().unwrap().health().await.unwrap();
+        HttpClient::builder(url)
+            .pretty_print_requests(true)
+            .build()
+            .unwrap()
+            .health()
+            .await
+            .unwrap();
+
+        let bodies = requests.lock().unwrap();
+        let (compact, pretty) = (&bodies[0], &bodies[1]);
+        assert!(!compact.contains('\n'));
+        assert!(pretty.contains('\n'));
+        assert!(compact.len() < pretty.len());
+
+        // Apart from their (random) IDs, the requests are identical.
+        let parse = |body: &str| {
+            let mut request: serde_json::Value = serde_json::from_str(body).unwrap();
+            request["id"].take();
+            request
+        };
+        assert_eq!(parse(compact), parse(pretty));
+    }
+
+    // Serves `/health` requests over both HTTP/1.1 and HTTP/2 (with prior
+    // knowledge), reporting the HTTP version of each request received.
+    async fn h2_capable_server() -> (HttpClientUrl, ChannelRx<hyper::Version>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap())
+            .parse()
+            .unwrap();
+        let (versions_tx, versions_rx) = unbounded();
+        tokio::spawn(async move {
+            loop {
+                let (stream, _) = listener.accept().await.unwrap();
+                let versions_tx = versions_tx.clone();
+                let service = hyper::service::service_fn(move |request: hyper::Request<_>| {
+                    versions_tx.send(request.version()).unwrap();
+                    async {
+                        Ok::<_, hyper::Error>(hyper::Response::new(hyper::Body::from(
+                            r#"{"jsonrpc":"2.0","id":"","result":{}}"#,
+                        )))
+                    }
+                });
+                tokio::spawn(hyper::server::conn::Http::new().serve_connection(stream, service));
+            }
+        });
+        (url, versions_rx)
+    }
+
+    #[tokio::test]
+    async fn http2_prior_knowledge() {
+        let (url, mut versions) = h2_capable_server().await;
+
+        let client = HttpClient::builder(url.clone()).build().unwrap();
+        client.health().await.unwrap();
+        assert_eq!(Some(hyper::Version::HTTP_11), versions.recv().await);
+
+        let client = HttpClient::builder(url)
+            .http2_prior_knowledge(true)
+            .build()
+            .unwrap();
+        client.health().await.unwrap();
+        client.health().await.unwrap();
+        assert_eq!(Some(hyper::Version::HTTP_2), versions.recv().await);
+        assert_eq!(Some(hyper::Version::HTTP_2), versions.recv().await);
+    }
+
+    #[tokio::test]
+    async fn tcp_options_are_applied() {
+        let builder = HttpClient::builder("http://127.0.0.1:26657".parse().unwrap());
+        assert!(!builder.config.tcp_nodelay);
+        assert_eq!(None, builder.config.tcp_keepalive);
+
+        let builder = builder
+            .tcp_nodelay(true)
+            .tcp_keepalive(Duration::from_secs(60));
+        assert!(builder.config.tcp_nodelay);
+        assert_eq!(Some(Duration::from_secs(60)), builder.config.tcp_keepalive);
+
+        // The options must not prevent requests from being made.
+        let url = redirecting_server(|_| "/rpc".to_string()).await;
+        let client = HttpClient::builder(url)
+            .tcp_nodelay(true)
+            .tcp_keepalive(Duration::from_secs(60))
+            .max_redirects(1)
+            .build()
+            .unwrap();
+        client.health().await.unwrap();
+    }
+
+    // Serves `/health` requests, keeping connections alive, and counting the
+    // connections accepted.
+    async fn keep_alive_server() -> (HttpClientUrl, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap())
+            .parse()
+            .unwrap();
+        let connections = Arc::new(AtomicUsize::new(0));
+        let accepted = connections.clone();
+        tokio::spawn(async move {
+            loop {
+                let (stream, _) = listener.accept().await.unwrap();
+                accepted.fetch_add(1, Ordering::SeqCst);
+                let service = hyper::service::service_fn(|_: hyper::Request<_>| async {
+                    Ok::<_, hyper::Error>(hyper::Response::new(hyper::Body::from(
+                        r#"{"jsonrpc":"2.0","id":"","result":{}}"#,
+                    )))
+                });
+                tokio::spawn(hyper::server::conn::Http::new().serve_connection(stream, service));
+            }
+        });
+        (url, connections)
+    }
+
+    #[tokio::test]
+    async fn idle_connections_are_not_reused_after_timeout() {
+        let (url, connections) = keep_alive_server().await;
+        let accepted = || connections.load(Ordering::SeqCst);
+
+        // By default, the connection is reused.
+        let client = HttpClient::builder(url.clone()).build().unwrap();
+        client.health().await.unwrap();
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        client.health().await.unwrap();
+        assert_eq!(1, accepted());
+
+        let client = HttpClient::builder(url.clone())
+            .pool_idle_timeout(Duration::from_millis(100))
+            .build()
+            .unwrap();
+        client.health().await.unwrap();
+        client.health().await.unwrap();
+        assert_eq!(2, accepted());
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        client.health().await.unwrap();
+        assert_eq!(3, accepted());
+
+        // Without idle connections, every request needs a new connection.
+        let client = HttpClient::builder(url).pool_max_idle(0).build().unwrap();
+        client.health().await.unwrap();
+        client.health().await.unwrap();
+        assert_eq!(5, accepted());
+    }
+
+    #[tokio::test]
+    async fn connection_failures_expose_their_source() {
+        // Nothing listens on this port once the listener is dropped.
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        drop(listener);
+
+        let client = HttpClient::new(url.as_str()).unwrap();
+        let err = client.health().await.unwrap_err();
+        assert_eq!(crate::error::Code::HttpError, err.code());
+        let source = std::error::Error::source(&err).unwrap();
+        assert!(source.downcast_ref::<hyper::Error>().unwrap().is_connect());
+    }
+
+    #[tokio::test]
+    async fn unavailable_endpoints_are_retryable() {
+        use crate::error::ErrorKind;
+
+        let mut statuses = vec!["503 Service Unavailable", "504 Gateway Timeout"].into_iter();
+        let url = serve(move |_, _| json_response(statuses.next().unwrap(), "\"busy\"")).await;
+
+        let client = HttpClient::new(url).unwrap();
+        let err = client.health().await.unwrap_err();
+        assert!(err.to_string().contains("503"), "unexpected error: {}", err);
+        assert_eq!(ErrorKind::Unavailable, err.kind());
+        let err = client.health().await.unwrap_err();
+        assert_eq!(ErrorKind::Timeout, err.kind());
+        assert!(err.is_retryable());
+
+        // Redirects which are not followed are not worth retrying.
+        let url = redirecting_server(|_| "/rpc".to_string()).await;
+        let err = HttpClient::new(url).unwrap().health().await.unwrap_err();
+        assert_eq!(ErrorKind::Client, err.kind());
+        assert!(!err.is_retryable());
+    }
+
+    #[tokio::test]
+    async fn redirects_are_not_followed_by_default() {
+        let url = redirecting_server(|_| "/rpc".to_string()).await;
+        let client = HttpClient::builder(url).build().unwrap();
+        let err = client.health().await.unwrap_err();
+        assert!(err.to_string().contains("307"), "unexpected error: {}", err);
+    }
+
+    #[tokio::test]
+    async fn redirects_preserve_request_body() {
+        let url = redirecting_server(|_| "/rpc".to_string()).await;
+        let client = HttpClient::builder(url).max_redirects(1).build().unwrap();
+        client.health().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn redirect_limit_is_enforced() {
+        // Redirects back to itself indefinitely.
+        let url = redirecting_server(|_| "/".to_string()).await;
+        let client = HttpClient::builder(url).max_redirects(3).build().unwrap();
+        let err = client.health().await.unwrap_err();
+        assert!(
+            err.to_string().contains("after following 3 of at most 3"),
+            "unexpected error: {}",
+            err
+        );
+    }
+
+    #[tokio::test]
+    async fn cross_host_redirects_are_refused() {
+        let url = redirecting_server(|port| format!("http://localhost:{}/rpc", port)).await;
+        let client = HttpClient::builder(url).max_redirects(1).build().unwrap();
+        let err = client.health().await.unwrap_err();
+        assert!(
+            err.to_string().contains("different host"),
+            "unexpected error: {}",
+            err
+        );
+    }
+
+    fn assert_shareable<T: Clone + Send + Sync + 'static>() {}
+
+    #[test]
+    fn http_client_is_shareable() {
+        assert_shareable::<HttpClient>();
+    }
+
+    // Client futures can only be spawned onto other threads if they are
+    // `Send`.
+    #[cfg(not(feature = "unsend"))]
+    #[tokio::test]
+    async fn cloned_client_can_be_used_from_many_tasks() {
+        // Nothing listens on this port, so requests fail fast.
+        let client = HttpClient::new("http://127.0.0.1:1").unwrap();
+        let handles = (0..4)
+            .map(|_| {
+                let client = client.clone();
+                tokio::spawn(async move { client.health().await })
+            })
+            .collect::<Vec<_>>();
+        for handle in handles {
+            assert!(handle.await.unwrap().is_err());
+        }
+    }
+
+    #[tokio::test]
+    async fn chunked_responses_are_read_in_full() {
+        use crate::error::ErrorKind;
+
+        // Responds to each request with the `block_results` fixture, split
+        // into many small chunks.
+        let url = serve(|_, _| {
+            let mut response = b"HTTP/1.1 200 OK\r\nContent-Type: application/json\r\n\
+                                 Transfer-Encoding: chunked\r\nConnection: close\r\n\r\n"
+                .to_vec();
+            for chunk in fixture("block_results").as_bytes().chunks(100) {
+                response.extend_from_slice(format!("{:x}\r\n", chunk.len()).as_bytes());
+                response.extend_from_slice(chunk);
+                response.extend_from_slice(b"\r\n");
+            }
+            response.extend_from_slice(b"0\r\n\r\n");
+            response
+        })
+        .await;
+
+        let client = HttpClient::builder(url.clone())
+            .max_response_size(1 << 20)
+            .build()
+            .unwrap();
+        let response = client.block_results(10_u32).await.unwrap();
+        assert!(response.txs_results.unwrap().len() > 1);
+
+        // The limit applies across chunks.
+        let client = HttpClient::builder(url)
+            .max_response_size(1000)
+            .build()
+            .unwrap();
+        let err = client.block_results(10_u32).await.unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("exceeds the maximum size of 1000 bytes"),
+            "unexpected error: {}",
+            err
+        );
+        assert_eq!(ErrorKind::Client, err.kind());
     }
 
-    async fn response_to_string(response: hyper::Response<hyper::Body>) -> Result<String> {
-        let mut response_body = String::new();
-        hyper::body::aggregate(response.into_body())
-            .await?
-            .reader()
-            .read_to_string(&mut response_body)
-            .map_err(|_| Error::client_internal_error("failed to read response body to string"))?;
-        Ok(response_body)
+    #[tokio::test]
+    async fn connect_timeout_elapses() {
+        let (listener, _backlog) = hanging_listener().await;
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let timeout = Duration::from_millis(100);
+        let client = HttpClient::builder(url.parse().unwrap())
+            .connect_timeout(timeout)
+            .build()
+            .unwrap();
+        let start = Instant::now();
+        let err = client.health().await.unwrap_err();
+        assert!(start.elapsed() >= timeout);
+        assert_eq!(ErrorKind::Timeout, err.kind(), "unexpected error: {}", err);
     }
 }
@@ -0,0 +1,60 @@
+//! URLs for use with HTTP-based transports.
+
+use crate::{Error, Result, Scheme, Url};
+use std::convert::{TryFrom, TryInto};
+use std::str::FromStr;
+use tendermint::net;
+
+/// A URL limited to use with HTTP clients.
+///
+/// Facilitates useful type conversions and inferences.
+#[derive(Debug, Clone)]
+pub struct HttpClientUrl(pub(crate) Url);
+
+impl TryFrom<Url> for HttpClientUrl {
+    type Error = Error;
+
+    fn try_from(value: Url) -> Result<Self> {
+        match value.scheme() {
+            Scheme::Http | Scheme::Https => Ok(Self(value)),
+            _ => Err(Error::invalid_params(&format!(
+                "cannot use URL {} with HTTP clients",
+                value
+            ))),
+        }
+    }
+}
+
+impl FromStr for HttpClientUrl {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let url: Url = s.parse()?;
+        url.try_into()
+    }
+}
+
+impl TryFrom<&str> for HttpClientUrl {
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self> {
+        value.parse()
+    }
+}
+
+impl TryFrom<net::Address> for HttpClientUrl {
+    type Error = Error;
+
+    fn try_from(value: net::Address) -> Result<Self> {
+        match value {
+            net::Address::Tcp {
+                peer_id: _,
+                host,
+                port,
+            } => format!("http://{}:{}", host, port).parse(),
+            net::Address::Unix { .. } => Err(Error::invalid_params(
+                "only TCP-based node addresses are supported",
+            )),
+        }
+    }
+}
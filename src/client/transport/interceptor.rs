@@ -0,0 +1,105 @@
+//! Hooks for inspecting and modifying the requests and responses of
+//! HTTP-based transports.
+
+use crate::{Method, Result};
+use http::{HeaderMap, StatusCode};
+use std::fmt;
+use std::sync::Arc;
+
+/// A hook invoked by a transport before it sends each request and after it
+/// receives each response, e.g. to sign requests, to attach credentials, or
+/// to log requests and responses for auditing.
+///
+/// Interceptors are added to a client in order (see
+/// [`crate::HttpClientBuilder::interceptor`]), and form a chain: requests
+/// pass through them in the order in which they were added, and responses
+/// in the reverse order. If an interceptor fails, the request fails with its
+/// error, without invoking the rest of the chain.
+///
+/// Both hooks do nothing by default.
+///
+/// ## Examples
+///
+/// ```rust,ignore
+/// use tendermint_rpc::{HttpClient, OutgoingRequest, RequestInterceptor, Result};
+///
+/// #[derive(Debug)]
+/// struct BearerAuth(String);
+///
+/// impl RequestInterceptor for BearerAuth {
+///     fn before_send(&self, request: &mut OutgoingRequest) -> Result<()> {
+///         let value = format!("Bearer {}", self.0).parse().unwrap();
+///         request.headers.insert("authorization", value);
+///         Ok(())
+///     }
+/// }
+///
+/// let client = HttpClient::builder("https://rpc.example.com".parse().unwrap())
+///     .interceptor(BearerAuth("token".to_string()))
+///     .build()
+///     .unwrap();
+/// ```
+pub trait RequestInterceptor: fmt::Debug + Send + Sync {
+    /// Inspect or modify a request before it is sent.
+    fn before_send(&self, _request: &mut OutgoingRequest) -> Result<()> {
+        Ok(())
+    }
+
+    /// Inspect or modify a response once it has been received, before it is
+    /// checked and parsed.
+    fn after_receive(&self, _response: &mut IncomingResponse) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// A request about to be sent by a transport (see [`RequestInterceptor`]).
+#[derive(Debug, Clone)]
+pub struct OutgoingRequest {
+    /// The method being requested
+    pub method: Method,
+
+    /// The HTTP headers with which the request will be sent
+    pub headers: HeaderMap,
+
+    /// The serialized JSON-RPC request
+    pub body: String,
+}
+
+/// A response received by a transport (see [`RequestInterceptor`]).
+#[derive(Debug, Clone)]
+pub struct IncomingResponse {
+    /// The method which was requested
+    pub method: Method,
+
+    /// The HTTP status of the response
+    pub status: StatusCode,
+
+    /// The HTTP headers of the response
+    pub headers: HeaderMap,
+
+    /// The body of the response, which is parsed as a JSON-RPC response
+    pub body: Vec<u8>,
+}
+
+/// A chain of interceptors, invoked in turn.
+#[derive(Debug, Clone, Default)]
+pub struct Interceptors(Vec<Arc<dyn RequestInterceptor>>);
+
+impl Interceptors {
+    pub fn push(&mut self, interceptor: Arc<dyn RequestInterceptor>) {
+        self.0.push(interceptor);
+    }
+
+    pub fn before_send(&self, request: &mut OutgoingRequest) -> Result<()> {
+        self.0
+            .iter()
+            .try_for_each(|interceptor| interceptor.before_send(request))
+    }
+
+    pub fn after_receive(&self, response: &mut IncomingResponse) -> Result<()> {
+        self.0
+            .iter()
+            .rev()
+            .try_for_each(|interceptor| interceptor.after_receive(response))
+    }
+}
@@ -50,10 +50,16 @@ use std::collections::HashMap;
 pub struct MockClient<M: MockRequestMatcher> {
     matcher: M,
     driver_tx: ChannelTx<DriverCommand>,
+    max_query_conditions: Option<usize>,
 }
 
-#[async_trait]
+#[cfg_attr(not(feature = "unsend"), async_trait)]
+#[cfg_attr(feature = "unsend", async_trait(?Send))]
 impl<M: MockRequestMatcher> Client for MockClient<M> {
+    fn max_query_conditions(&self) -> Option<usize> {
+        self.max_query_conditions
+    }
+
     async fn perform<R>(&self, request: R) -> Result<R::Response>
     where
         R: Request,
@@ -69,11 +75,23 @@ impl<M: MockRequestMatcher> MockClient<M> {
     pub fn new(matcher: M) -> (Self, MockClientDriver) {
         let (driver_tx, driver_rx) = unbounded();
         (
-            Self { matcher, driver_tx },
+            Self {
+                matcher,
+                driver_tx,
+                max_query_conditions: None,
+            },
             MockClientDriver::new(driver_rx),
         )
     }
 
+    /// Reject queries with more than the given number of conditions, as
+    /// would a client configured with the same limit (see
+    /// [`Client::max_query_conditions`]).
+    pub fn with_max_query_conditions(mut self, max: usize) -> Self {
+        self.max_query_conditions = Some(max);
+        self
+    }
+
     /// Publishes the given event to all subscribers whose query exactly
     /// matches that of the event.
     pub fn publish(&self, ev: &Event) {
@@ -82,15 +100,23 @@ impl<M: MockRequestMatcher> MockClient<M> {
             .unwrap();
     }
 
+    /// The matcher used to respond to requests.
+    #[cfg(test)]
+    pub(crate) fn matcher(&self) -> &M {
+        &self.matcher
+    }
+
     /// Signal to the mock client's driver to terminate.
     pub fn close(self) {
         self.driver_tx.send(DriverCommand::Terminate).unwrap();
     }
 }
 
-#[async_trait]
+#[cfg_attr(not(feature = "unsend"), async_trait)]
+#[cfg_attr(feature = "unsend", async_trait(?Send))]
 impl<M: MockRequestMatcher> SubscriptionClient for MockClient<M> {
     async fn subscribe(&self, query: Query) -> Result<Subscription> {
+        query.validate_for_server_with_max(self.max_query_conditions)?;
         let id = uuid_str();
         let (subs_tx, subs_rx) = unbounded();
         let (result_tx, mut result_rx) = unbounded();
@@ -105,6 +131,7 @@ impl<M: MockRequestMatcher> SubscriptionClient for MockClient<M> {
     }
 
     async fn unsubscribe(&self, query: Query) -> Result<()> {
+        query.validate_for_server_with_max(self.max_query_conditions)?;
         let (result_tx, mut result_rx) = unbounded();
         self.driver_tx
             .send(DriverCommand::Unsubscribe { query, result_tx })?;
@@ -278,6 +305,23 @@ mod test {
         driver_hdl.await.unwrap().unwrap();
     }
 
+    #[tokio::test]
+    async fn local_only_queries_are_not_sent() {
+        let (client, driver) = MockClient::new(MockRequestMethodMatcher::default());
+        let driver_hdl = tokio::spawn(async move { driver.run().await });
+
+        let query = Query::from(EventType::Tx).and_not_eq("transfer.sender", "AddrA");
+        assert!(client.subscribe(query.clone()).await.is_err());
+        assert!(client.unsubscribe(query.clone()).await.is_err());
+        assert!(client
+            .tx_search(query, false, 1, 10, crate::Order::Ascending)
+            .await
+            .is_err());
+
+        client.close();
+        driver_hdl.await.unwrap().unwrap();
+    }
+
     #[tokio::test]
     async fn mock_subscription_client() {
         let (client, driver) = MockClient::new(MockRequestMethodMatcher::default());
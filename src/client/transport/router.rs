@@ -2,6 +2,7 @@
 
 use crate::client::subscription::SubscriptionTx;
 use crate::event::Event;
+use crate::Result;
 use std::borrow::BorrowMut;
 use std::collections::{HashMap, HashSet};
 use tracing::debug;
@@ -25,7 +26,14 @@ impl SubscriptionRouter {
     /// associated with the event, and only queries that exactly match that of
     /// the event's.
     pub fn publish(&mut self, ev: &Event) -> PublishResult {
-        let subs_for_query = match self.subscriptions.get_mut(&ev.query) {
+        self.publish_to_query(&ev.query, || Ok(ev.clone()))
+    }
+
+    fn publish_to_query<F>(&mut self, query: &str, item: F) -> PublishResult
+    where
+        F: Fn() -> Result<Event>,
+    {
+        let subs_for_query = match self.subscriptions.get_mut(query) {
             Some(s) => s,
             None => return PublishResult::NoSubscribers,
         };
@@ -34,11 +42,11 @@ impl SubscriptionRouter {
         // us to safely stop tracking the subscription.
         let mut disconnected = HashSet::new();
         for (id, event_tx) in subs_for_query.borrow_mut() {
-            if let Err(e) = event_tx.send(Ok(ev.clone())) {
+            if let Err(e) = event_tx.send(item()) {
                 disconnected.insert(id.clone());
                 debug!(
                     "Automatically disconnecting subscription with ID {} for query \"{}\" due to failure to publish to it: {}",
-                    id, ev.query, e
+                    id, query, e
                 );
             }
         }
@@ -77,6 +85,35 @@ impl SubscriptionRouter {
 
 #[cfg(feature = "websocket-client")]
 impl SubscriptionRouter {
+    /// Publishes the given error to all of the subscriptions to the given
+    /// query, e.g. when an event relating to that query could not be parsed.
+    pub fn publish_error(&mut self, query: &str, err: &crate::Error) -> PublishResult {
+        self.publish_to_query(query, || Err(err.clone()))
+    }
+
+    /// Publishes the given error to all subscriptions, e.g. when the
+    /// connection to the remote endpoint has been closed.
+    pub fn publish_error_to_all(&mut self, err: &crate::Error) {
+        let queries: Vec<String> = self.subscriptions.keys().cloned().collect();
+        for query in queries {
+            self.publish_error(&query, err);
+        }
+    }
+
+    /// Removes the subscription with the given ID for the given query,
+    /// returning the number of subscriptions remaining for the query, or
+    /// `None` if the subscription is not being tracked (e.g. because all the
+    /// subscriptions for the query were already removed).
+    pub fn remove(&mut self, id: &str, query: &str) -> Option<usize> {
+        let subs_for_query = self.subscriptions.get_mut(query)?;
+        subs_for_query.remove(id)?;
+        let remaining = subs_for_query.len();
+        if remaining == 0 {
+            self.subscriptions.remove(query);
+        }
+        Some(remaining)
+    }
+
     /// Returns the number of active subscriptions for the given query.
     pub fn num_subscriptions_for_query(&self, query: impl ToString) -> usize {
         self.subscriptions
@@ -84,6 +121,15 @@ impl SubscriptionRouter {
             .map(|subs_for_query| subs_for_query.len())
             .unwrap_or(0)
     }
+
+    /// Returns the number of distinct queries with at least one active
+    /// subscription.
+    pub fn num_queries(&self) -> usize {
+        self.subscriptions
+            .values()
+            .filter(|subs_for_query| !subs_for_query.is_empty())
+            .count()
+    }
 }
 
 impl Default for SubscriptionRouter {
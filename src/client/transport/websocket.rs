@@ -4,6 +4,7 @@ use crate::client::subscription::SubscriptionTx;
 use crate::client::sync::{ChannelRx, ChannelTx};
 use crate::client::transport::router::{PublishResult, SubscriptionRouter};
 use crate::endpoint::{subscribe, unsubscribe};
+use crate::error::ErrorKind;
 use crate::event::Event;
 use crate::query::Query;
 use crate::request::Wrapper;
@@ -15,6 +16,7 @@ use async_trait::async_trait;
 use async_tungstenite::tokio::ConnectStream;
 use async_tungstenite::tungstenite::protocol::frame::coding::CloseCode;
 use async_tungstenite::tungstenite::protocol::CloseFrame;
+use async_tungstenite::tungstenite::Error as WSError;
 use async_tungstenite::tungstenite::Message;
 use async_tungstenite::WebSocketStream;
 use futures::{SinkExt, StreamExt};
@@ -25,6 +27,8 @@ use std::convert::{TryFrom, TryInto};
 use std::ops::Add;
 use std::str::FromStr;
 use tendermint::net;
+use tokio::runtime::Handle;
+use tokio::task::JoinHandle;
 use tokio::time::{Duration, Instant};
 use tracing::{debug, error};
 
@@ -41,6 +45,25 @@ const RECV_TIMEOUT: Duration = Duration::from_secs(RECV_TIMEOUT_SECONDS);
 // Taken from https://github.com/tendermint/tendermint/blob/309e29c245a01825fc9630103311fd04de99fa5e/rpc/jsonrpc/server/ws_handler.go#L28
 const PING_INTERVAL: Duration = Duration::from_secs((RECV_TIMEOUT_SECONDS * 9) / 10);
 
+// The default maximum number of distinct queries to which a single connection
+// can be subscribed at once.
+//
+// Matches Tendermint's default `max_subscriptions_per_client`.
+const DEFAULT_MAX_SUBSCRIPTIONS: usize = 5;
+
+// The default maximum size of an incoming WebSocket message or frame, which
+// bounds the memory the driver allocates for a single incoming message.
+//
+// These match `tungstenite`'s defaults.
+const DEFAULT_MAX_MESSAGE_SIZE: usize = 64 << 20;
+const DEFAULT_MAX_FRAME_SIZE: usize = 16 << 20;
+
+// The number of consecutive incoming messages which we may fail to decode
+// (e.g. text messages which are not valid UTF-8) before giving up on the
+// connection. Such messages are otherwise skipped, so that a single corrupt
+// message doesn't terminate all subscriptions.
+const MAX_CONSECUTIVE_DECODE_ERRORS: usize = 3;
+
 /// Tendermint RPC client that provides access to all RPC functionality
 /// (including [`Event`] subscription) over a WebSocket connection.
 ///
@@ -48,17 +71,45 @@ const PING_INTERVAL: Duration = Duration::from_secs((RECV_TIMEOUT_SECONDS * 9) /
 /// The driver is the component of the client that actually interacts with the
 /// remote RPC over the WebSocket connection. The `WebSocketClient` can
 /// therefore be cloned into different asynchronous contexts, effectively
-/// allowing for asynchronous access to the driver.
+/// allowing for asynchronous access to the driver. Both the client and its
+/// driver are `Send`, and the client is also `Sync`.
 ///
 /// It is the caller's responsibility to spawn an asynchronous task in which to
 /// execute the [`WebSocketClientDriver::run`] method. See the example below.
 ///
+/// ### Runtimes
+///
+/// The connection is registered with the Tokio runtime on which it is
+/// established, so the driver must be run on that same runtime, which must
+/// outlive the client. The client itself can be used from any runtime (or
+/// none at all). Applications running the client's I/O on a dedicated runtime
+/// can use [`WebSocketClientBuilder::build_on`] to establish the connection
+/// and spawn the driver on that runtime, or
+/// [`WebSocketClientDriver::spawn_on`] to spawn a driver whose connection was
+/// established on it.
+///
 /// Dropping [`Subscription`]s will automatically terminate them (the
 /// `WebSocketClientDriver` detects a disconnected channel and removes the
 /// subscription from its internal routing table). When all subscriptions to a
 /// particular query have disconnected, the driver will automatically issue an
 /// unsubscribe request to the remote RPC endpoint.
 ///
+/// Multiple [`Subscription`]s to the same query share a single subscription on
+/// the remote endpoint, even if they are initiated concurrently. Events are
+/// fanned out to all of them locally.
+///
+/// Tendermint limits the number of distinct queries to which each connection
+/// can be subscribed. Subscribing to a new query beyond this limit fails
+/// without contacting the remote endpoint. The limit defaults to 5 (matching
+/// Tendermint's default), and can be changed via
+/// [`WebSocketClientBuilder::max_subscriptions`] to match the node's
+/// `max_subscriptions_per_client` setting.
+///
+/// If an event received for a subscription cannot be parsed, the subscription
+/// yields an error in its place. By default the subscription then carries on
+/// with subsequent events, but it can instead be terminated (see
+/// [`WebSocketClientBuilder::on_event_parse_error`]).
+///
 /// ### Timeouts
 ///
 /// The WebSocket client connection times out after 30 seconds if it does not
@@ -67,6 +118,18 @@ const PING_INTERVAL: Duration = Duration::from_secs((RECV_TIMEOUT_SECONDS * 9) /
 ///
 /// This is not configurable at present.
 ///
+/// The time spent establishing the connection (including the TLS and
+/// WebSocket handshakes) can be limited separately via
+/// [`WebSocketClientBuilder::connect_timeout`].
+///
+/// ### Message Size Limits
+///
+/// Incoming messages larger than 64 MiB, or frames larger than 16 MiB, are
+/// rejected without being read into memory in full. The connection is then
+/// closed and the driver terminates with an error. These limits can be
+/// changed via [`WebSocketClientBuilder::max_message_size`] and
+/// [`WebSocketClientBuilder::max_frame_size`].
+///
 /// ### Keep-Alive
 ///
 /// The WebSocket client implements a keep-alive mechanism whereby it sends a
@@ -123,6 +186,7 @@ const PING_INTERVAL: Duration = Duration::from_secs((RECV_TIMEOUT_SECONDS * 9) /
 #[derive(Debug, Clone)]
 pub struct WebSocketClient {
     inner: sealed::WebSocketClient,
+    max_query_conditions: Option<usize>,
 }
 
 impl WebSocketClient {
@@ -134,18 +198,245 @@ impl WebSocketClient {
     where
         U: TryInto<WebSocketClientUrl, Error = Error>,
     {
-        let url = url.try_into()?;
-        let (inner, driver) = if url.0.is_secure() {
-            sealed::WebSocketClient::new_secure(url.0).await?
+        Self::builder(url.try_into()?).build().await
+    }
+
+    /// Initiate a builder for a WebSocket-based client connecting to the
+    /// given Tendermint node's RPC endpoint, allowing for further
+    /// configuration of the client.
+    pub fn builder(url: WebSocketClientUrl) -> WebSocketClientBuilder {
+        WebSocketClientBuilder::new(url)
+    }
+}
+
+/// A builder for [`WebSocketClient`]s, allowing for configuration of the
+/// client beyond that which is available through [`WebSocketClient::new`].
+///
+/// ## Examples
+///
+/// ```rust,ignore
+/// use std::time::Duration;
+/// use tendermint_rpc::WebSocketClient;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let (client, driver) =
+///         WebSocketClient::builder("ws://127.0.0.1:26657/websocket".parse().unwrap())
+///             .connect_timeout(Duration::from_secs(5))
+///             .build()
+///             .await
+///             .unwrap();
+///     let driver_handle = tokio::spawn(async move { driver.run().await });
+///
+///     // ...
+///
+///     client.close().unwrap();
+///     let _ = driver_handle.await.unwrap();
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct WebSocketClientBuilder {
+    url: WebSocketClientUrl,
+    config: sealed::Config,
+}
+
+impl WebSocketClientBuilder {
+    /// Initiate a builder for a client connecting to the given URL.
+    pub fn new(url: WebSocketClientUrl) -> Self {
+        Self {
+            url,
+            config: sealed::Config::default(),
+        }
+    }
+
+    /// Limit the time spent establishing the connection to the remote
+    /// endpoint, including the TCP connection as well as the TLS (if
+    /// applicable) and WebSocket handshakes.
+    ///
+    /// This is independent of the receive timeout applied once connected. By
+    /// default, connection attempts are only limited by the operating system's
+    /// own TCP timeout, which can be several minutes for unreachable hosts.
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.config.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Whether to disable Nagle's algorithm (i.e. set `TCP_NODELAY`) on the
+    /// underlying TCP connection, trading bandwidth efficiency for lower
+    /// latency.
+    ///
+    /// Defaults to `false`.
+    pub fn tcp_nodelay(mut self, nodelay: bool) -> Self {
+        self.config.tcp_nodelay = nodelay;
+        self
+    }
+
+    /// Enable TCP keepalive on the underlying TCP connection, sending the
+    /// first keepalive probe after the connection has been idle for the given
+    /// duration.
+    ///
+    /// By default, TCP keepalive is disabled (the client nevertheless pings
+    /// the server periodically at the WebSocket level).
+    pub fn tcp_keepalive(mut self, idle: Duration) -> Self {
+        self.config.tcp_keepalive = Some(idle);
+        self
+    }
+
+    /// Encode requests in the form expected by nodes speaking the given
+    /// version of the RPC protocol (see [`crate::CompatMode`]).
+    ///
+    /// Defaults to [`crate::CompatMode::V0_34`].
+    pub fn compat_mode(mut self, compat: crate::CompatMode) -> Self {
+        self.config.compat = compat;
+        self
+    }
+
+    /// Send requests as pretty-printed rather than compact JSON, e.g. to make
+    /// them easier to inspect in logs or captured traffic. Outgoing requests
+    /// are logged at the `debug` level in either case.
+    ///
+    /// Defaults to `false`, as compact requests use less bandwidth.
+    pub fn pretty_print_requests(mut self, pretty: bool) -> Self {
+        self.config.pretty_requests = pretty;
+        self
+    }
+
+    /// Whether to reject responses containing both a `result` and an `error`
+    /// outright (see [`Response::from_string_strict`]), rather than treating
+    /// them as errors and only logging a warning.
+    ///
+    /// Defaults to `false`.
+    ///
+    /// [`Response::from_string_strict`]: crate::Response::from_string_strict
+    pub fn strict_responses(mut self, strict: bool) -> Self {
+        self.config.strict_responses = strict;
+        self
+    }
+
+    /// Set the maximum number of distinct queries to which the client may be
+    /// subscribed at once, which should match the remote node's
+    /// `max_subscriptions_per_client` setting.
+    ///
+    /// Defaults to 5, which is Tendermint's default.
+    pub fn max_subscriptions(mut self, limit: usize) -> Self {
+        self.config.max_subscriptions = limit;
+        self
+    }
+
+    /// Set the maximum size, in bytes, of an incoming message (which may
+    /// consist of several frames). Receiving a larger message terminates the
+    /// connection with an error.
+    ///
+    /// Defaults to 64 MiB.
+    pub fn max_message_size(mut self, bytes: usize) -> Self {
+        self.config.max_message_size = bytes;
+        self
+    }
+
+    /// Set the maximum size, in bytes, of a single incoming frame. Receiving
+    /// a larger frame terminates the connection with an error.
+    ///
+    /// Defaults to 16 MiB.
+    pub fn max_frame_size(mut self, bytes: usize) -> Self {
+        self.config.max_frame_size = bytes;
+        self
+    }
+
+    /// Reject queries (e.g. for subscriptions or `/tx_search`) with more than
+    /// the given number of conditions without sending them (see
+    /// [`Client::max_query_conditions`]).
+    ///
+    /// Defaults to no limit.
+    pub fn max_query_conditions(mut self, max: usize) -> Self {
+        self.config.max_query_conditions = Some(max);
+        self
+    }
+
+    /// Choose what happens to subscriptions when an event relating to them
+    /// cannot be parsed. In either case, the subscriptions yield an error in
+    /// place of the event.
+    ///
+    /// Defaults to [`EventParseErrorPolicy::Continue`].
+    pub fn on_event_parse_error(mut self, policy: EventParseErrorPolicy) -> Self {
+        self.config.event_parse_error_policy = policy;
+        self
+    }
+
+    /// Connect to the remote endpoint, returning the client and its driver.
+    ///
+    /// See [`WebSocketClient`] for details as to the driver's execution.
+    pub async fn build(self) -> Result<(WebSocketClient, WebSocketClientDriver)> {
+        let url = self.url.0;
+        let (inner, driver) = if url.is_secure() {
+            sealed::WebSocketClient::new_secure(url, &self.config).await?
         } else {
-            sealed::WebSocketClient::new_unsecure(url.0).await?
+            sealed::WebSocketClient::new_unsecure(url, &self.config).await?
+        };
+        let client = WebSocketClient {
+            inner,
+            max_query_conditions: self.config.max_query_conditions,
         };
-        Ok((Self { inner }, driver))
+        Ok((client, driver))
+    }
+
+    /// Connect to the remote endpoint on the given runtime, and spawn the
+    /// client's driver on that same runtime, returning the client and a
+    /// handle to the driver's task.
+    ///
+    /// This may be called from a different runtime (see
+    /// [`WebSocketClient`] for details as to runtimes).
+    pub async fn build_on(
+        self,
+        handle: &Handle,
+    ) -> Result<(WebSocketClient, JoinHandle<Result<()>>)> {
+        let (client, driver) = handle.spawn(self.build()).await.map_err(|e| {
+            Error::client_internal_error(format!("failed to connect on the given runtime: {}", e))
+        })??;
+        Ok((client, driver.spawn_on(handle)))
     }
 }
 
-#[async_trait]
+/// What to do with a subscription when an event for it cannot be parsed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventParseErrorPolicy {
+    /// Yield an error in place of the event, and keep delivering subsequent
+    /// events (the default).
+    Continue,
+
+    /// Yield an error in place of the event, and then terminate the
+    /// subscription, unsubscribing from the remote endpoint.
+    FailFast,
+}
+
+impl Default for EventParseErrorPolicy {
+    fn default() -> Self {
+        EventParseErrorPolicy::Continue
+    }
+}
+
+impl WebSocketClient {
+    /// Perform the given request, returning the full JSON-RPC response
+    /// envelope (including its `id` and `jsonrpc` version) rather than only
+    /// its result.
+    ///
+    /// Unlike [`Client::perform`], this does not fail if the node responds
+    /// with an error; see [`response::Wrapper::into_result`] for extracting
+    /// the result.
+    pub async fn perform_wrapped<R>(&self, request: R) -> Result<response::Wrapper<R::Response>>
+    where
+        R: SimpleRequest,
+    {
+        self.inner.perform_wrapped(request).await
+    }
+}
+
+#[cfg_attr(not(feature = "unsend"), async_trait)]
+#[cfg_attr(feature = "unsend", async_trait(?Send))]
 impl Client for WebSocketClient {
+    fn max_query_conditions(&self) -> Option<usize> {
+        self.max_query_conditions
+    }
+
     async fn perform<R>(&self, request: R) -> Result<<R as Request>::Response>
     where
         R: SimpleRequest,
@@ -154,13 +445,16 @@ impl Client for WebSocketClient {
     }
 }
 
-#[async_trait]
+#[cfg_attr(not(feature = "unsend"), async_trait)]
+#[cfg_attr(feature = "unsend", async_trait(?Send))]
 impl SubscriptionClient for WebSocketClient {
     async fn subscribe(&self, query: Query) -> Result<Subscription> {
+        query.validate_for_server_with_max(self.max_query_conditions)?;
         self.inner.subscribe(query).await
     }
 
     async fn unsubscribe(&self, query: Query) -> Result<()> {
+        query.validate_for_server_with_max(self.max_query_conditions)?;
         self.inner.unsubscribe(query).await
     }
 
@@ -225,17 +519,110 @@ impl TryFrom<net::Address> for WebSocketClientUrl {
 
 mod sealed {
     use super::{
-        DriverCommand, SimpleRequestCommand, SubscribeCommand, UnsubscribeCommand,
-        WebSocketClientDriver,
+        DriverCommand, EventParseErrorPolicy, SimpleRequestCommand, SubscribeCommand,
+        UnsubscribeCommand, WebSocketClientDriver, DEFAULT_MAX_FRAME_SIZE,
+        DEFAULT_MAX_MESSAGE_SIZE, DEFAULT_MAX_SUBSCRIPTIONS,
     };
     use crate::client::sync::{unbounded, ChannelTx};
+    use crate::error::ErrorKind;
     use crate::query::Query;
     use crate::request::Wrapper;
     use crate::utils::uuid_str;
-    use crate::{Error, Response, Result, SimpleRequest, Subscription, Url};
-    use async_tungstenite::tokio::{connect_async, connect_async_with_tls_connector};
+    use crate::{CompatMode, Error, Response, Result, SimpleRequest, Subscription, Url};
+    use async_tungstenite::tokio::{client_async_tls_with_config, ConnectStream};
+    use async_tungstenite::tungstenite::protocol::WebSocketConfig;
+    use async_tungstenite::tungstenite::Error as WSError;
+    use async_tungstenite::WebSocketStream;
+    use socket2::{SockRef, TcpKeepalive};
+    use std::future::Future;
+    use std::time::Duration;
+    use tokio::net::TcpStream;
     use tracing::debug;
 
+    /// Configuration shared by all variants of our WebSocket client.
+    #[derive(Debug, Clone)]
+    pub struct Config {
+        pub connect_timeout: Option<Duration>,
+        pub tcp_nodelay: bool,
+        pub tcp_keepalive: Option<Duration>,
+        pub compat: CompatMode,
+        pub max_subscriptions: usize,
+        pub event_parse_error_policy: EventParseErrorPolicy,
+        pub max_message_size: usize,
+        pub max_frame_size: usize,
+        pub pretty_requests: bool,
+        pub strict_responses: bool,
+        pub max_query_conditions: Option<usize>,
+    }
+
+    impl Default for Config {
+        fn default() -> Self {
+            Self {
+                connect_timeout: None,
+                tcp_nodelay: false,
+                tcp_keepalive: None,
+                compat: CompatMode::default(),
+                max_subscriptions: DEFAULT_MAX_SUBSCRIPTIONS,
+                event_parse_error_policy: EventParseErrorPolicy::default(),
+                max_message_size: DEFAULT_MAX_MESSAGE_SIZE,
+                max_frame_size: DEFAULT_MAX_FRAME_SIZE,
+                pretty_requests: false,
+                strict_responses: false,
+                max_query_conditions: None,
+            }
+        }
+    }
+
+    /// Await the given connection attempt, bounded by the configured connect
+    /// timeout (if any).
+    async fn connect<F, T>(url: &str, config: &Config, connect: F) -> Result<T>
+    where
+        F: Future<Output = std::result::Result<T, WSError>>,
+    {
+        match config.connect_timeout {
+            Some(timeout) => tokio::time::timeout(timeout, connect)
+                .await
+                .map_err(|_| {
+                    Error::websocket_error(format!(
+                        "timed out after {}ms connecting to {}",
+                        timeout.as_millis(),
+                        url
+                    ))
+                    .with_kind(ErrorKind::Timeout)
+                })?
+                .map_err(Into::into),
+            None => Ok(connect.await?),
+        }
+    }
+
+    /// Open a TCP connection to the given URL's host, configured according to
+    /// the given configuration.
+    pub(super) async fn connect_tcp(url: &Url, config: &Config) -> std::io::Result<TcpStream> {
+        let stream = TcpStream::connect((url.host(), url.port())).await?;
+        stream.set_nodelay(config.tcp_nodelay)?;
+        if let Some(idle) = config.tcp_keepalive {
+            SockRef::from(&stream).set_tcp_keepalive(&TcpKeepalive::new().with_time(idle))?;
+        }
+        Ok(stream)
+    }
+
+    /// Establish a WebSocket connection (over TLS, if the URL is secure) to
+    /// the given URL.
+    async fn connect_websocket(
+        url: &Url,
+        config: &Config,
+    ) -> std::result::Result<WebSocketStream<ConnectStream>, WSError> {
+        let stream = connect_tcp(url, config).await?;
+        let ws_config = WebSocketConfig {
+            max_message_size: Some(config.max_message_size),
+            max_frame_size: Some(config.max_frame_size),
+            ..WebSocketConfig::default()
+        };
+        let (stream, _response) =
+            client_async_tls_with_config(url.to_string(), stream, Some(ws_config)).await?;
+        Ok(stream)
+    }
+
     /// Marker for the [`AsyncTungsteniteClient`] for clients operating over
     /// unsecure connections.
     #[derive(Debug, Clone)]
@@ -255,6 +642,9 @@ mod sealed {
     #[derive(Debug, Clone)]
     pub struct AsyncTungsteniteClient<C> {
         cmd_tx: ChannelTx<DriverCommand>,
+        compat: CompatMode,
+        pretty_requests: bool,
+        strict_responses: bool,
         _client_type: std::marker::PhantomData<C>,
     }
 
@@ -267,15 +657,17 @@ mod sealed {
         /// this driver becomes the responsibility of the client owner, and must be
         /// executed in a separate asynchronous context to the client to ensure it
         /// doesn't block the client.
-        pub async fn new(url: Url) -> Result<(Self, WebSocketClientDriver)> {
-            let url = url.to_string();
+        pub async fn new(url: Url, config: &Config) -> Result<(Self, WebSocketClientDriver)> {
             debug!("Connecting to unsecure WebSocket endpoint: {}", url);
-            let (stream, _response) = connect_async(url).await?;
+            let stream = connect(&url.to_string(), config, connect_websocket(&url, config)).await?;
             let (cmd_tx, cmd_rx) = unbounded();
-            let driver = WebSocketClientDriver::new(stream, cmd_rx);
+            let driver = WebSocketClientDriver::new(stream, cmd_rx, config);
             Ok((
                 Self {
                     cmd_tx,
+                    compat: config.compat,
+                    pretty_requests: config.pretty_requests,
+                    strict_responses: config.strict_responses,
                     _client_type: Default::default(),
                 },
                 driver,
@@ -293,17 +685,19 @@ mod sealed {
         /// this driver becomes the responsibility of the client owner, and must be
         /// executed in a separate asynchronous context to the client to ensure it
         /// doesn't block the client.
-        pub async fn new(url: Url) -> Result<(Self, WebSocketClientDriver)> {
-            let url = url.to_string();
+        pub async fn new(url: Url, config: &Config) -> Result<(Self, WebSocketClientDriver)> {
             debug!("Connecting to secure WebSocket endpoint: {}", url);
-            // Not supplying a connector means async_tungstenite will create the
-            // connector for us.
-            let (stream, _response) = connect_async_with_tls_connector(url, None).await?;
+            // The TLS connector is created for us by async_tungstenite, based
+            // on the URL's scheme.
+            let stream = connect(&url.to_string(), config, connect_websocket(&url, config)).await?;
             let (cmd_tx, cmd_rx) = unbounded();
-            let driver = WebSocketClientDriver::new(stream, cmd_rx);
+            let driver = WebSocketClientDriver::new(stream, cmd_rx, config);
             Ok((
                 Self {
                     cmd_tx,
+                    compat: config.compat,
+                    pretty_requests: config.pretty_requests,
+                    strict_responses: config.strict_responses,
                     _client_type: Default::default(),
                 },
                 driver,
@@ -312,35 +706,50 @@ mod sealed {
     }
 
     impl<C> AsyncTungsteniteClient<C> {
+        /// Send the given command to the driver, failing immediately if the
+        /// driver has stopped (e.g. because it was dropped, or its task
+        /// panicked) rather than waiting for a response that will never come.
         fn send_cmd(&self, cmd: DriverCommand) -> Result<()> {
-            self.cmd_tx.send(cmd).map_err(|e| {
-                Error::client_internal_error(format!(
-                    "failed to send command to client driver: {}",
-                    e
-                ))
-            })
+            self.cmd_tx.send(cmd).map_err(|_| driver_stopped())
         }
 
         pub async fn perform<R>(&self, request: R) -> Result<R::Response>
+        where
+            R: SimpleRequest,
+        {
+            let response = self.send_request(request).await?;
+            if self.strict_responses {
+                R::Response::from_string_strict(response)
+            } else {
+                R::Response::from_string(response)
+            }
+        }
+
+        pub async fn perform_wrapped<R>(&self, request: R) -> Result<response::Wrapper<R::Response>>
+        where
+            R: SimpleRequest,
+        {
+            response::Wrapper::from_string(self.send_request(request).await?)
+        }
+
+        /// Send the given request via the driver, returning the response.
+        async fn send_request<R>(&self, request: R) -> Result<String>
         where
             R: SimpleRequest,
         {
             let wrapper = Wrapper::new(request);
             let id = wrapper.id().clone().to_string();
-            let wrapped_request = wrapper.into_json();
+            let wrapped_request = wrapper.into_json_formatted(self.compat, self.pretty_requests);
+            tracing::debug!("Outgoing request: {}", wrapped_request);
             let (response_tx, mut response_rx) = unbounded();
             self.send_cmd(DriverCommand::SimpleRequest(SimpleRequestCommand {
                 id,
                 wrapped_request,
                 response_tx,
             }))?;
-            let response = response_rx.recv().await.ok_or_else(|| {
-                Error::client_internal_error(
-                    "failed to hear back from WebSocket driver".to_string(),
-                )
-            })??;
+            let response = response_rx.recv().await.ok_or_else(driver_stopped)??;
             tracing::debug!("Incoming response: {}", response);
-            R::Response::from_string(response)
+            Ok(response)
         }
 
         pub async fn subscribe(&self, query: Query) -> Result<Subscription> {
@@ -355,12 +764,19 @@ mod sealed {
                 response_tx,
             }))?;
             // Make sure our subscription request went through successfully.
-            let _ = response_rx.recv().await.ok_or_else(|| {
-                Error::client_internal_error(
-                    "failed to hear back from WebSocket driver".to_string(),
-                )
-            })??;
-            Ok(Subscription::new(id, query, subscription_rx))
+            let _ = response_rx.recv().await.ok_or_else(driver_stopped)??;
+            // Let the driver know once the subscription has been dropped, so
+            // that it can unsubscribe if it was the last one for its query.
+            // If the driver has already stopped, there's nothing to clean up.
+            let cmd_tx = self.cmd_tx.clone();
+            let dropped = DroppedCommand {
+                id: id.clone(),
+                query: query.to_string(),
+            };
+            let subscription = Subscription::new(id, query, subscription_rx);
+            Ok(subscription.on_drop(move || {
+                let _ = cmd_tx.send(DriverCommand::Dropped(dropped));
+            }))
         }
 
         pub async fn unsubscribe(&self, query: Query) -> Result<()> {
@@ -369,11 +785,7 @@ mod sealed {
                 query: query.to_string(),
                 response_tx,
             }))?;
-            let _ = response_rx.recv().await.ok_or_else(|| {
-                Error::client_internal_error(
-                    "failed to hear back from WebSocket driver".to_string(),
-                )
-            })??;
+            let _ = response_rx.recv().await.ok_or_else(driver_stopped)??;
             Ok(())
         }
 
@@ -383,6 +795,13 @@ mod sealed {
         }
     }
 
+    /// The error returned by client methods once the driver has stopped, and
+    /// hence dropped its end of the command channel (or the response channel
+    /// of a command in flight).
+    fn driver_stopped() -> Error {
+        Error::client_internal_error("websocket driver has stopped")
+    }
+
     /// Allows us to erase the type signatures associated with the different
     /// WebSocket client variants.
     #[derive(Debug, Clone)]
@@ -392,13 +811,19 @@ mod sealed {
     }
 
     impl WebSocketClient {
-        pub async fn new_unsecure(url: Url) -> Result<(Self, WebSocketClientDriver)> {
-            let (client, driver) = AsyncTungsteniteClient::<Unsecure>::new(url).await?;
+        pub async fn new_unsecure(
+            url: Url,
+            config: &Config,
+        ) -> Result<(Self, WebSocketClientDriver)> {
+            let (client, driver) = AsyncTungsteniteClient::<Unsecure>::new(url, config).await?;
             Ok((Self::Unsecure(client), driver))
         }
 
-        pub async fn new_secure(url: Url) -> Result<(Self, WebSocketClientDriver)> {
-            let (client, driver) = AsyncTungsteniteClient::<Secure>::new(url).await?;
+        pub async fn new_secure(
+            url: Url,
+            config: &Config,
+        ) -> Result<(Self, WebSocketClientDriver)> {
+            let (client, driver) = AsyncTungsteniteClient::<Secure>::new(url, config).await?;
             Ok((Self::Secure(client), driver))
         }
 
@@ -412,6 +837,16 @@ mod sealed {
             }
         }
 
+        pub async fn perform_wrapped<R>(&self, request: R) -> Result<response::Wrapper<R::Response>>
+        where
+            R: SimpleRequest,
+        {
+            match self {
+                WebSocketClient::Unsecure(c) => c.perform_wrapped(request).await,
+                WebSocketClient::Secure(c) => c.perform_wrapped(request).await,
+            }
+        }
+
         pub async fn subscribe(&self, query: Query) -> Result<Subscription> {
             match self {
                 WebSocketClient::Unsecure(c) => c.subscribe(query).await,
@@ -443,6 +878,8 @@ enum DriverCommand {
     Subscribe(SubscribeCommand),
     // Initiate an unsubscribe request.
     Unsubscribe(UnsubscribeCommand),
+    // Stop tracking a subscription which has been dropped.
+    Dropped(DroppedCommand),
     // For non-subscription-related requests.
     SimpleRequest(SimpleRequestCommand),
     Terminate,
@@ -468,6 +905,14 @@ struct UnsubscribeCommand {
     response_tx: ChannelTx<Result<()>>,
 }
 
+#[derive(Debug, Clone)]
+struct DroppedCommand {
+    // The ID of the dropped subscription.
+    id: String,
+    // The query of the dropped subscription.
+    query: String,
+}
+
 #[derive(Debug, Clone)]
 struct SimpleRequestCommand {
     // The desired ID for the outgoing JSON-RPC request. Technically we
@@ -499,18 +944,45 @@ pub struct WebSocketClientDriver {
     // Commands we've received but have not yet completed, indexed by their ID.
     // A Terminate command is executed immediately.
     pending_commands: HashMap<String, DriverCommand>,
+    // Subscribe commands waiting on a subscription request for the same query
+    // that is already in flight, indexed by query. The presence of a key
+    // indicates that a subscription request for that query is pending.
+    queued_subscriptions: HashMap<String, Vec<SubscribeCommand>>,
+    // The maximum number of distinct queries to which we may be subscribed.
+    max_subscriptions: usize,
+    // What to do with subscriptions whose events we fail to parse.
+    event_parse_error_policy: EventParseErrorPolicy,
+    // Whether to pretty-print the requests we send.
+    pretty_requests: bool,
 }
 
 impl WebSocketClientDriver {
-    fn new(stream: WebSocketStream<ConnectStream>, cmd_rx: ChannelRx<DriverCommand>) -> Self {
+    fn new(
+        stream: WebSocketStream<ConnectStream>,
+        cmd_rx: ChannelRx<DriverCommand>,
+        config: &sealed::Config,
+    ) -> Self {
         Self {
             stream,
             router: SubscriptionRouter::default(),
             cmd_rx,
             pending_commands: HashMap::new(),
+            queued_subscriptions: HashMap::new(),
+            max_subscriptions: config.max_subscriptions,
+            event_parse_error_policy: config.event_parse_error_policy,
+            pretty_requests: config.pretty_requests,
         }
     }
 
+    /// Spawn the driver on the given runtime, which must be the runtime on
+    /// which its connection was established (see [`WebSocketClient`] for
+    /// details as to runtimes).
+    ///
+    /// The returned handle resolves to the result of [`Self::run`].
+    pub fn spawn_on(self, handle: &Handle) -> JoinHandle<Result<()>> {
+        handle.spawn(self.run())
+    }
+
     /// Executes the WebSocket driver, which manages the underlying WebSocket
     /// transport.
     pub async fn run(mut self) -> Result<()> {
@@ -520,15 +992,31 @@ impl WebSocketClientDriver {
         let recv_timeout = tokio::time::sleep(RECV_TIMEOUT);
         tokio::pin!(recv_timeout);
 
+        // The number of consecutive incoming messages we've failed to decode.
+        let mut decode_errors = 0;
+
         loop {
             tokio::select! {
                 Some(res) = self.stream.next() => match res {
+                    Ok(Message::Close(frame)) => return self.closed_by_remote(frame).await,
                     Ok(msg) => {
                         // Reset the receive timeout every time we successfully
                         // receive a message from the remote endpoint.
                         recv_timeout.as_mut().reset(Instant::now().add(RECV_TIMEOUT));
+                        decode_errors = 0;
                         self.handle_incoming_msg(msg).await?
                     },
+                    Err(WSError::Capacity(e)) => return self.close_oversized(e.to_string()).await,
+                    Err(WSError::Utf8) => {
+                        decode_errors += 1;
+                        if decode_errors > MAX_CONSECUTIVE_DECODE_ERRORS {
+                            return Err(Error::websocket_error(format!(
+                                "failed to decode {} consecutive messages from WebSocket connection",
+                                decode_errors
+                            )));
+                        }
+                        error!("Failed to decode incoming WebSocket message; skipping it");
+                    },
                     Err(e) => return Err(
                         Error::websocket_error(
                             format!("failed to read from WebSocket connection: {}", e),
@@ -538,6 +1026,7 @@ impl WebSocketClientDriver {
                 Some(cmd) = self.cmd_rx.recv() => match cmd {
                     DriverCommand::Subscribe(subs_cmd) => self.subscribe(subs_cmd).await?,
                     DriverCommand::Unsubscribe(unsubs_cmd) => self.unsubscribe(unsubs_cmd).await?,
+                    DriverCommand::Dropped(dropped_cmd) => self.dropped(dropped_cmd).await,
                     DriverCommand::SimpleRequest(req_cmd) => self.simple_request(req_cmd).await?,
                     DriverCommand::Terminate => return self.close().await,
                 },
@@ -546,7 +1035,8 @@ impl WebSocketClientDriver {
                     return Err(Error::websocket_error(format!(
                         "reading from WebSocket connection timed out after {} seconds",
                         RECV_TIMEOUT.as_secs()
-                    )));
+                    ))
+                    .with_kind(ErrorKind::Timeout));
                 }
             }
         }
@@ -562,10 +1052,14 @@ impl WebSocketClientDriver {
     where
         R: Request,
     {
-        self.send_msg(Message::Text(
-            serde_json::to_string_pretty(&wrapper).unwrap(),
-        ))
-        .await
+        let json = if self.pretty_requests {
+            serde_json::to_string_pretty(&wrapper)
+        } else {
+            serde_json::to_string(&wrapper)
+        }
+        .unwrap();
+        tracing::debug!("Outgoing request: {}", json);
+        self.send_msg(Message::Text(json)).await
     }
 
     async fn subscribe(&mut self, cmd: SubscribeCommand) -> Result<()> {
@@ -579,7 +1073,25 @@ impl WebSocketClientDriver {
             return response_tx.send(Ok(()));
         }
 
-        // Otherwise, we need to initiate a subscription request.
+        // If a subscription request for this query is already in flight, this
+        // subscription will be completed along with it, so as to avoid sending
+        // a duplicate subscription request to the remote endpoint.
+        if let Some(queued) = self.queued_subscriptions.get_mut(&cmd.query) {
+            queued.push(cmd);
+            return Ok(());
+        }
+
+        // Otherwise, we need to initiate a subscription request, provided the
+        // remote endpoint would allow it. Queries whose subscription requests
+        // are still in flight count towards the limit.
+        if self.router.num_queries() + self.queued_subscriptions.len() >= self.max_subscriptions {
+            return cmd
+                .response_tx
+                .send(Err(Error::client_internal_error(format!(
+                    "subscription limit reached; Tendermint allows {} per connection",
+                    self.max_subscriptions
+                ))));
+        }
         let wrapper = Wrapper::new_with_id(
             Id::Str(cmd.id.clone()),
             subscribe::Request::new(cmd.query.clone()),
@@ -588,6 +1100,8 @@ impl WebSocketClientDriver {
             cmd.response_tx.send(Err(e.clone()))?;
             return Err(e);
         }
+        self.queued_subscriptions
+            .insert(cmd.query.clone(), Vec::new());
         self.pending_commands
             .insert(cmd.id.clone(), DriverCommand::Subscribe(cmd));
         Ok(())
@@ -617,6 +1131,26 @@ impl WebSocketClientDriver {
         Ok(())
     }
 
+    async fn dropped(&mut self, cmd: DroppedCommand) {
+        // Only unsubscribe once the last subscription for the query has been
+        // dropped, and not if the query was already unsubscribed from.
+        if self.router.remove(&cmd.id, &cmd.query) != Some(0) {
+            return;
+        }
+        debug!(
+            "All subscriptions for query \"{}\" have been dropped. Unsubscribing from query...",
+            cmd.query
+        );
+        // As in publish_event(), we issue a fire-and-forget unsubscribe
+        // message.
+        if let Err(e) = self
+            .send_request(Wrapper::new(unsubscribe::Request::new(cmd.query)))
+            .await
+        {
+            error!("Failed to send unsubscribe request: {}", e);
+        }
+    }
+
     async fn simple_request(&mut self, cmd: SimpleRequestCommand) -> Result<()> {
         if let Err(e) = self
             .send_msg(Message::Text(cmd.wrapped_request.clone()))
@@ -639,10 +1173,13 @@ impl WebSocketClientDriver {
     }
 
     async fn handle_text_msg(&mut self, msg: String) -> Result<()> {
-        if let Ok(ev) = Event::from_string(&msg) {
-            self.publish_event(ev).await;
-            return Ok(());
-        }
+        let parse_err = match Event::from_string(&msg) {
+            Ok(ev) => {
+                self.publish_event(ev).await;
+                return Ok(());
+            }
+            Err(e) => e,
+        };
 
         let wrapper = match serde_json::from_str::<response::Wrapper<GenericJsonResponse>>(&msg) {
             Ok(w) => w,
@@ -659,12 +1196,47 @@ impl WebSocketClientDriver {
         if let Some(pending_cmd) = self.pending_commands.remove(&id) {
             return self.respond_to_pending_command(pending_cmd, msg).await;
         };
-        // We ignore incoming messages whose ID we don't recognize (could be
-        // relating to a fire-and-forget unsubscribe request - see the
+        // A message carrying the query of one of our subscriptions is an event
+        // that we failed to parse.
+        let query = wrapper.into_result().ok().and_then(|res| {
+            res.0
+                .get("query")
+                .and_then(|q| q.as_str())
+                .map(String::from)
+        });
+        if let Some(query) = query {
+            if self.router.num_subscriptions_for_query(&query) > 0 {
+                self.publish_event_error(query, parse_err).await;
+            }
+        }
+        // We ignore other incoming messages whose ID we don't recognize (could
+        // be relating to a fire-and-forget unsubscribe request - see the
         // publish_event() method below).
         Ok(())
     }
 
+    async fn publish_event_error(&mut self, query: String, err: Error) {
+        error!("Failed to parse event for query \"{}\": {}", query, err);
+        let result = self.router.publish_error(&query, &err);
+        let unsubscribe = match self.event_parse_error_policy {
+            EventParseErrorPolicy::Continue => matches!(result, PublishResult::AllDisconnected),
+            EventParseErrorPolicy::FailFast => {
+                // Dropping the subscriptions' channels terminates them once
+                // they have yielded the error.
+                self.router.remove_by_query(query.clone());
+                true
+            }
+        };
+        if unsubscribe {
+            if let Err(e) = self
+                .send_request(Wrapper::new(unsubscribe::Request::new(query)))
+                .await
+            {
+                error!("Failed to send unsubscribe request: {}", e);
+            }
+        }
+    }
+
     async fn publish_event(&mut self, ev: Event) {
         if let PublishResult::AllDisconnected = self.router.publish(&ev) {
             debug!(
@@ -690,10 +1262,21 @@ impl WebSocketClientDriver {
     ) -> Result<()> {
         match pending_cmd {
             DriverCommand::Subscribe(cmd) => {
-                let (id, query, subscription_tx, response_tx) =
-                    (cmd.id, cmd.query, cmd.subscription_tx, cmd.response_tx);
-                self.router.add(id, query, subscription_tx);
-                response_tx.send(Ok(()))
+                let queued = self
+                    .queued_subscriptions
+                    .remove(&cmd.query)
+                    .unwrap_or_default();
+                for cmd in std::iter::once(cmd).chain(queued) {
+                    let (id, query, subscription_tx, response_tx) =
+                        (cmd.id, cmd.query, cmd.subscription_tx, cmd.response_tx);
+                    self.router.add(id.clone(), query, subscription_tx);
+                    // A subscriber which has stopped waiting for confirmation
+                    // must not prevent the others from receiving theirs.
+                    if let Err(e) = response_tx.send(Ok(())) {
+                        debug!("Failed to confirm subscription {}: {}", id, e);
+                    }
+                }
+                Ok(())
             }
             DriverCommand::Unsubscribe(cmd) => cmd.response_tx.send(Ok(())),
             DriverCommand::SimpleRequest(cmd) => cmd.response_tx.send(Ok(response)),
@@ -709,6 +1292,45 @@ impl WebSocketClientDriver {
         self.send_msg(Message::Ping(Vec::new())).await
     }
 
+    // Close the connection after receiving a message or frame exceeding our
+    // size limits, reporting why.
+    async fn close_oversized(mut self, cause: String) -> Result<()> {
+        let _ = self
+            .stream
+            .close(Some(CloseFrame {
+                code: CloseCode::Size,
+                reason: Cow::from("message too large"),
+            }))
+            .await;
+        Err(Error::websocket_error(format!(
+            "received a WebSocket message exceeding the configured size limits ({}); \
+             closed the connection",
+            cause
+        )))
+    }
+
+    // Terminate all subscriptions once the remote endpoint has closed the
+    // connection (e.g. because the node is shutting down), so that they end
+    // with an error carrying the reason it gave rather than simply ending.
+    async fn closed_by_remote(mut self, frame: Option<CloseFrame<'_>>) -> Result<()> {
+        let err = Error::websocket_error(match frame {
+            Some(frame) if !frame.reason.is_empty() => format!(
+                "connection closed by remote endpoint with code {}: {}",
+                frame.code, frame.reason
+            ),
+            Some(frame) => format!(
+                "connection closed by remote endpoint with code {}",
+                frame.code
+            ),
+            None => "connection closed by remote endpoint".to_string(),
+        });
+        error!("{}", err);
+        self.router.publish_error_to_all(&err);
+        // Complete the closing handshake.
+        while let Some(Ok(_)) = self.stream.next().await {}
+        Err(err)
+    }
+
     async fn close(mut self) -> Result<()> {
         self.send_msg(Message::Close(Some(CloseFrame {
             code: CloseCode::Normal,
@@ -736,6 +1358,8 @@ mod test {
     use std::collections::HashMap;
     use std::path::PathBuf;
     use std::str::FromStr;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
     use tendermint::net;
     use tokio::fs;
     use tokio::net::{TcpListener, TcpStream};
@@ -746,7 +1370,8 @@ mod test {
         node_addr: net::Address,
         driver_hdl: JoinHandle<Result<()>>,
         terminate_tx: ChannelTx<Result<()>>,
-        event_tx: ChannelTx<Event>,
+        event_tx: ChannelTx<TestEvent>,
+        request_counts: Arc<RequestCounts>,
     }
 
     impl TestServer {
@@ -760,18 +1385,33 @@ mod test {
             };
             let (terminate_tx, terminate_rx) = unbounded();
             let (event_tx, event_rx) = unbounded();
-            let driver = TestServerDriver::new(listener, event_rx, terminate_rx);
+            let request_counts = Arc::new(RequestCounts::default());
+            let driver =
+                TestServerDriver::new(listener, event_rx, terminate_rx, request_counts.clone());
             let driver_hdl = tokio::spawn(async move { driver.run().await });
             Self {
                 node_addr,
                 driver_hdl,
                 terminate_tx,
                 event_tx,
+                request_counts,
             }
         }
 
         fn publish_event(&mut self, ev: Event) -> Result<()> {
-            self.event_tx.send(ev)
+            self.event_tx.send(TestEvent::Valid(ev))
+        }
+
+        fn publish_malformed_event(&mut self, query: impl ToString) -> Result<()> {
+            self.event_tx.send(TestEvent::Malformed(query.to_string()))
+        }
+
+        fn subscribe_requests(&self) -> usize {
+            self.request_counts.subscribe.load(Ordering::SeqCst)
+        }
+
+        fn unsubscribe_requests(&self) -> usize {
+            self.request_counts.unsubscribe.load(Ordering::SeqCst)
         }
 
         async fn terminate(self) -> Result<()> {
@@ -780,25 +1420,54 @@ mod test {
         }
     }
 
+    // The total numbers of subscription and unsubscription requests received
+    // by the test server across all connections.
+    #[derive(Debug, Default)]
+    struct RequestCounts {
+        subscribe: AtomicUsize,
+        unsubscribe: AtomicUsize,
+    }
+
+    // An event published by the test server, which may deliberately be
+    // malformed.
+    #[derive(Debug, Clone)]
+    enum TestEvent {
+        Valid(Event),
+        // An event for the given query whose data cannot be parsed.
+        Malformed(String),
+    }
+
+    impl TestEvent {
+        fn query(&self) -> &str {
+            match self {
+                TestEvent::Valid(ev) => &ev.query,
+                TestEvent::Malformed(query) => query,
+            }
+        }
+    }
+
     // Manages all incoming WebSocket connections.
     struct TestServerDriver {
         listener: TcpListener,
-        event_rx: ChannelRx<Event>,
+        event_rx: ChannelRx<TestEvent>,
         terminate_rx: ChannelRx<Result<()>>,
         handlers: Vec<TestServerHandler>,
+        request_counts: Arc<RequestCounts>,
     }
 
     impl TestServerDriver {
         fn new(
             listener: TcpListener,
-            event_rx: ChannelRx<Event>,
+            event_rx: ChannelRx<TestEvent>,
             terminate_rx: ChannelRx<Result<()>>,
+            request_counts: Arc<RequestCounts>,
         ) -> Self {
             Self {
                 listener,
                 event_rx,
                 terminate_rx,
                 handlers: Vec::new(),
+                request_counts,
             }
         }
 
@@ -820,14 +1489,15 @@ mod test {
 
         // Publishes the given event to all subscribers for the query relating
         // to the event.
-        fn publish_event(&mut self, ev: Event) {
+        fn publish_event(&mut self, ev: TestEvent) {
             for handler in &mut self.handlers {
                 handler.publish_event(ev.clone());
             }
         }
 
         async fn handle_incoming(&mut self, stream: TcpStream) {
-            self.handlers.push(TestServerHandler::new(stream).await);
+            self.handlers
+                .push(TestServerHandler::new(stream, self.request_counts.clone()).await);
         }
 
         async fn terminate(&mut self) {
@@ -846,16 +1516,16 @@ mod test {
     struct TestServerHandler {
         driver_hdl: JoinHandle<Result<()>>,
         terminate_tx: ChannelTx<Result<()>>,
-        event_tx: ChannelTx<Event>,
+        event_tx: ChannelTx<TestEvent>,
     }
 
     impl TestServerHandler {
-        async fn new(stream: TcpStream) -> Self {
+        async fn new(stream: TcpStream, request_counts: Arc<RequestCounts>) -> Self {
             let conn: WebSocketStream<TokioAdapter<TcpStream>> =
                 accept_async(stream).await.unwrap();
             let (terminate_tx, terminate_rx) = unbounded();
             let (event_tx, event_rx) = unbounded();
-            let driver = TestServerHandlerDriver::new(conn, event_rx, terminate_rx);
+            let driver = TestServerHandlerDriver::new(conn, event_rx, terminate_rx, request_counts);
             let driver_hdl = tokio::spawn(async move { driver.run().await });
             Self {
                 driver_hdl,
@@ -864,7 +1534,7 @@ mod test {
             }
         }
 
-        fn publish_event(&mut self, ev: Event) {
+        fn publish_event(&mut self, ev: TestEvent) {
             let _ = self.event_tx.send(ev);
         }
 
@@ -877,24 +1547,27 @@ mod test {
     // Manages interaction with a single incoming WebSocket connection.
     struct TestServerHandlerDriver {
         conn: WebSocketStream<TokioAdapter<TcpStream>>,
-        event_rx: ChannelRx<Event>,
+        event_rx: ChannelRx<TestEvent>,
         terminate_rx: ChannelRx<Result<()>>,
         // A mapping of subscription queries to subscription IDs for this
         // connection.
         subscriptions: HashMap<String, String>,
+        request_counts: Arc<RequestCounts>,
     }
 
     impl TestServerHandlerDriver {
         fn new(
             conn: WebSocketStream<TokioAdapter<TcpStream>>,
-            event_rx: ChannelRx<Event>,
+            event_rx: ChannelRx<TestEvent>,
             terminate_rx: ChannelRx<Result<()>>,
+            request_counts: Arc<RequestCounts>,
         ) -> Self {
             Self {
                 conn,
                 event_rx,
                 terminate_rx,
                 subscriptions: HashMap::new(),
+                request_counts,
             }
         }
 
@@ -915,12 +1588,21 @@ mod test {
             }
         }
 
-        async fn publish_event(&mut self, ev: Event) {
-            let subs_id = match self.subscriptions.get(&ev.query) {
-                Some(id) => id.clone(),
+        async fn publish_event(&mut self, ev: TestEvent) {
+            let subs_id = match self.subscriptions.get(ev.query()) {
+                Some(id) => Id::Str(id.clone()),
                 None => return,
             };
-            let _ = self.send(Id::Str(subs_id), ev).await;
+            match ev {
+                TestEvent::Valid(ev) => self.send(subs_id, ev).await,
+                TestEvent::Malformed(query) => {
+                    let res = GenericJsonResponse(serde_json::json!({
+                        "query": query,
+                        "data": { "type": "tendermint/event/NewBlock", "value": 42 },
+                    }));
+                    self.send(subs_id, res).await
+                }
+            }
         }
 
         async fn handle_incoming_msg(&mut self, msg: Message) -> Option<Result<()>> {
@@ -950,6 +1632,7 @@ mod test {
                                     >(&msg)
                                     .unwrap();
 
+                                    self.request_counts.subscribe.fetch_add(1, Ordering::SeqCst);
                                     self.add_subscription(
                                         req.params().query.clone(),
                                         req.id().to_string(),
@@ -962,6 +1645,9 @@ mod test {
                                     >(&msg)
                                     .unwrap();
 
+                                    self.request_counts
+                                        .unsubscribe
+                                        .fetch_add(1, Ordering::SeqCst);
                                     self.remove_subscription(req.params().query.clone());
                                     self.send(req.id().clone(), unsubscribe::Response {}).await;
                                 }
@@ -1030,6 +1716,14 @@ mod test {
         Event::from_string(&read_json_fixture(name).await).unwrap()
     }
 
+    #[test]
+    fn websocket_client_is_shareable() {
+        fn assert_shareable<T: Clone + Send + Sync + 'static>() {}
+        fn assert_send<T: Send + 'static>() {}
+        assert_shareable::<WebSocketClient>();
+        assert_send::<WebSocketClientDriver>();
+    }
+
     #[tokio::test]
     async fn websocket_client_happy_path() {
         let event1 = read_event("event_new_block_1").await;
@@ -1082,4 +1776,447 @@ mod test {
             );
         }
     }
+
+    #[test]
+    fn websocket_client_driver_runs_on_dedicated_runtime() {
+        let driver_rt = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .enable_all()
+            .build()
+            .unwrap();
+        let test_rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        let handle = driver_rt.handle().clone();
+
+        test_rt.block_on(async move {
+            let event = read_event("event_new_block_1").await;
+            let mut server = TestServer::new("127.0.0.1:0").await;
+            let (client, driver_handle) =
+                WebSocketClient::builder(server.node_addr.clone().try_into().unwrap())
+                    .build_on(&handle)
+                    .await
+                    .unwrap();
+
+            // The client is used from the test's runtime, while its driver
+            // runs on the dedicated one.
+            let mut subs = client.subscribe(EventType::NewBlock.into()).await.unwrap();
+            server.publish_event(event.clone()).unwrap();
+            assert_eq!(event, subs.next().await.unwrap().unwrap());
+
+            client.close().unwrap();
+            driver_handle.await.unwrap().unwrap();
+            server.terminate().await.unwrap();
+        });
+    }
+
+    #[tokio::test]
+    async fn websocket_client_shares_subscriptions_to_same_query() {
+        let event = read_event("event_new_block_1").await;
+        let mut server = TestServer::new("127.0.0.1:0").await;
+        let (client, driver) = WebSocketClient::new(server.node_addr.clone())
+            .await
+            .unwrap();
+        let driver_handle = tokio::spawn(async move { driver.run().await });
+
+        // Both subscription requests reach the driver before the remote
+        // endpoint has confirmed either of them.
+        let (subs1, subs2) = futures::join!(
+            client.subscribe(EventType::NewBlock.into()),
+            client.subscribe(EventType::NewBlock.into()),
+        );
+        let (mut subs1, mut subs2) = (subs1.unwrap(), subs2.unwrap());
+        assert_ne!(subs1.id(), subs2.id());
+        assert_eq!(1, server.subscribe_requests());
+
+        server.publish_event(event.clone()).unwrap();
+        assert_eq!(event, subs1.next().await.unwrap().unwrap());
+        assert_eq!(event, subs2.next().await.unwrap().unwrap());
+
+        client.close().unwrap();
+        server.terminate().await.unwrap();
+        let _ = driver_handle.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn websocket_client_confirms_subscriptions_despite_abandoned_ones() {
+        let mut server = TestServer::new("127.0.0.1:0").await;
+        let (client, driver) = WebSocketClient::new(server.node_addr.clone())
+            .await
+            .unwrap();
+        let driver_handle = tokio::spawn(async move { driver.run().await });
+
+        // The first subscriber gives up once its subscription request has
+        // been sent, before the others are queued behind it.
+        let mut abandoned = Box::pin(client.subscribe(EventType::NewBlock.into()));
+        assert!(futures::poll!(&mut abandoned).is_pending());
+        drop(abandoned);
+        let (subs1, subs2) = futures::join!(
+            client.subscribe(EventType::NewBlock.into()),
+            client.subscribe(EventType::NewBlock.into()),
+        );
+        assert!(subs1.is_ok());
+        assert!(subs2.is_ok());
+        assert_eq!(1, server.subscribe_requests());
+
+        client.close().unwrap();
+        server.terminate().await.unwrap();
+        let _ = driver_handle.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn websocket_client_unsubscribes_when_subscriptions_are_dropped() {
+        let server = TestServer::new("127.0.0.1:0").await;
+        let (client, driver) = WebSocketClient::new(server.node_addr.clone())
+            .await
+            .unwrap();
+        let driver_handle = tokio::spawn(async move { driver.run().await });
+
+        let subs1 = client.subscribe(EventType::NewBlock.into()).await.unwrap();
+        let subs2 = client.subscribe(EventType::NewBlock.into()).await.unwrap();
+
+        // The driver handles commands, and the server handles requests, in
+        // order, so once a subsequent subscription has been confirmed, any
+        // unsubscribe request prompted by a dropped subscription has been
+        // received.
+        drop(subs1);
+        let _subs3 = client.subscribe(EventType::Tx.into()).await.unwrap();
+        assert_eq!(0, server.unsubscribe_requests());
+
+        drop(subs2);
+        let _subs4 = client
+            .subscribe(Query::from(EventType::Tx).and_eq("tx.height", 1_u64))
+            .await
+            .unwrap();
+        assert_eq!(1, server.unsubscribe_requests());
+
+        // Dropping subscriptions to a query which was explicitly unsubscribed
+        // from doesn't prompt another unsubscribe request.
+        let subs5 = client.subscribe(EventType::NewBlock.into()).await.unwrap();
+        client
+            .unsubscribe(EventType::NewBlock.into())
+            .await
+            .unwrap();
+        assert_eq!(2, server.unsubscribe_requests());
+        drop(subs5);
+        let _subs6 = client
+            .subscribe(Query::from(EventType::Tx).and_eq("tx.height", 2_u64))
+            .await
+            .unwrap();
+        assert_eq!(2, server.unsubscribe_requests());
+
+        client.close().unwrap();
+        server.terminate().await.unwrap();
+        let _ = driver_handle.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn websocket_client_handles_malformed_events() {
+        let event1 = read_event("event_new_block_1").await;
+        let event2 = read_event("event_new_block_2").await;
+        let query = Query::from(EventType::NewBlock).to_string();
+
+        for &policy in &[
+            EventParseErrorPolicy::Continue,
+            EventParseErrorPolicy::FailFast,
+        ] {
+            let mut server = TestServer::new("127.0.0.1:0").await;
+            let url = WebSocketClientUrl::try_from(server.node_addr.clone()).unwrap();
+            let (client, driver) = WebSocketClient::builder(url)
+                .on_event_parse_error(policy)
+                .build()
+                .await
+                .unwrap();
+            let driver_handle = tokio::spawn(async move { driver.run().await });
+
+            let mut subs = client.subscribe(EventType::NewBlock.into()).await.unwrap();
+            server.publish_event(event1.clone()).unwrap();
+            server.publish_malformed_event(&query).unwrap();
+            server.publish_event(event2.clone()).unwrap();
+
+            assert_eq!(event1, subs.next().await.unwrap().unwrap());
+            assert!(subs.next().await.unwrap().is_err());
+            match policy {
+                EventParseErrorPolicy::Continue => {
+                    assert_eq!(event2, subs.next().await.unwrap().unwrap())
+                }
+                EventParseErrorPolicy::FailFast => assert!(subs.next().await.is_none()),
+            }
+
+            client.close().unwrap();
+            server.terminate().await.unwrap();
+            let _ = driver_handle.await.unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn websocket_client_rejects_oversized_messages() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let local_addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut conn = accept_async(stream).await.unwrap();
+            conn.send(Message::Text("x".repeat(4096))).await.unwrap();
+            // Wait for the client to close the connection.
+            while let Some(Ok(_)) = conn.next().await {}
+        });
+
+        let url = WebSocketClientUrl::try_from(net::Address::Tcp {
+            peer_id: None,
+            host: local_addr.ip().to_string(),
+            port: local_addr.port(),
+        })
+        .unwrap();
+        let (_client, driver) = WebSocketClient::builder(url)
+            .max_message_size(1024)
+            .build()
+            .await
+            .unwrap();
+        let err = driver.run().await.unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("received a WebSocket message exceeding the configured size limits"));
+        server.await.unwrap();
+    }
+
+    // Accepts a single connection, confirming the first request received on
+    // it, which must be a subscription request.
+    async fn accept_subscriber(listener: &TcpListener) -> WebSocketStream<TokioAdapter<TcpStream>> {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut conn = accept_async(stream).await.unwrap();
+        let req = match conn.next().await.unwrap().unwrap() {
+            Message::Text(s) => {
+                serde_json::from_str::<request::Wrapper<subscribe::Request>>(&s).unwrap()
+            }
+            msg => panic!("unexpected message: {:?}", msg),
+        };
+        let res =
+            response::Wrapper::new_with_id(req.id().clone(), Some(subscribe::Response {}), None);
+        conn.send(Message::Text(serde_json::to_string(&res).unwrap()))
+            .await
+            .unwrap();
+        conn
+    }
+
+    #[tokio::test]
+    async fn websocket_client_surfaces_close_reason() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let local_addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            // Confirm the subscription, then shut down.
+            let mut conn = accept_subscriber(&listener).await;
+            conn.close(Some(CloseFrame {
+                code: CloseCode::Away,
+                reason: Cow::from("node shutting down"),
+            }))
+            .await
+            .unwrap();
+            while let Some(Ok(_)) = conn.next().await {}
+        });
+
+        let (client, driver) = WebSocketClient::new(net::Address::Tcp {
+            peer_id: None,
+            host: local_addr.ip().to_string(),
+            port: local_addr.port(),
+        })
+        .await
+        .unwrap();
+        let driver_handle = tokio::spawn(async move { driver.run().await });
+
+        let mut subs = client.subscribe(EventType::NewBlock.into()).await.unwrap();
+        let err = subs.next().await.unwrap().unwrap_err();
+        assert!(
+            err.to_string().contains(
+                "connection closed by remote endpoint with code 1001: node shutting down"
+            ),
+            "unexpected error: {}",
+            err
+        );
+        assert!(subs.next().await.is_none());
+
+        let err = driver_handle.await.unwrap().unwrap_err();
+        assert!(err.to_string().contains("node shutting down"));
+        server.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn websocket_client_skips_transient_bad_frames() {
+        let event1 = read_json_fixture("event_new_block_1").await;
+        let event2 = read_json_fixture("event_new_block_2").await;
+
+        for &bad_frames in &[1, MAX_CONSECUTIVE_DECODE_ERRORS + 1] {
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let local_addr = listener.local_addr().unwrap();
+            let (ev1, ev2) = (event1.clone(), event2.clone());
+            let server = tokio::spawn(async move {
+                let mut conn = accept_subscriber(&listener).await;
+                conn.send(Message::Text(ev1)).await.unwrap();
+                // Unfragmented text frames whose payloads are not valid
+                // UTF-8, written directly to the underlying connection.
+                for _ in 0..bad_frames {
+                    futures::AsyncWriteExt::write_all(conn.get_mut(), &[0x81, 0x02, 0xff, 0xfe])
+                        .await
+                        .unwrap();
+                }
+                // The client may already have given up on the connection.
+                let _ = conn.send(Message::Text(ev2)).await;
+                while let Some(Ok(_)) = conn.next().await {}
+            });
+
+            let (client, driver) = WebSocketClient::new(net::Address::Tcp {
+                peer_id: None,
+                host: local_addr.ip().to_string(),
+                port: local_addr.port(),
+            })
+            .await
+            .unwrap();
+            let driver_handle = tokio::spawn(async move { driver.run().await });
+
+            let mut subs = client.subscribe(EventType::NewBlock.into()).await.unwrap();
+            assert_eq!(
+                Event::from_string(&event1).unwrap(),
+                subs.next().await.unwrap().unwrap()
+            );
+            if bad_frames <= MAX_CONSECUTIVE_DECODE_ERRORS {
+                // The bad frames are skipped.
+                assert_eq!(
+                    Event::from_string(&event2).unwrap(),
+                    subs.next().await.unwrap().unwrap()
+                );
+                client.close().unwrap();
+                driver_handle.await.unwrap().unwrap();
+            } else {
+                // The driver eventually gives up.
+                let err = driver_handle.await.unwrap().unwrap_err();
+                assert!(
+                    err.to_string().contains(&format!(
+                        "failed to decode {} consecutive messages",
+                        bad_frames
+                    )),
+                    "unexpected error: {}",
+                    err
+                );
+                assert!(subs.next().await.is_none());
+            }
+            server.await.unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn websocket_client_enforces_subscription_limit() {
+        let server = TestServer::new("127.0.0.1:0").await;
+        let url = WebSocketClientUrl::try_from(server.node_addr.clone()).unwrap();
+        let (client, driver) = WebSocketClient::builder(url)
+            .max_subscriptions(2)
+            .build()
+            .await
+            .unwrap();
+        let driver_handle = tokio::spawn(async move { driver.run().await });
+
+        let _subs1 = client.subscribe(EventType::NewBlock.into()).await.unwrap();
+        let _subs2 = client.subscribe(EventType::Tx.into()).await.unwrap();
+        // Additional subscriptions to an already subscribed query are fine.
+        let _subs3 = client.subscribe(EventType::Tx.into()).await.unwrap();
+
+        let err = client
+            .subscribe(Query::from(EventType::Tx).and_eq("tx.height", 1_u64))
+            .await
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("subscription limit reached; Tendermint allows 2 per connection"));
+        assert_eq!(2, server.subscribe_requests());
+
+        // Unsubscribing frees up capacity.
+        client
+            .unsubscribe(EventType::NewBlock.into())
+            .await
+            .unwrap();
+        let _subs4 = client
+            .subscribe(Query::from(EventType::Tx).and_eq("tx.height", 1_u64))
+            .await
+            .unwrap();
+        assert_eq!(3, server.subscribe_requests());
+
+        client.close().unwrap();
+        server.terminate().await.unwrap();
+        let _ = driver_handle.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn websocket_client_fails_promptly_once_driver_stops() {
+        let server = TestServer::new("127.0.0.1:0").await;
+        let (client, driver) = WebSocketClient::new(server.node_addr.clone())
+            .await
+            .unwrap();
+        drop(driver);
+
+        let err = tokio::time::timeout(
+            Duration::from_secs(1),
+            client.subscribe(EventType::NewBlock.into()),
+        )
+        .await
+        .expect("subscribing hung after the driver was dropped")
+        .unwrap_err();
+        assert!(
+            err.to_string().contains("websocket driver has stopped"),
+            "unexpected error: {}",
+            err
+        );
+
+        // Likewise once a running driver terminates.
+        let (client, driver) = WebSocketClient::new(server.node_addr.clone())
+            .await
+            .unwrap();
+        let driver_handle = tokio::spawn(async move { driver.run().await });
+        client.clone().close().unwrap();
+        let _ = driver_handle.await.unwrap();
+        let err = tokio::time::timeout(Duration::from_secs(1), client.health())
+            .await
+            .expect("request hung after the driver terminated")
+            .unwrap_err();
+        assert!(err.to_string().contains("websocket driver has stopped"));
+
+        server.terminate().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn websocket_client_applies_tcp_options() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url: Url = format!("ws://{}/websocket", listener.local_addr().unwrap())
+            .parse()
+            .unwrap();
+
+        let config = sealed::Config::default();
+        let stream = sealed::connect_tcp(&url, &config).await.unwrap();
+        assert!(!stream.nodelay().unwrap());
+        assert!(!socket2::SockRef::from(&stream).keepalive().unwrap());
+
+        let builder = WebSocketClient::builder(url.clone().try_into().unwrap())
+            .tcp_nodelay(true)
+            .tcp_keepalive(Duration::from_secs(60));
+        let stream = sealed::connect_tcp(&url, &builder.config).await.unwrap();
+        assert!(stream.nodelay().unwrap());
+        assert!(socket2::SockRef::from(&stream).keepalive().unwrap());
+    }
+
+    #[tokio::test]
+    async fn websocket_client_connect_timeout_elapses() {
+        let (listener, _backlog) = crate::test_support::hanging_listener().await;
+        let url = format!("ws://{}/websocket", listener.local_addr().unwrap());
+        let timeout = Duration::from_millis(100);
+        let start = Instant::now();
+        let err = match WebSocketClient::builder(url.parse().unwrap())
+            .connect_timeout(timeout)
+            .build()
+            .await
+        {
+            Ok(_) => panic!("connecting to an unresponsive node succeeded"),
+            Err(e) => e,
+        };
+        assert!(start.elapsed() >= timeout);
+        assert_eq!(ErrorKind::Timeout, err.kind(), "unexpected error: {}", err);
+        assert!(err.to_string().contains("timed out after 100ms"));
+    }
 }
@@ -0,0 +1,92 @@
+//! Compatibility with different versions of the Tendermint RPC protocol.
+
+use crate::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// The version of the Tendermint RPC protocol spoken by a node, which
+/// determines how certain request parameters are encoded.
+///
+/// Most requests are encoded identically for all supported versions (e.g.
+/// transactions are always base64-encoded). At present, only
+/// `/broadcast_evidence` differs, in the name of its evidence parameter (`ev`
+/// for v0.34, `evidence` for v0.37). The evidence itself is tagged with its
+/// Amino type name (e.g. `{"type": "tendermint/DuplicateVoteEvidence",
+/// "value": {...}}`) for both versions.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CompatMode {
+    /// Tendermint v0.34 (the default)
+    V0_34,
+
+    /// Tendermint v0.37
+    V0_37,
+}
+
+impl Default for CompatMode {
+    fn default() -> Self {
+        CompatMode::V0_34
+    }
+}
+
+impl CompatMode {
+    /// The compatibility mode to use for a node running the given version of
+    /// Tendermint (see [`crate::Client::server_version`]).
+    ///
+    /// Versions prior to v0.37 are treated as v0.34, and later ones as v0.37.
+    pub fn from_version(version: &semver::Version) -> Self {
+        if (version.major, version.minor) < (0, 37) {
+            CompatMode::V0_34
+        } else {
+            CompatMode::V0_37
+        }
+    }
+}
+
+impl fmt::Display for CompatMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CompatMode::V0_34 => write!(f, "v0.34"),
+            CompatMode::V0_37 => write!(f, "v0.37"),
+        }
+    }
+}
+
+impl FromStr for CompatMode {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim_start_matches('v') {
+            "0.34" => Ok(CompatMode::V0_34),
+            "0.37" => Ok(CompatMode::V0_37),
+            _ => Err(Error::invalid_params(&format!(
+                "unsupported compatibility mode: {} (must be \"v0.34\" or \"v0.37\")",
+                s
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn compat_mode_from_version() {
+        let mode = |v: &str| CompatMode::from_version(&semver::Version::parse(v).unwrap());
+        assert_eq!(CompatMode::V0_34, mode("0.33.9"));
+        assert_eq!(CompatMode::V0_34, mode("0.34.24"));
+        assert_eq!(CompatMode::V0_37, mode("0.37.0"));
+        assert_eq!(CompatMode::V0_37, mode("0.38.1"));
+    }
+
+    #[test]
+    fn compat_mode_parsing() {
+        assert_eq!(CompatMode::V0_34, "v0.34".parse().unwrap());
+        assert_eq!(CompatMode::V0_37, "0.37".parse().unwrap());
+        assert!("v0.35".parse::<CompatMode>().is_err());
+        assert_eq!(
+            CompatMode::V0_37,
+            CompatMode::V0_37.to_string().parse().unwrap()
+        );
+    }
+}
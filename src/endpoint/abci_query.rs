@@ -1,6 +1,6 @@
 //! `/abci_query` endpoint JSON-RPC wrapper
 
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
 use tendermint::abci::{Code, Log, Path};
 use tendermint::block;
@@ -40,6 +40,27 @@ impl Request {
             prove,
         }
     }
+
+    /// Create a query for the value stored under the given key by the given
+    /// module of a Cosmos SDK application, i.e. a query with the path
+    /// `/store/<module>/key` and the key as its data.
+    ///
+    /// Fails if the module name is empty or contains a `/`.
+    pub fn store_key(
+        module: &str,
+        key: &[u8],
+        height: Option<block::Height>,
+        prove: bool,
+    ) -> crate::Result<Self> {
+        if module.is_empty() || module.contains('/') {
+            return Err(crate::Error::invalid_params(&format!(
+                "invalid store name {:?}: must be non-empty and not contain '/'",
+                module
+            )));
+        }
+        let path = format!("/store/{}/key", module).parse()?;
+        Ok(Self::new(Some(path), key, height, prove))
+    }
 }
 
 impl crate::Request for Request {
@@ -98,3 +119,86 @@ pub struct AbciQuery {
     #[serde(default = "String::new")]
     pub codespace: String,
 }
+
+impl AbciQuery {
+    /// Decode the returned value as JSON.
+    ///
+    /// Fails if the query itself failed (i.e. returned a non-zero code).
+    pub fn value_json<T: DeserializeOwned>(&self) -> Result<T, crate::Error> {
+        self.ensure_ok()?;
+        serde_json::from_slice(&self.value).map_err(crate::Error::parse_error)
+    }
+
+    /// Decode the returned value as a Protobuf message.
+    ///
+    /// Fails if the query itself failed (i.e. returned a non-zero code).
+    pub fn value_proto<T: prost::Message + Default>(&self) -> Result<T, crate::Error> {
+        self.ensure_ok()?;
+        T::decode(self.value.as_slice()).map_err(crate::Error::parse_error)
+    }
+
+    /// Whether the query failed because the node could not generate a proof.
+    pub(crate) fn is_proof_failure(&self) -> bool {
+        self.code.is_err() && mentions_proof(&self.log.to_string())
+    }
+
+    fn ensure_ok(&self) -> Result<(), crate::Error> {
+        if self.code.is_err() {
+            return Err(crate::Error::server_error(format!(
+                "ABCI query failed with code {}: {}",
+                self.code.value(),
+                self.log
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// The results of an ABCI query for which a proof was requested, but which
+/// may have been retried without one (see
+/// [`crate::Client::abci_query_with_proof`]).
+#[derive(Clone, Debug)]
+pub struct ProvenQuery {
+    /// ABCI query results
+    pub response: AbciQuery,
+
+    /// Whether the node failed to generate a proof, so that the query was
+    /// retried without requesting one (in which case `response.proof` is
+    /// `None`)
+    pub proof_dropped: bool,
+}
+
+/// Whether the given error indicates that the node failed to generate a proof
+/// for a query, rather than that the query itself failed.
+pub(crate) fn is_proof_error(e: &crate::Error) -> bool {
+    mentions_proof(e.message()) || e.data().map_or(false, mentions_proof)
+}
+
+fn mentions_proof(s: &str) -> bool {
+    s.to_lowercase().contains("proof")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn store_key_queries() {
+        let request = Request::store_key("bank", &[0x02, 0xab], Some(5_u32.into()), true).unwrap();
+        assert_eq!(
+            "/store/bank/key",
+            request.path.as_ref().unwrap().to_string()
+        );
+        assert_eq!(vec![0x02, 0xab], request.data);
+        assert_eq!(Some(block::Height::from(5_u32)), request.height);
+        assert!(request.prove);
+
+        // The key is sent hex-encoded.
+        let params = serde_json::to_value(&request).unwrap();
+        assert_eq!("02AB", params["data"].as_str().unwrap().to_uppercase());
+
+        for module in &["", "bank/balances", "/bank"] {
+            assert!(Request::store_key(module, b"key", None, false).is_err());
+        }
+    }
+}
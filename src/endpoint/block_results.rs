@@ -1,8 +1,15 @@
 //! `/block_results` endpoint JSON-RPC wrapper
 
-use serde::{Deserialize, Serialize};
+use prost::Message;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::value::RawValue;
+use std::io::Read;
 
-use tendermint::{abci, block, consensus, validator};
+use tendermint::{abci, block, consensus, validator, Hash};
+use tendermint_proto::abci::ResponseDeliverTx;
+
+use crate::response::{parse_json, parse_json_reader, Wrapper};
+use crate::Error;
 
 /// Get ABCI results at a given height.
 #[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
@@ -55,4 +62,154 @@ pub struct Response {
     pub consensus_param_updates: Option<consensus::Params>,
 }
 
+impl Response {
+    /// The Merkle root of the deterministic parts of the results of the
+    /// block's transactions (their code, data and gas), which the header of
+    /// the following block commits to as its `last_results_hash`.
+    pub fn results_hash(&self) -> Hash {
+        let leaves: Vec<Vec<u8>> = self
+            .txs_results
+            .iter()
+            .flatten()
+            .map(|result| {
+                let deterministic = ResponseDeliverTx {
+                    code: result.code.value(),
+                    data: result
+                        .data
+                        .as_ref()
+                        .map(|data| data.value().to_vec())
+                        .unwrap_or_default(),
+                    gas_wanted: result.gas_wanted.value() as i64,
+                    gas_used: result.gas_used.value() as i64,
+                    ..Default::default()
+                };
+                let mut bytes = Vec::new();
+                deterministic
+                    .encode(&mut bytes)
+                    .expect("encoding into a Vec cannot fail");
+                bytes
+            })
+            .collect();
+        Hash::Sha256(crate::merkle::root(&leaves))
+    }
+}
+
 impl crate::Response for Response {}
+
+/// Get ABCI results at a given height, decoding the results of individual
+/// transactions and block events only on access (see [`LazyResponse`]).
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct LazyRequest {
+    /// Height of the block to request.
+    ///
+    /// If no height is provided, it will fetch results for the latest block.
+    pub height: Option<block::Height>,
+}
+
+impl LazyRequest {
+    /// Create a new request for information about a particular block
+    pub fn new(height: block::Height) -> Self {
+        Self {
+            height: Some(height),
+        }
+    }
+}
+
+impl crate::Request for LazyRequest {
+    type Response = LazyResponse;
+
+    fn method(&self) -> crate::Method {
+        crate::Method::BlockResults
+    }
+}
+
+impl crate::SimpleRequest for LazyRequest {}
+
+/// ABCI result response, which retains the results of transactions and the
+/// begin/end block events as raw JSON, decoding them one at a time as they
+/// are iterated over.
+///
+/// For blocks containing thousands of transactions, this avoids holding all
+/// of their decoded results in memory at once.
+///
+/// Retaining the raw JSON requires parsing with `serde_json`, even if the
+/// `simd-json` feature is enabled.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct LazyResponse {
+    /// Block height
+    pub height: block::Height,
+
+    txs_results: Option<Box<RawValue>>,
+
+    begin_block_events: Option<Box<RawValue>>,
+
+    end_block_events: Option<Box<RawValue>>,
+
+    /// Validator updates (might be explicit null)
+    #[serde(deserialize_with = "abci::responses::deserialize_validator_updates")]
+    pub validator_updates: Vec<validator::Update>,
+
+    /// New consensus params (might be explicit null)
+    pub consensus_param_updates: Option<consensus::Params>,
+}
+
+impl LazyResponse {
+    /// The results of the block's transactions, in order, each decoded only
+    /// once the iterator reaches it.
+    ///
+    /// Fails if the results are not a JSON array. A result which fails to
+    /// decode only produces an error for that item.
+    pub fn txs_results(
+        &self,
+    ) -> Result<impl Iterator<Item = Result<abci::DeliverTx, Error>> + '_, Error> {
+        decode_lazily(&self.txs_results)
+    }
+
+    /// The begin block events, each decoded only once the iterator reaches it.
+    pub fn begin_block_events(
+        &self,
+    ) -> Result<impl Iterator<Item = Result<abci::Event, Error>> + '_, Error> {
+        decode_lazily(&self.begin_block_events)
+    }
+
+    /// The end block events, each decoded only once the iterator reaches it.
+    pub fn end_block_events(
+        &self,
+    ) -> Result<impl Iterator<Item = Result<abci::Event, Error>> + '_, Error> {
+        decode_lazily(&self.end_block_events)
+    }
+}
+
+impl crate::Response for LazyResponse {
+    fn from_string(response: impl AsRef<[u8]>) -> Result<Self, Error> {
+        let wrapper: Wrapper<Self> = parse_json(response.as_ref())?;
+        wrapper.into_result()
+    }
+
+    fn from_string_strict(response: impl AsRef<[u8]>) -> Result<Self, Error> {
+        let wrapper: Wrapper<Self> = parse_json(response.as_ref())?;
+        wrapper.into_result_strict()
+    }
+
+    fn from_reader(reader: impl Read) -> Result<Self, Error> {
+        let wrapper: Wrapper<Self> = parse_json_reader(reader)?;
+        wrapper.into_result()
+    }
+}
+
+/// Split the given raw JSON array (treating `null` as empty) into its
+/// elements, without decoding them until they are iterated over.
+fn decode_lazily<T>(
+    raw: &Option<Box<RawValue>>,
+) -> Result<impl Iterator<Item = Result<T, Error>> + '_, Error>
+where
+    T: DeserializeOwned,
+{
+    let items: Vec<&RawValue> = match raw {
+        Some(raw) => serde_json::from_str(raw.get()).map_err(Error::parse_error)?,
+        None => Vec::new(),
+    };
+    Ok(items
+        .into_iter()
+        .map(|item| serde_json::from_str(item.get()).map_err(Error::parse_error)))
+}
@@ -5,6 +5,9 @@ use std::ops::Range;
 
 use tendermint::block;
 
+/// The maximum number of block headers returned by the node at once.
+pub const MAX_BLOCKCHAIN_HEADERS: u64 = 20;
+
 /// Get information about a specific block
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct Request {
@@ -3,3 +3,211 @@
 pub mod tx_async;
 pub mod tx_commit;
 pub mod tx_sync;
+
+use crate::endpoint::tx_search::ResultTx;
+use tendermint::abci::{self, transaction, Code, Transaction};
+use tendermint::block::Height;
+
+/// Conversion into a [`Transaction`], allowing the raw bytes of a transaction
+/// to be passed directly to the broadcast methods of [`crate::Client`].
+pub trait IntoTransaction {
+    /// Convert into a transaction.
+    fn into_transaction(self) -> Transaction;
+}
+
+impl IntoTransaction for Transaction {
+    fn into_transaction(self) -> Transaction {
+        self
+    }
+}
+
+impl IntoTransaction for Vec<u8> {
+    fn into_transaction(self) -> Transaction {
+        Transaction::from(self)
+    }
+}
+
+impl IntoTransaction for &[u8] {
+    fn into_transaction(self) -> Transaction {
+        Transaction::from(self.to_vec())
+    }
+}
+
+impl<const N: usize> IntoTransaction for &[u8; N] {
+    fn into_transaction(self) -> Transaction {
+        Transaction::from(self.to_vec())
+    }
+}
+
+/// A state reached by a transaction submitted via
+/// [`crate::Client::broadcast_tx_with_progress`], in the order in which they
+/// are reached.
+#[derive(Clone, Debug)]
+pub enum TxProgress {
+    /// The node responded to the broadcast with the result of `CheckTx`. The
+    /// transaction only entered the mempool if this result is OK.
+    CheckTx(tx_sync::Response),
+
+    /// The transaction was included in the block at the given height, and
+    /// can now be found via `/tx_search`.
+    Included(ResultTx),
+
+    /// The block including the transaction was committed to by the next
+    /// block, and so is final.
+    Committed {
+        /// Hash of the transaction
+        hash: transaction::Hash,
+        /// The height of the block including the transaction
+        height: Height,
+    },
+}
+
+/// The outcome of an ABCI application's handling of a transaction (via
+/// `CheckTx` or `DeliverTx`), as identified by its code and the codespace
+/// within which the application assigns meanings to codes.
+///
+/// Codes are only meaningful within their codespace (e.g. the Cosmos SDK
+/// reports its own errors in the `sdk` codespace), so applications should
+/// match on both (see [`AbciError::is`]). A zero code indicates success
+/// regardless of the codespace (see [`AbciError::is_ok`]).
+///
+/// Nodes prior to Tendermint v0.34 do not report the codespace in
+/// `/broadcast_tx_sync` and `/broadcast_tx_async` responses, so errors
+/// constructed from their responses have an empty one.
+#[derive(Clone, Debug, Eq, PartialEq, thiserror::Error)]
+#[error("ABCI error with code {} in codespace {:?}: {log}", .code.value(), .codespace)]
+pub struct AbciError {
+    /// Code
+    pub code: Code,
+
+    /// Codespace within which the code is defined (empty if unknown)
+    pub codespace: String,
+
+    /// Log
+    pub log: String,
+}
+
+impl AbciError {
+    /// Whether the transaction was handled successfully.
+    pub fn is_ok(&self) -> bool {
+        self.code.is_ok()
+    }
+
+    /// Whether this is the error with the given code in the given codespace.
+    pub fn is(&self, codespace: &str, code: u32) -> bool {
+        self.codespace == codespace && self.code.value() == code
+    }
+
+    /// Fail with this error unless the transaction was handled successfully.
+    pub fn into_result(self) -> Result<(), Self> {
+        if self.is_ok() {
+            Ok(())
+        } else {
+            Err(self)
+        }
+    }
+}
+
+impl From<&tx_sync::Response> for AbciError {
+    fn from(response: &tx_sync::Response) -> Self {
+        Self {
+            code: response.code,
+            codespace: response.codespace.to_string(),
+            log: response.log.to_string(),
+        }
+    }
+}
+
+impl From<&tx_async::Response> for AbciError {
+    fn from(response: &tx_async::Response) -> Self {
+        Self {
+            code: response.code,
+            codespace: response.codespace.to_string(),
+            log: response.log.to_string(),
+        }
+    }
+}
+
+impl From<&tx_commit::TxResult> for AbciError {
+    fn from(result: &tx_commit::TxResult) -> Self {
+        Self {
+            code: result.code,
+            codespace: result.codespace.to_string(),
+            log: result.log.to_string(),
+        }
+    }
+}
+
+impl From<&abci::DeliverTx> for AbciError {
+    fn from(result: &abci::DeliverTx) -> Self {
+        Self {
+            code: result.code,
+            codespace: result.codespace.to_string(),
+            log: result.log.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::endpoint::tx_search;
+    use crate::test_support::fixture;
+    use crate::Response as _;
+
+    #[test]
+    fn abci_error_from_check_tx() {
+        let sync = fixture("broadcast_tx_sync")
+            .replace("\"code\": \"0\"", "\"code\": \"13\"")
+            .replace("\"log\": \"\"", "\"log\": \"insufficient fee\"");
+        let response = tx_sync::Response::from_string(&sync).unwrap();
+        let err = AbciError::from(&response);
+        assert!(!err.is_ok());
+        assert!(err.is("", 13));
+        assert_eq!("insufficient fee", err.log);
+        assert_eq!(
+            "ABCI error with code 13 in codespace \"\": insufficient fee",
+            err.to_string()
+        );
+        assert_eq!(Err(err.clone()), err.into_result());
+
+        // Tendermint v0.34 onwards reports the codespace.
+        let mut sync: serde_json::Value = serde_json::from_str(&sync).unwrap();
+        sync["result"]["codespace"] = "sdk".into();
+        let response = tx_sync::Response::from_string(sync.to_string()).unwrap();
+        assert!(AbciError::from(&response).is("sdk", 13));
+        let response = tx_async::Response::from_string(sync.to_string()).unwrap();
+        assert!(AbciError::from(&response).is("sdk", 13));
+
+        let mut commit: serde_json::Value =
+            serde_json::from_str(&fixture("broadcast_tx_commit")).unwrap();
+        let check_tx = &mut commit["result"]["check_tx"];
+        check_tx["code"] = 13.into();
+        check_tx["codespace"] = "sdk".into();
+        let response = tx_commit::Response::from_string(commit.to_string()).unwrap();
+        let err = AbciError::from(&response.check_tx);
+        assert!(err.is("sdk", 13));
+        assert!(!err.is("", 13));
+        assert!(AbciError::from(&response.deliver_tx).is_ok());
+    }
+
+    #[test]
+    fn abci_error_from_deliver_tx() {
+        let mut search: serde_json::Value =
+            serde_json::from_str(&fixture("tx_search_no_prove")).unwrap();
+        let tx_result = &mut search["result"]["txs"][0]["tx_result"];
+        tx_result["code"] = 5.into();
+        tx_result["codespace"] = "bank".into();
+        tx_result["log"] = "insufficient funds".into();
+        let response = tx_search::Response::from_string(search.to_string()).unwrap();
+
+        let err = AbciError::from(&response.txs[0].tx_result);
+        assert!(!err.is_ok());
+        assert!(err.is("bank", 5));
+        assert_eq!("insufficient funds", err.log);
+        assert!(err.into_result().is_err());
+        assert!(AbciError::from(&response.txs[1].tx_result)
+            .into_result()
+            .is_ok());
+    }
+}
@@ -2,6 +2,7 @@
 //! if we timeout waiting for tx to commit.
 
 use serde::{Deserialize, Serialize};
+use std::fmt;
 
 use tendermint::abci::responses::Codespace;
 use tendermint::abci::{Event, Gas, Info};
@@ -56,6 +57,79 @@ pub struct Response {
 
 impl crate::Response for Response {}
 
+impl Response {
+    /// Whether the transaction passed `CheckTx`.
+    pub fn is_check_tx_ok(&self) -> bool {
+        self.check_tx.code.is_ok()
+    }
+
+    /// Whether the transaction was successfully executed by `DeliverTx`.
+    ///
+    /// Always `false` if the transaction failed `CheckTx`, since it is then
+    /// never delivered.
+    pub fn is_deliver_tx_ok(&self) -> bool {
+        self.is_check_tx_ok() && self.deliver_tx.code.is_ok()
+    }
+
+    /// Convert this response into an error if either `CheckTx` or
+    /// `DeliverTx` failed, tagged with the phase that failed.
+    ///
+    /// A `CheckTx` failure means the transaction never made it into the
+    /// mempool (and may be worth resubmitting), whereas a `DeliverTx` failure
+    /// means it was included in a block at `height`, but failed to execute.
+    pub fn into_result(self) -> Result<Self, TxCommitError> {
+        if !self.is_check_tx_ok() {
+            Err(TxCommitError {
+                phase: TxPhase::CheckTx,
+                hash: self.hash,
+                result: self.check_tx,
+            })
+        } else if !self.is_deliver_tx_ok() {
+            Err(TxCommitError {
+                phase: TxPhase::DeliverTx,
+                hash: self.hash,
+                result: self.deliver_tx,
+            })
+        } else {
+            Ok(self)
+        }
+    }
+}
+
+/// The phase of a transaction's processing at which it failed.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TxPhase {
+    /// The transaction was rejected by `CheckTx`, and so never entered the
+    /// mempool.
+    CheckTx,
+
+    /// The transaction was included in a block, but `DeliverTx` failed.
+    DeliverTx,
+}
+
+impl fmt::Display for TxPhase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TxPhase::CheckTx => write!(f, "CheckTx"),
+            TxPhase::DeliverTx => write!(f, "DeliverTx"),
+        }
+    }
+}
+
+/// A failed `/broadcast_tx_commit` (see [`Response::into_result`]).
+#[derive(Clone, Debug, thiserror::Error)]
+#[error("{phase} failed for transaction {hash} with code {}: {}", .result.code.value(), .result.log)]
+pub struct TxCommitError {
+    /// The phase at which the transaction failed
+    pub phase: TxPhase,
+
+    /// Hash of the transaction
+    pub hash: transaction::Hash,
+
+    /// The result of the failed phase
+    pub result: TxResult,
+}
+
 /// Results from either `CheckTx` or `DeliverTx`.
 #[derive(Clone, Debug, Deserialize, Serialize, Default)]
 pub struct TxResult {
@@ -2,6 +2,7 @@
 
 use serde::{Deserialize, Serialize};
 
+use tendermint::abci::responses::Codespace;
 use tendermint::abci::{transaction, Code, Data, Log, Transaction};
 
 /// `/broadcast_tx_sync`: returns with the response from `CheckTx`.
@@ -40,6 +41,10 @@ pub struct Response {
     /// Log
     pub log: Log,
 
+    /// Codespace (not reported by Tendermint prior to v0.34)
+    #[serde(default)]
+    pub codespace: Codespace,
+
     /// Transaction hash
     pub hash: transaction::Hash,
 }
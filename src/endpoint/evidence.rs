@@ -1,6 +1,6 @@
 //! `/broadcast_evidence`: broadcast an evidence.
 
-use crate::Method;
+use crate::{CompatMode, Method};
 
 use serde::{Deserialize, Serialize};
 use tendermint::{abci::transaction, evidence::Evidence};
@@ -9,6 +9,7 @@ use tendermint::{abci::transaction, evidence::Evidence};
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct Request {
     /// Evidence to broadcast
+    #[serde(alias = "evidence")]
     pub ev: Evidence,
 }
 
@@ -25,6 +26,15 @@ impl crate::Request for Request {
     fn method(&self) -> Method {
         Method::BroadcastEvidence
     }
+
+    /// Tendermint v0.37 expects the evidence as the `evidence` parameter,
+    /// rather than `ev`, but encoded in the same way.
+    fn params_compat(&self, compat: CompatMode) -> serde_json::Value {
+        match compat {
+            CompatMode::V0_34 => serde_json::json!({ "ev": self.ev }),
+            CompatMode::V0_37 => serde_json::json!({ "evidence": self.ev }),
+        }
+    }
 }
 
 impl crate::SimpleRequest for Request {}
@@ -38,3 +48,60 @@ pub struct Response {
 }
 
 impl crate::Response for Response {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_support::fixture;
+    use crate::{Request as _, Response as _};
+
+    fn evidence() -> Evidence {
+        let response =
+            crate::endpoint::block::Response::from_string(fixture("block_with_evidences")).unwrap();
+        response.block.evidence.iter().next().unwrap().clone()
+    }
+
+    #[test]
+    fn evidence_params_depend_on_compat_mode() {
+        let request = Request::new(evidence());
+        let expected = serde_json::to_value(&request.ev).unwrap();
+        assert_eq!(
+            "tendermint/DuplicateVoteEvidence",
+            expected["type"].as_str().unwrap()
+        );
+
+        let json: serde_json::Value =
+            serde_json::from_str(&request.clone().into_json_compat(CompatMode::V0_34)).unwrap();
+        assert_eq!("broadcast_evidence", json["method"]);
+        assert_eq!(serde_json::json!({ "ev": expected }), json["params"]);
+        // The default encoding is that of v0.34.
+        let default: serde_json::Value =
+            serde_json::from_str(&request.clone().into_json()).unwrap();
+        assert_eq!(json["params"], default["params"]);
+
+        let json: serde_json::Value =
+            serde_json::from_str(&request.clone().into_json_compat(CompatMode::V0_37)).unwrap();
+        // Only the name of the parameter differs: the evidence is still tagged
+        // with its Amino type name.
+        assert_eq!(serde_json::json!({ "evidence": expected }), json["params"]);
+
+        // Either form can be read back.
+        let params: Request = serde_json::from_value(json["params"].clone()).unwrap();
+        assert_eq!(request, params);
+    }
+
+    #[test]
+    fn tx_params_do_not_depend_on_compat_mode() {
+        let request = crate::endpoint::broadcast::tx_sync::Request::new(vec![1, 2, 3].into());
+        let params = |compat| {
+            let json: serde_json::Value =
+                serde_json::from_str(&request.clone().into_json_compat(compat)).unwrap();
+            json["params"].clone()
+        };
+        assert_eq!(
+            serde_json::json!({ "tx": "AQID" }),
+            params(CompatMode::V0_34)
+        );
+        assert_eq!(params(CompatMode::V0_34), params(CompatMode::V0_37));
+    }
+}
@@ -1,8 +1,12 @@
 //! `/genesis` endpoint JSON-RPC wrapper
 
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
 
-use tendermint::Genesis;
+use tendermint::{validator, Genesis};
+
+/// The maximum block size permitted by Tendermint.
+pub const MAX_BLOCK_SIZE_BYTES: u64 = 104_857_600;
 
 /// Get the genesis state for the current chain
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
@@ -20,9 +24,175 @@ impl crate::SimpleRequest for Request {}
 
 /// Block responses
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(from = "RawResponse", into = "RawResponse")]
 pub struct Response {
     /// Genesis data
     pub genesis: Genesis,
+
+    /// The height of the chain's first block, which is part of the genesis
+    /// data but only reported by nodes from Tendermint v0.34 onwards
+    pub initial_height: Option<i64>,
 }
 
 impl crate::Response for Response {}
+
+/// A `/genesis` response as encoded by the node, with the initial height
+/// alongside the rest of the genesis data.
+#[derive(Clone, Deserialize, Serialize)]
+struct RawResponse {
+    genesis: RawGenesis,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+struct RawGenesis {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    initial_height: Option<InitialHeight>,
+
+    #[serde(flatten)]
+    genesis: Genesis,
+}
+
+#[derive(Clone, Copy, Deserialize, Serialize)]
+struct InitialHeight(#[serde(with = "crate::serializers::string_or_number")] i64);
+
+impl From<RawResponse> for Response {
+    fn from(raw: RawResponse) -> Self {
+        Self {
+            genesis: raw.genesis.genesis,
+            initial_height: raw.genesis.initial_height.map(|height| height.0),
+        }
+    }
+}
+
+impl From<Response> for RawResponse {
+    fn from(response: Response) -> Self {
+        Self {
+            genesis: RawGenesis {
+                initial_height: response.initial_height.map(InitialHeight),
+                genesis: response.genesis,
+            },
+        }
+    }
+}
+
+impl Response {
+    /// Sanity-check the genesis data, returning all of the problems found.
+    ///
+    /// The consensus parameters are checked against the same bounds that
+    /// Tendermint itself enforces. The initial height is only checked if the
+    /// node reports it (nodes prior to Tendermint v0.34 do not).
+    pub fn validate(&self) -> Result<(), Vec<GenesisValidationError>> {
+        let genesis = &self.genesis;
+        let params = &genesis.consensus_params;
+        let mut errors = Vec::new();
+
+        if genesis.chain_id.as_str().is_empty() {
+            errors.push(GenesisValidationError::EmptyChainId);
+        }
+        if let Some(initial_height) = self.initial_height {
+            if initial_height < 1 {
+                errors.push(GenesisValidationError::InvalidInitialHeight(initial_height));
+            }
+        }
+        if genesis.validators.is_empty() {
+            errors.push(GenesisValidationError::NoValidators);
+        }
+        if params.block.max_bytes == 0 || params.block.max_bytes > MAX_BLOCK_SIZE_BYTES {
+            errors.push(GenesisValidationError::InvalidBlockMaxBytes(
+                params.block.max_bytes,
+            ));
+        }
+        if params.block.max_gas < -1 {
+            errors.push(GenesisValidationError::InvalidBlockMaxGas(
+                params.block.max_gas,
+            ));
+        }
+        if params.evidence.max_age_num_blocks == 0 {
+            errors.push(GenesisValidationError::InvalidEvidenceMaxAgeNumBlocks);
+        }
+        if params.evidence.max_age_duration.0.as_nanos() == 0 {
+            errors.push(GenesisValidationError::InvalidEvidenceMaxAgeDuration);
+        }
+        if params.validator.pub_key_types.is_empty() {
+            errors.push(GenesisValidationError::NoPubKeyTypes);
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+/// Get only the validators in the genesis state for the current chain,
+/// without decoding the rest of the genesis data (in particular the
+/// application state, which may be very large).
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct ValidatorsRequest;
+
+impl crate::Request for ValidatorsRequest {
+    type Response = ValidatorsResponse;
+
+    fn method(&self) -> crate::Method {
+        crate::Method::Genesis
+    }
+}
+
+impl crate::SimpleRequest for ValidatorsRequest {}
+
+/// Genesis validators response (see [`ValidatorsRequest`]).
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ValidatorsResponse {
+    /// The validators from the genesis data
+    pub genesis: GenesisValidators,
+}
+
+impl crate::Response for ValidatorsResponse {}
+
+/// The validators in the genesis data, ignoring all of its other fields.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GenesisValidators {
+    /// Initial validator set
+    #[serde(deserialize_with = "crate::serializers::validator_infos")]
+    pub validators: Vec<validator::Info>,
+}
+
+/// A problem found when validating genesis data (see [`Response::validate`]).
+#[derive(Clone, Debug, Eq, Error, PartialEq)]
+pub enum GenesisValidationError {
+    /// The chain ID is empty.
+    #[error("chain ID is empty")]
+    EmptyChainId,
+
+    /// The initial height is less than 1.
+    #[error("initial height must be at least 1, but is {0}")]
+    InvalidInitialHeight(i64),
+
+    /// There are no genesis validators.
+    #[error("validator set is empty")]
+    NoValidators,
+
+    /// The maximum block size is out of bounds.
+    #[error(
+        "block.max_bytes must be between 1 and {}, but is {0}",
+        MAX_BLOCK_SIZE_BYTES
+    )]
+    InvalidBlockMaxBytes(u64),
+
+    /// The maximum gas per block is out of bounds.
+    #[error("block.max_gas must be at least -1, but is {0}")]
+    InvalidBlockMaxGas(i64),
+
+    /// The maximum evidence age in blocks is zero.
+    #[error("evidence.max_age_num_blocks must be positive")]
+    InvalidEvidenceMaxAgeNumBlocks,
+
+    /// The maximum evidence age is zero.
+    #[error("evidence.max_age_duration must be positive")]
+    InvalidEvidenceMaxAgeDuration,
+
+    /// No validator public key types are permitted.
+    #[error("validator.pub_key_types must not be empty")]
+    NoPubKeyTypes,
+}
@@ -5,6 +5,7 @@ use std::fmt::{self, Display};
 use std::net::IpAddr;
 use std::time::Duration;
 
+use crate::response::{FieldError, LenientFields};
 use tendermint::{channel::Channel, node, serializers, Time};
 
 /// Request network information from a node
@@ -40,6 +41,55 @@ pub struct Response {
 
 impl crate::Response for Response {}
 
+impl Response {
+    /// Assess the health of each of the node's peers (see
+    /// [`PeerInfo::health`]).
+    pub fn peer_health(&self, thresholds: &HealthThresholds) -> Vec<PeerHealth> {
+        self.peers
+            .iter()
+            .map(|peer| peer.health(thresholds))
+            .collect()
+    }
+
+    /// Parse a `/net_info` response on a best-effort basis.
+    ///
+    /// Each top-level field of the result is parsed independently, and the
+    /// ones which fail to parse are reported alongside the ones which
+    /// succeeded (see [`crate::endpoint::status::Response::from_string_lenient`]).
+    pub fn from_string_lenient(response: impl AsRef<[u8]>) -> crate::Result<PartialResponse> {
+        let mut fields = LenientFields::from_string(response)?;
+        Ok(PartialResponse {
+            listening: fields.take("listening"),
+            listeners: fields.take("listeners"),
+            n_peers: fields.take("n_peers").map(|NPeers(n)| n),
+            peers: fields.take("peers"),
+            errors: fields.into_errors(),
+        })
+    }
+}
+
+/// A best-effort `/net_info` response (see [`Response::from_string_lenient`])
+#[derive(Clone, Debug)]
+pub struct PartialResponse {
+    /// Are we presently listening? (if it could be parsed)
+    pub listening: Option<bool>,
+
+    /// Active listeners, if they could be parsed
+    pub listeners: Option<Vec<Listener>>,
+
+    /// Number of connected peers, if it could be parsed
+    pub n_peers: Option<u64>,
+
+    /// Peer information, if it could be parsed
+    pub peers: Option<Vec<PeerInfo>>,
+
+    /// Errors encountered while parsing the fields above
+    pub errors: Vec<FieldError>,
+}
+
+#[derive(Deserialize)]
+struct NPeers(#[serde(with = "serializers::from_str")] u64);
+
 /// Listener information
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Listener(String);
@@ -59,15 +109,112 @@ pub struct PeerInfo {
     /// Is this an outbound connection?
     pub is_outbound: bool,
 
-    /// Connection status
+    /// Connection status (defaults to empty statistics for nodes which don't
+    /// report it)
+    #[serde(default)]
     pub connection_status: ConnectionStatus,
 
     /// Remote IP address
     pub remote_ip: IpAddr,
 }
 
+impl PeerInfo {
+    /// Assess the health of the connection to this peer, based on its
+    /// duration, its direction and the recent activity in each direction.
+    ///
+    /// The score ranges from 0 to 100, and is made up of:
+    ///
+    /// * up to 40 points for the connection's duration, reaching the maximum
+    ///   once the connection is [`HealthThresholds::mature_after`] old,
+    /// * 25 points for recent activity in the receiving direction, and 15 for
+    ///   recent activity in the sending direction,
+    /// * 20 points for an outbound connection (i.e. a peer we chose), or 10
+    ///   for an inbound one.
+    ///
+    /// A direction has seen recent activity if it has not been idle for
+    /// longer than [`HealthThresholds::max_idle`], and its current rate is at
+    /// least [`HealthThresholds::min_rate`]. A peer with no recent activity
+    /// in either direction is considered stalled. Peers whose node does not
+    /// report connection statistics are scored on their direction alone, and
+    /// are never considered stalled.
+    pub fn health(&self, thresholds: &HealthThresholds) -> PeerHealth {
+        let status = &self.connection_status;
+        let direction = if self.is_outbound { 20.0 } else { 10.0 };
+        if status.duration == Duration::default() {
+            return PeerHealth {
+                node_id: self.node_info.id,
+                score: direction as u8,
+                stalled: false,
+            };
+        }
+
+        let maturity = if thresholds.mature_after == Duration::default() {
+            1.0
+        } else {
+            (status.duration.as_secs_f64() / thresholds.mature_after.as_secs_f64()).min(1.0)
+        };
+        let sending = status.send_monitor.is_active(thresholds);
+        let receiving = status.recv_monitor.is_active(thresholds);
+        let mut score = 40.0 * maturity + direction;
+        if receiving {
+            score += 25.0;
+        }
+        if sending {
+            score += 15.0;
+        }
+        PeerHealth {
+            node_id: self.node_info.id,
+            score: score.round() as u8,
+            stalled: !sending && !receiving,
+        }
+    }
+}
+
+/// The thresholds against which the health of peers is assessed (see
+/// [`PeerInfo::health`]).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct HealthThresholds {
+    /// How long a direction of a connection may be idle while still being
+    /// considered active (defaults to 1 minute)
+    pub max_idle: Duration,
+
+    /// The minimum current rate, in bytes per second, at which a direction
+    /// of a connection is considered active (defaults to 1)
+    pub min_rate: u64,
+
+    /// The duration after which a connection is considered established, and
+    /// scores full points for its duration (defaults to 1 hour)
+    pub mature_after: Duration,
+}
+
+impl Default for HealthThresholds {
+    fn default() -> Self {
+        Self {
+            max_idle: Duration::from_secs(60),
+            min_rate: 1,
+            mature_after: Duration::from_secs(60 * 60),
+        }
+    }
+}
+
+/// The assessed health of a peer (see [`PeerInfo::health`])
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct PeerHealth {
+    /// The peer's node ID
+    pub node_id: node::Id,
+
+    /// The peer's health score, from 0 (worst) to 100 (best)
+    pub score: u8,
+
+    /// Whether the connection to the peer has seen no recent activity
+    pub stalled: bool,
+}
+
 /// Connection status information
-#[derive(Clone, Debug, Deserialize, Serialize)]
+///
+/// Fields missing from the node's response are left at their defaults.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(default)]
 pub struct ConnectionStatus {
     /// Duration of this connection
     #[serde(rename = "Duration", with = "serializers::time_duration")]
@@ -86,8 +233,12 @@ pub struct ConnectionStatus {
     pub channels: Vec<Channel>,
 }
 
-/// Monitor
+/// Bandwidth monitor for one direction of a peer connection
+///
+/// Rates are in bytes per second. Fields missing from the node's response are
+/// left at their defaults.
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default)]
 pub struct Monitor {
     /// Is this monitor active?
     #[serde(rename = "Active")]
@@ -141,3 +292,30 @@ pub struct Monitor {
     #[serde(rename = "Progress")]
     pub progress: u64,
 }
+
+impl Monitor {
+    /// Whether this direction of a connection has seen recent activity.
+    fn is_active(&self, thresholds: &HealthThresholds) -> bool {
+        self.idle <= thresholds.max_idle && self.cur_rate >= thresholds.min_rate
+    }
+}
+
+impl Default for Monitor {
+    fn default() -> Self {
+        Self {
+            active: false,
+            start: Time::unix_epoch(),
+            duration: Duration::default(),
+            idle: Duration::default(),
+            bytes: 0,
+            samples: 0,
+            inst_rate: 0,
+            cur_rate: 0,
+            avg_rate: 0,
+            peak_rate: 0,
+            bytes_rem: 0,
+            time_rem: 0,
+            progress: 0,
+        }
+    }
+}
@@ -2,7 +2,10 @@
 
 use serde::{Deserialize, Serialize};
 
-use tendermint::{block, node, validator, AppHash, Hash, Time};
+use crate::response::{FieldError, LenientFields};
+use crate::serializers;
+use tendermint::node::info::TxIndexStatus;
+use tendermint::{block, net, node, validator, AppHash, Hash, Time};
 
 /// Node status request
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
@@ -18,6 +21,22 @@ impl crate::Request for Request {
 
 impl crate::SimpleRequest for Request {}
 
+/// A best-effort `/status` response (see [`Response::from_string_lenient`])
+#[derive(Clone, Debug)]
+pub struct PartialResponse {
+    /// Node information, if it could be parsed
+    pub node_info: Option<node::Info>,
+
+    /// Sync information, if it could be parsed
+    pub sync_info: Option<SyncInfo>,
+
+    /// Validator information, if it could be parsed
+    pub validator_info: Option<validator::Info>,
+
+    /// Errors encountered while parsing the fields above
+    pub errors: Vec<FieldError>,
+}
+
 /// Status responses
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Response {
@@ -28,11 +47,56 @@ pub struct Response {
     pub sync_info: SyncInfo,
 
     /// Validator information
+    #[serde(deserialize_with = "serializers::validator_info")]
     pub validator_info: validator::Info,
 }
 
 impl crate::Response for Response {}
 
+/// Validator information, parsed on its own by
+/// [`Response::from_string_lenient`].
+#[derive(Deserialize)]
+struct ValidatorInfo(#[serde(deserialize_with = "serializers::validator_info")] validator::Info);
+
+impl Response {
+    /// Parse a `/status` response on a best-effort basis.
+    ///
+    /// Unlike [`crate::Response::from_string`], a malformed top-level field of
+    /// the result does not cause the whole response to be rejected: each
+    /// field is parsed independently, and the ones which fail to parse are
+    /// reported alongside the ones which succeeded. The JSON-RPC envelope
+    /// itself must still be well-formed.
+    pub fn from_string_lenient(response: impl AsRef<[u8]>) -> crate::Result<PartialResponse> {
+        let mut fields = LenientFields::from_string(response)?;
+        Ok(PartialResponse {
+            node_info: fields.take("node_info"),
+            sync_info: fields.take("sync_info"),
+            validator_info: fields
+                .take("validator_info")
+                .map(|info: ValidatorInfo| info.0),
+            errors: fields.into_errors(),
+        })
+    }
+
+    /// Whether the node indexes transactions (i.e. whether `/tx` and
+    /// `/tx_search` are available).
+    pub fn tx_index_enabled(&self) -> bool {
+        matches!(self.node_info.other.tx_index, TxIndexStatus::On)
+    }
+
+    /// The address on which the node's RPC server listens, if it could be
+    /// parsed.
+    pub fn rpc_address(&self) -> Option<net::Address> {
+        self.node_info.other.rpc_address.parse().ok()
+    }
+
+    /// The address on which the node listens for P2P connections, if it could
+    /// be parsed.
+    pub fn p2p_listen_address(&self) -> Option<net::Address> {
+        self.node_info.listen_addr.to_net_address()
+    }
+}
+
 /// Sync information
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct SyncInfo {
@@ -50,6 +114,31 @@ pub struct SyncInfo {
     /// Latest block time
     pub latest_block_time: Time,
 
+    /// Earliest block height available from the node, which is greater than
+    /// 1 if older blocks have been pruned (absent prior to Tendermint v0.33)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub earliest_block_height: Option<block::Height>,
+
+    /// Earliest block time available from the node (absent prior to
+    /// Tendermint v0.33)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub earliest_block_time: Option<Time>,
+
     /// Are we catching up?
     pub catching_up: bool,
 }
+
+impl SyncInfo {
+    /// The inclusive range of heights of the blocks available from the node,
+    /// from the earliest block not yet pruned to the latest.
+    ///
+    /// Nodes which do not report their earliest block height (or report it
+    /// as 0) are assumed to retain all blocks from height 1.
+    pub fn available_height_range(&self) -> (block::Height, block::Height) {
+        let earliest = self
+            .earliest_block_height
+            .filter(|h| h.value() > 0)
+            .unwrap_or_else(|| block::Height::from(1_u32));
+        (earliest, self.latest_block_height)
+    }
+}
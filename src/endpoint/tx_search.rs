@@ -1,10 +1,16 @@
 //! `/tx_search` endpoint JSON-RPC wrapper
 
-use crate::{Method, Order};
+use crate::{merkle, Error, Method, Order, Result};
 use serde::{Deserialize, Serialize};
-use tendermint::{abci, block};
+use sha2::{Digest, Sha256};
+use std::convert::{TryFrom, TryInto};
+use tendermint::{abci, block, Hash};
 use tendermint_proto::types::TxProof;
 
+/// The maximum number of results per page that nodes return, beyond which
+/// they silently reduce the page size.
+pub const MAX_PER_PAGE: u8 = 100;
+
 /// Request for searching for transactions with their results.
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct Request {
@@ -53,6 +59,22 @@ pub struct Response {
     pub total_count: u32,
 }
 
+impl Response {
+    /// Sort the transactions by height and then by their index within their
+    /// block, in the given order, so that results are deterministic
+    /// regardless of how the node breaks ties.
+    ///
+    /// Only the transactions in this response are sorted, so the results of
+    /// separate requests (e.g. for different pages) are not sorted relative
+    /// to one another.
+    pub fn sort_by_position(&mut self, order: &Order) {
+        self.txs.sort_by_key(|tx| (tx.height, tx.index));
+        if *order == Order::Descending {
+            self.txs.reverse();
+        }
+    }
+}
+
 impl crate::Response for Response {}
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -65,3 +87,100 @@ pub struct ResultTx {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub proof: Option<TxProof>,
 }
+
+impl ResultTx {
+    /// Decode the proof of this transaction's inclusion in its block, if one
+    /// was requested (i.e. via `prove`).
+    pub fn inclusion_proof(&self) -> Result<Option<TxInclusionProof>> {
+        self.proof.clone().map(TryInto::try_into).transpose()
+    }
+}
+
+/// A Merkle proof of the inclusion of a transaction in a block, as decoded
+/// from the proof returned by `/tx_search` (see [`ResultTx::inclusion_proof`]).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TxInclusionProof {
+    /// The Merkle root of the block's transactions, i.e. the `data_hash` in
+    /// the block's header
+    pub root_hash: Hash,
+
+    /// The transaction whose inclusion is proven
+    pub tx: Vec<u8>,
+
+    /// The number of transactions in the block
+    pub total: u64,
+
+    /// The index of the transaction in the block
+    pub index: u64,
+
+    /// The hash of the Merkle tree's leaf for the transaction
+    pub leaf_hash: [u8; 32],
+
+    /// The hashes of the sibling subtrees on the path from the leaf to the
+    /// root, from the bottom up
+    pub aunts: Vec<[u8; 32]>,
+}
+
+impl TxInclusionProof {
+    /// Verify that this proves the inclusion of its transaction in a block
+    /// whose transactions have the given Merkle root (i.e. the `data_hash` in
+    /// the block's header).
+    ///
+    /// The root hash included in the proof itself is reported by the node,
+    /// and so must not be relied upon.
+    pub fn verify(&self, root_hash: &Hash) -> Result<()> {
+        if self.root_hash != *root_hash {
+            return Err(Error::client_internal_error(format!(
+                "transaction proof is for root hash {} rather than {}",
+                self.root_hash, root_hash
+            )));
+        }
+        // Transactions are included in the Merkle tree by their hashes.
+        if merkle::leaf_hash(&Sha256::digest(&self.tx)) != self.leaf_hash {
+            return Err(Error::client_internal_error(
+                "transaction proof's leaf hash does not match the transaction",
+            ));
+        }
+        match merkle::root_from_aunts(self.index, self.total, self.leaf_hash, &self.aunts) {
+            Some(computed) if Hash::Sha256(computed) == *root_hash => Ok(()),
+            _ => Err(Error::client_internal_error(format!(
+                "transaction proof does not lead to root hash {}",
+                root_hash
+            ))),
+        }
+    }
+}
+
+impl TryFrom<TxProof> for TxInclusionProof {
+    type Error = Error;
+
+    fn try_from(value: TxProof) -> Result<Self> {
+        let proof = value
+            .proof
+            .ok_or_else(|| Error::parse_error("transaction proof is missing its Merkle proof"))?;
+        Ok(TxInclusionProof {
+            root_hash: Hash::Sha256(sha256_hash(&value.root_hash, "root hash")?),
+            tx: value.data,
+            total: u64::try_from(proof.total)
+                .map_err(|_| Error::parse_error("negative total in transaction proof"))?,
+            index: u64::try_from(proof.index)
+                .map_err(|_| Error::parse_error("negative index in transaction proof"))?,
+            leaf_hash: sha256_hash(&proof.leaf_hash, "leaf hash")?,
+            aunts: proof
+                .aunts
+                .iter()
+                .map(|aunt| sha256_hash(aunt, "aunt"))
+                .collect::<Result<_>>()?,
+        })
+    }
+}
+
+fn sha256_hash(bytes: &[u8], what: &str) -> Result<[u8; 32]> {
+    <[u8; 32]>::try_from(bytes).map_err(|_| {
+        Error::parse_error(format!(
+            "transaction proof {} has {} bytes rather than 32",
+            what,
+            bytes.len()
+        ))
+    })
+}
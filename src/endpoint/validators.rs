@@ -1,6 +1,6 @@
 //! `/validators` endpoint JSON-RPC wrapper
 
-use crate::{PageNumber, PerPage};
+use crate::{serializers, PageNumber, PerPage};
 use serde::{Deserialize, Serialize};
 use tendermint::{block, validator};
 
@@ -72,10 +72,11 @@ pub struct Response {
     pub block_height: block::Height,
 
     /// Validator list
+    #[serde(deserialize_with = "serializers::validator_infos")]
     pub validators: Vec<validator::Info>,
 
     /// Total number of validators for this block height.
-    #[serde(with = "tendermint_proto::serializers::from_str")]
+    #[serde(with = "serializers::string_or_number")]
     pub total: i32,
 }
 
@@ -5,12 +5,20 @@ use async_tungstenite::tungstenite::Error as WSError;
 
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::fmt::{self, Display};
+use std::sync::Arc;
 use thiserror::Error;
 
 // TODO(thane): Differentiate between RPC response errors and internal crate
 //              errors (e.g. domain type-related errors).
 /// Tendermint RPC errors
-#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+///
+/// Errors arising from a lower-level error (e.g. a failure to connect to the
+/// node) expose that error via [`std::error::Error::source`]. The source is
+/// neither serialized nor taken into account when comparing errors.
+///
+/// Errors can be classified via [`Error::kind`], e.g. to decide whether the
+/// failed request is worth retrying (see [`Error::is_retryable`]).
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Error {
     /// Error code
     code: Code,
@@ -20,8 +28,32 @@ pub struct Error {
 
     /// Additional data about the error
     data: Option<String>,
+
+    /// The lower-level error which caused this one, if any
+    #[serde(skip)]
+    source: Option<Arc<dyn std::error::Error + Send + Sync + 'static>>,
+
+    /// The kind of error, if not implied by the code and source
+    #[serde(skip)]
+    kind: Option<ErrorKind>,
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match &self.source {
+            Some(source) => Some(source.as_ref()),
+            None => None,
+        }
+    }
+}
+
+impl PartialEq for Error {
+    fn eq(&self, other: &Self) -> bool {
+        self.code == other.code && self.message == other.message && self.data == other.data
+    }
 }
-impl std::error::Error for Error {}
+
+impl Eq for Error {}
 
 impl Error {
     /// Create a new RPC error
@@ -32,6 +64,8 @@ impl Error {
             code,
             message,
             data,
+            source: None,
+            kind: None,
         }
     }
 
@@ -41,6 +75,8 @@ impl Error {
             code: Code::HttpError,
             message: message.into(),
             data: None,
+            source: None,
+            kind: None,
         }
     }
 
@@ -80,6 +116,85 @@ impl Error {
         Error::new(Code::ClientInternalError, Some(cause.into()))
     }
 
+    /// Attach the lower-level error which caused this one, to be returned by
+    /// [`std::error::Error::source`].
+    pub fn with_source<E>(mut self, source: E) -> Error
+    where
+        E: std::error::Error + Send + Sync + 'static,
+    {
+        self.source = Some(Arc::new(source));
+        self
+    }
+
+    /// Classify this error as being of the given kind, rather than the kind
+    /// implied by its code (see [`Error::kind`]).
+    pub fn with_kind(mut self, kind: ErrorKind) -> Error {
+        self.kind = Some(kind);
+        self
+    }
+
+    /// The kind of failure this error represents.
+    ///
+    /// Unless classified otherwise when the error was created, the kind is
+    /// determined by the error's code, except that errors caused by an I/O
+    /// timeout are [`ErrorKind::Timeout`]s, and the node rejecting a
+    /// transaction because its mempool is full is [`ErrorKind::Unavailable`].
+    pub fn kind(&self) -> ErrorKind {
+        if let Some(kind) = self.kind {
+            return kind;
+        }
+        if self.is_caused_by_timeout() {
+            return ErrorKind::Timeout;
+        }
+        match self.code {
+            Code::HttpError | Code::WebSocketError => ErrorKind::Connection,
+            Code::ClientInternalError => ErrorKind::Client,
+            Code::ParseError => ErrorKind::Parse,
+            Code::InvalidRequest | Code::MethodNotFound | Code::InvalidParams => {
+                ErrorKind::InvalidRequest
+            }
+            _ if self.is_mempool_full() => ErrorKind::Unavailable,
+            Code::InternalError | Code::ServerError | Code::Other(_) => ErrorKind::Server,
+        }
+    }
+
+    /// Whether the failure is likely to be transient, such that the same
+    /// request may succeed if retried later (see [`ErrorKind::is_retryable`]).
+    pub fn is_retryable(&self) -> bool {
+        self.kind().is_retryable()
+    }
+
+    /// Whether the node does not support the requested method (i.e. it
+    /// responded with the JSON-RPC "method not found" error code, -32601),
+    /// e.g. because the endpoint was introduced in a later version of
+    /// Tendermint.
+    ///
+    /// Unlike other invalid requests, this can be used to detect whether a
+    /// node supports a given endpoint.
+    pub fn is_method_not_found(&self) -> bool {
+        self.code == Code::MethodNotFound
+    }
+
+    /// Whether this is the node's response to a transaction being submitted
+    /// while its mempool is full.
+    pub(crate) fn is_mempool_full(&self) -> bool {
+        self.data()
+            .map_or(false, |data| data.contains("mempool is full"))
+    }
+
+    fn is_caused_by_timeout(&self) -> bool {
+        let mut source = std::error::Error::source(self);
+        while let Some(e) = source {
+            if let Some(e) = e.downcast_ref::<std::io::Error>() {
+                if e.kind() == std::io::ErrorKind::TimedOut {
+                    return true;
+                }
+            }
+            source = e.source();
+        }
+        false
+    }
+
     /// Obtain the `rpc::error::Code` for this error
     pub fn code(&self) -> Code {
         self.code
@@ -113,13 +228,13 @@ impl Display for Error {
 
 impl From<std::io::Error> for Error {
     fn from(e: std::io::Error) -> Self {
-        Error::client_internal_error(e.to_string())
+        Error::client_internal_error(e.to_string()).with_source(e)
     }
 }
 
 impl From<url::ParseError> for Error {
     fn from(e: url::ParseError) -> Self {
-        Error::invalid_params(&e.to_string())
+        Error::invalid_params(&e.to_string()).with_source(e)
     }
 }
 
@@ -127,13 +242,15 @@ impl From<url::ParseError> for Error {
 impl From<http::Error> for Error {
     fn from(http_error: http::Error) -> Error {
         Error::http_error(http_error.to_string())
+            .with_source(http_error)
+            .with_kind(ErrorKind::Client)
     }
 }
 
 #[cfg(feature = "http-client")]
 impl From<hyper::Error> for Error {
     fn from(hyper_error: hyper::Error) -> Error {
-        Error::http_error(hyper_error.to_string())
+        Error::http_error(hyper_error.to_string()).with_source(hyper_error)
     }
 }
 
@@ -141,13 +258,15 @@ impl From<hyper::Error> for Error {
 impl From<http::uri::InvalidUri> for Error {
     fn from(e: http::uri::InvalidUri) -> Self {
         Error::http_error(e.to_string())
+            .with_source(e)
+            .with_kind(ErrorKind::Client)
     }
 }
 
 #[cfg(feature = "websocket-client")]
 impl From<WSError> for Error {
     fn from(websocket_error: WSError) -> Error {
-        Error::websocket_error(websocket_error.to_string())
+        Error::websocket_error(websocket_error.to_string()).with_source(websocket_error)
     }
 }
 
@@ -165,6 +284,60 @@ impl From<tendermint::Error> for Error {
     }
 }
 
+/// The kinds of failure an [`Error`] can represent (see [`Error::kind`]).
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum ErrorKind {
+    /// The node could not be reached, or the connection to it was lost.
+    Connection,
+
+    /// No response was received in time.
+    Timeout,
+
+    /// The node (or a proxy in front of it) is temporarily unable to handle
+    /// the request, e.g. because it is overloaded or its mempool is full.
+    Unavailable,
+
+    /// The node rejected the request as invalid, e.g. because of invalid
+    /// parameters or an unknown method.
+    InvalidRequest,
+
+    /// The response could not be parsed.
+    Parse,
+
+    /// The node failed to handle the request.
+    Server,
+
+    /// The client failed, or declined to send the request or to accept the
+    /// response (e.g. a redirect it was not configured to follow).
+    Client,
+}
+
+impl ErrorKind {
+    /// Whether failures of this kind are likely to be transient, such that
+    /// the same request may succeed if retried later.
+    ///
+    /// Connection failures, timeouts and unavailability are retryable,
+    /// whereas other failures would be expected to recur.
+    pub fn is_retryable(self) -> bool {
+        matches!(
+            self,
+            ErrorKind::Connection | ErrorKind::Timeout | ErrorKind::Unavailable
+        )
+    }
+
+    /// The kind of error indicated by the given HTTP response status, if it
+    /// means that the node (or a proxy in front of it) could not handle the
+    /// request at present. The node itself reports errors via JSON-RPC
+    /// instead.
+    pub(crate) fn from_http_status(status: u16) -> Option<Self> {
+        match status {
+            429 | 502 | 503 => Some(ErrorKind::Unavailable),
+            504 => Some(ErrorKind::Timeout),
+            _ => None,
+        }
+    }
+}
+
 /// Tendermint RPC error codes.
 ///
 /// See `func RPC*Error()` definitions in:
@@ -274,6 +447,7 @@ impl Serialize for Code {
 mod tests {
     use super::Code;
     use super::Error;
+    use super::ErrorKind;
 
     #[test]
     fn test_serialize() {
@@ -287,4 +461,92 @@ mod tests {
         assert_eq!(res.code.value(), -32700);
         assert_eq!(res.data, Some("hello world".to_string()));
     }
+
+    #[test]
+    fn source_is_exposed_but_not_compared() {
+        use std::error::Error as _;
+
+        let io_error = std::io::Error::new(std::io::ErrorKind::Other, "disk on fire");
+        let err = Error::from(io_error);
+        let source = err.source().unwrap();
+        assert_eq!("disk on fire", source.to_string());
+        assert!(source.downcast_ref::<std::io::Error>().is_some());
+
+        assert_eq!(Error::client_internal_error("disk on fire"), err);
+        assert!(Error::client_internal_error("disk on fire")
+            .source()
+            .is_none());
+        assert!(err.clone().source().is_some());
+    }
+
+    #[test]
+    fn errors_are_classified() {
+        use std::io;
+
+        let transient = vec![
+            (
+                Error::http_error("connection refused"),
+                ErrorKind::Connection,
+            ),
+            (
+                Error::websocket_error("connection reset"),
+                ErrorKind::Connection,
+            ),
+            (
+                Error::http_error("failed to connect")
+                    .with_source(io::Error::new(io::ErrorKind::TimedOut, "connect timeout")),
+                ErrorKind::Timeout,
+            ),
+            (
+                Error::client_internal_error("deadline elapsed").with_kind(ErrorKind::Timeout),
+                ErrorKind::Timeout,
+            ),
+            (
+                Error::http_error("received 503 Service Unavailable")
+                    .with_kind(ErrorKind::Unavailable),
+                ErrorKind::Unavailable,
+            ),
+            (
+                Error::new(
+                    Code::InternalError,
+                    Some("mempool is full: number of txs 5000 (max: 5000)".to_string()),
+                ),
+                ErrorKind::Unavailable,
+            ),
+        ];
+        let permanent = vec![
+            (
+                Error::invalid_params("bad height"),
+                ErrorKind::InvalidRequest,
+            ),
+            (Error::method_not_found("foo"), ErrorKind::InvalidRequest),
+            (
+                Error::new(Code::InvalidRequest, None),
+                ErrorKind::InvalidRequest,
+            ),
+            (Error::parse_error("unexpected EOF"), ErrorKind::Parse),
+            (
+                Error::server_error("tx already exists in cache"),
+                ErrorKind::Server,
+            ),
+            (Error::new(Code::Other(-1), None), ErrorKind::Server),
+            (Error::client_internal_error("oops"), ErrorKind::Client),
+            (
+                Error::http_error("too many redirects").with_kind(ErrorKind::Client),
+                ErrorKind::Client,
+            ),
+            (
+                Error::from(io::Error::new(io::ErrorKind::Other, "disk on fire")),
+                ErrorKind::Client,
+            ),
+        ];
+        for (err, kind) in transient {
+            assert_eq!(kind, err.kind(), "{}", err);
+            assert!(err.is_retryable(), "{}", err);
+        }
+        for (err, kind) in permanent {
+            assert_eq!(kind, err.kind(), "{}", err);
+            assert!(!err.is_retryable(), "{}", err);
+        }
+    }
 }
@@ -7,6 +7,7 @@ use tendermint::{
     Block,
 };
 
+use crate::endpoint::block_results;
 use crate::query::EventType;
 use crate::{response::Wrapper, Response};
 
@@ -38,6 +39,43 @@ impl Event {
             _ => None,
         }
     }
+
+    /// The ABCI results carried by a `NewBlock` event, in the same form as
+    /// those returned by the `/block_results` endpoint, which saves
+    /// subscribers a separate request.
+    ///
+    /// `NewBlock` events do not carry the results of individual transactions
+    /// (these are published as `Tx` events), so `txs_results` is always
+    /// `None`.
+    ///
+    /// Returns `None` if this is not a `NewBlock` event, or if the event
+    /// carries no block (needed for the height) or no results at all.
+    pub fn block_results(&self) -> Option<block_results::Response> {
+        let (block, begin_block, end_block) = match &self.data {
+            EventData::NewBlock {
+                block: Some(block),
+                result_begin_block,
+                result_end_block,
+            } => (block, result_begin_block, result_end_block),
+            _ => return None,
+        };
+        if begin_block.is_none() && end_block.is_none() {
+            return None;
+        }
+        Some(block_results::Response {
+            height: block.header.height,
+            txs_results: None,
+            begin_block_events: begin_block.as_ref().map(|r| r.events.clone()),
+            end_block_events: end_block.as_ref().map(|r| r.events.clone()),
+            validator_updates: end_block
+                .as_ref()
+                .map(|r| r.validator_updates.clone())
+                .unwrap_or_default(),
+            consensus_param_updates: end_block
+                .as_ref()
+                .and_then(|r| r.consensus_param_updates.clone()),
+        })
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
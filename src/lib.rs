@@ -16,34 +16,75 @@
 //!   client functionality, including general RPC functionality as well as
 //!   [`event::Event`] subscription functionality. Can be used over secure
 //!   (`wss://`) and unsecure (`ws://`) connections.
+//! * `wasm-client` - Provides [`FetchClient`], which interacts with remote
+//!   Tendermint nodes via **JSON-RPC over HTTP or HTTPS** using the `fetch`
+//!   API, for use in browsers and other JavaScript runtimes when compiling
+//!   to WebAssembly. Like [`HttpClient`], it does not provide [`event::Event`]
+//!   subscription functionality. Implies `unsend`.
+//! * `timed-client` - Provides [`TimedClient`], which records how long each
+//!   request takes, for use in latency tests. Requires one of the client
+//!   features above.
+//! * `unsend` - Relaxes the requirement for the futures returned by
+//!   [`Client`] and [`SubscriptionClient`] methods to be `Send`, so that
+//!   clients can be used in single-threaded contexts (see [`Client`]).
 //!
 //! ### Mock Clients
 //!
-//! Mock clients are included when either of the `http-client` or
-//! `websocket-client` features are enabled to aid in testing. This includes
-//! [`MockClient`], which implements both [`Client`] and [`SubscriptionClient`]
-//! traits.
+//! Mock clients are included when any of the `http-client`,
+//! `websocket-client` or `wasm-client` features are enabled to aid in
+//! testing. This includes [`MockClient`], which implements both [`Client`]
+//! and [`SubscriptionClient`] traits.
 //!
 //! [Tendermint RPC]: https://docs.tendermint.com/master/rpc/
 //! [`/subscribe` endpoint]: https://docs.tendermint.com/master/rpc/#/Websocket/subscribe
 
-#[cfg(any(feature = "http-client", feature = "websocket-client"))]
+#[cfg(any(
+    feature = "http-client",
+    feature = "websocket-client",
+    feature = "wasm-client"
+))]
 mod client;
-#[cfg(any(feature = "http-client", feature = "websocket-client"))]
+mod compat;
+#[cfg(any(
+    feature = "http-client",
+    feature = "websocket-client",
+    feature = "wasm-client"
+))]
 pub use client::{
-    Client, MockClient, MockRequestMatcher, MockRequestMethodMatcher, Subscription,
+    AppHashTransition, CachingClient, ChainFollower, Client, ClientStream, FinalizedQueryClient,
+    FollowEvent, HealthGatedClient, LightBlock, MockClient, MockRequestMatcher,
+    MockRequestMethodMatcher, MultiClient, NodeSnapshot, PinnedHeightClient, Subscription,
     SubscriptionClient,
 };
 
-#[cfg(feature = "http-client")]
-pub use client::{HttpClient, HttpClientUrl};
+#[cfg(feature = "wasm-client")]
+pub use client::FetchClient;
+#[cfg(any(feature = "http-client", feature = "wasm-client"))]
+pub use client::HttpClientUrl;
 #[cfg(feature = "websocket-client")]
-pub use client::{WebSocketClient, WebSocketClientDriver, WebSocketClientUrl};
+pub use client::{
+    EventParseErrorPolicy, WebSocketClient, WebSocketClientBuilder, WebSocketClientDriver,
+    WebSocketClientUrl,
+};
+#[cfg(feature = "http-client")]
+pub use client::{
+    HttpClient, HttpClientBuilder, IncomingResponse, OutgoingRequest, RequestInterceptor,
+};
+#[cfg(all(
+    any(test, feature = "timed-client"),
+    any(
+        feature = "http-client",
+        feature = "websocket-client",
+        feature = "wasm-client"
+    )
+))]
+pub use client::{RequestDuration, TimedClient, DEFAULT_DURATION_CAPACITY};
 
 pub mod endpoint;
 pub mod error;
 pub mod event;
 mod id;
+mod merkle;
 mod method;
 mod order;
 mod paging;
@@ -52,14 +93,18 @@ pub mod request;
 pub mod response;
 mod result;
 mod rpc_url;
+pub mod serializers;
+#[cfg(test)]
+mod test_support;
 mod utils;
 mod version;
 
+pub use compat::CompatMode;
 pub use error::Error;
 pub use id::Id;
 pub use method::Method;
 pub use order::Order;
-pub use paging::{PageNumber, Paging, PerPage};
+pub use paging::{PageNumber, Paging, PerPage, DEFAULT_MAX_PAGES};
 pub use request::{Request, SimpleRequest};
 pub use response::Response;
 pub use result::Result;
@@ -0,0 +1,68 @@
+//! Merkle tree hashing, as per Tendermint's `crypto/merkle` package, with
+//! leaves and inner nodes hashed with distinct prefixes (RFC 6962).
+
+use sha2::{Digest, Sha256};
+
+/// The hash of a leaf of a Merkle tree.
+pub(crate) fn leaf_hash(leaf: &[u8]) -> [u8; 32] {
+    Sha256::new().chain(&[0]).chain(leaf).finalize().into()
+}
+
+/// The hash of an inner node of a Merkle tree, given those of its children.
+pub(crate) fn inner_hash(left: &[u8], right: &[u8]) -> [u8; 32] {
+    Sha256::new()
+        .chain(&[1])
+        .chain(left)
+        .chain(right)
+        .finalize()
+        .into()
+}
+
+/// The number of leaves in the left subtree of a tree with `total` (at least
+/// two) leaves: the largest power of two less than `total`.
+fn split_point(total: u64) -> u64 {
+    1 << (63 - (total - 1).leading_zeros())
+}
+
+/// The root of the Merkle tree of the given leaves, as per Tendermint's
+/// `HashFromByteSlices`.
+pub(crate) fn root<T: AsRef<[u8]>>(leaves: &[T]) -> [u8; 32] {
+    match leaves {
+        [] => Sha256::digest(&[]).into(),
+        [leaf] => leaf_hash(leaf.as_ref()),
+        _ => {
+            let split = split_point(leaves.len() as u64) as usize;
+            inner_hash(&root(&leaves[..split]), &root(&leaves[split..]))
+        }
+    }
+}
+
+/// The root of the Merkle tree of `total` leaves, computed from the hash of
+/// the leaf at `index` and its aunts, as per Tendermint's
+/// `computeHashFromAunts`. `None` if the aunts do not fit the tree's shape.
+pub(crate) fn root_from_aunts(
+    index: u64,
+    total: u64,
+    leaf: [u8; 32],
+    aunts: &[[u8; 32]],
+) -> Option<[u8; 32]> {
+    if index >= total {
+        return None;
+    }
+    if total == 1 {
+        return if aunts.is_empty() { Some(leaf) } else { None };
+    }
+    let (last, rest) = aunts.split_last()?;
+    let split = split_point(total);
+    if index < split {
+        Some(inner_hash(
+            &root_from_aunts(index, split, leaf, rest)?,
+            last,
+        ))
+    } else {
+        Some(inner_hash(
+            last,
+            &root_from_aunts(index - split, total - split, leaf, rest)?,
+        ))
+    }
+}
@@ -95,6 +95,17 @@ impl Method {
             Method::Validators => "validators",
         }
     }
+
+    /// The earliest Tendermint version known to provide this method.
+    ///
+    /// Methods which have been available for as long as this crate has
+    /// supported Tendermint report version `0.0.0`.
+    pub fn min_version(self) -> semver::Version {
+        match self {
+            Method::BroadcastEvidence => semver::Version::new(0, 33, 0),
+            _ => semver::Version::new(0, 0, 0),
+        }
+    }
 }
 
 impl FromStr for Method {
@@ -5,12 +5,20 @@ use serde::{Deserialize, Serialize};
 use std::convert::TryInto;
 use std::str::FromStr;
 
+/// The maximum number of pages fetched for [`Paging::All`], beyond which the
+/// node's responses are assumed to be inconsistent (see
+/// [`Client::all_validators`] to use a different limit).
+///
+/// [`Client::all_validators`]: crate::Client::all_validators
+pub const DEFAULT_MAX_PAGES: usize = 100;
+
 /// Pagination control for those RPC client methods supporting pagination.
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub enum Paging {
     /// No explicit options set - use whatever the endpoint's defaults are.
     Default,
-    /// Try to automatically fetch all pages' data.
+    /// Try to automatically fetch all pages' data, failing if that takes more
+    /// than [`DEFAULT_MAX_PAGES`] pages.
     All,
     /// Fetch a specific page's data.
     Specific {
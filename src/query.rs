@@ -7,9 +7,16 @@
 // TODO(thane): These warnings are generated by the PEG for some reason. Try to fix and remove.
 #![allow(clippy::redundant_closure_call, clippy::unit_arg)]
 
+use crate::event::{Event, EventData};
 use crate::{Error, Result};
-use chrono::{Date, DateTime, FixedOffset, Utc};
-use std::fmt;
+use chrono::{Date, DateTime, FixedOffset, NaiveDate, Utc};
+use once_cell::sync::OnceCell;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::fmt::{self, Write};
 use std::str::FromStr;
 
 /// A structured query for use in interacting with the Tendermint RPC event
@@ -36,6 +43,28 @@ use std::str::FromStr;
 /// assert_eq!("tm.event = 'Tx' AND tx.height >= 100", query.to_string());
 /// ```
 ///
+/// ### Local matching
+///
+/// Queries can also be evaluated locally against the events of an
+/// [`Event`](crate::event::Event) using [`Query::matches`]. For local
+/// filtering only, the negated conditions [`Condition::NotEq`] and
+/// [`Condition::NotContains`] are available. These are not part of the
+/// Tendermint query grammar, so queries containing them are rejected before
+/// being sent to a node.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use tendermint_rpc::query::{Query, EventType};
+///
+/// let query = Query::from(EventType::Tx).and_not_eq("transfer.sender", "AddrA");
+/// assert!(query.validate_for_server().is_err());
+///
+/// let mut events = HashMap::new();
+/// events.insert("tm.event".to_string(), vec!["Tx".to_string()]);
+/// events.insert("transfer.sender".to_string(), vec!["AddrB".to_string()]);
+/// assert!(query.matches(&events));
+/// ```
+///
 /// [subscribe endpoint documentation]: https://docs.tendermint.com/master/rpc/#/Websocket/subscribe
 #[derive(Debug, Clone, PartialEq)]
 pub struct Query {
@@ -44,114 +73,517 @@ pub struct Query {
     // We can have zero or more additional conditions associated with a query.
     // Conditions are currently exclusively joined by logical ANDs.
     conditions: Vec<Condition>,
+    // The rendered form of this query, computed on first use and discarded
+    // whenever the query is modified.
+    rendered: Rendered,
 }
 
 impl Query {
     /// Query constructor testing whether `<key> = <value>`
     pub fn eq(key: impl ToString, value: impl Into<Operand>) -> Self {
-        Self {
-            event_type: None,
-            conditions: vec![Condition::Eq(key.to_string(), value.into())],
-        }
+        Self::default().and_eq(key, value)
     }
 
     /// Query constructor testing whether `<key> < <value>`
     pub fn lt(key: impl ToString, value: impl Into<Operand>) -> Self {
-        Self {
-            event_type: None,
-            conditions: vec![Condition::Lt(key.to_string(), value.into())],
-        }
+        Self::default().and_lt(key, value)
     }
 
     /// Query constructor testing whether `<key> <= <value>`
     pub fn lte(key: impl ToString, value: impl Into<Operand>) -> Self {
-        Self {
-            event_type: None,
-            conditions: vec![Condition::Lte(key.to_string(), value.into())],
-        }
+        Self::default().and_lte(key, value)
     }
 
     /// Query constructor testing whether `<key> > <value>`
     pub fn gt(key: impl ToString, value: impl Into<Operand>) -> Self {
-        Self {
-            event_type: None,
-            conditions: vec![Condition::Gt(key.to_string(), value.into())],
-        }
+        Self::default().and_gt(key, value)
     }
 
     /// Query constructor testing whether `<key> >= <value>`
     pub fn gte(key: impl ToString, value: impl Into<Operand>) -> Self {
-        Self {
-            event_type: None,
-            conditions: vec![Condition::Gte(key.to_string(), value.into())],
-        }
+        Self::default().and_gte(key, value)
+    }
+
+    /// Query constructor for events of the given type whose height is within
+    /// the inclusive range `min..=max`, using the height key appropriate to
+    /// the event type (see [`EventType::height_key`]).
+    pub fn for_type_height_range(event_type: EventType, min: u64, max: u64) -> Self {
+        let key = event_type.height_key();
+        Self::from(event_type).and_gte(key, min).and_lte(key, max)
+    }
+
+    /// Query constructor for transactions including a Cosmos SDK message of
+    /// the given type, i.e. `tm.event = 'Tx' AND message.action = '<action>'`.
+    ///
+    /// ```rust
+    /// use tendermint_rpc::query::Query;
+    ///
+    /// let query = Query::for_message_action("/cosmos.bank.v1beta1.MsgSend");
+    /// assert_eq!(
+    ///     "tm.event = 'Tx' AND message.action = '/cosmos.bank.v1beta1.MsgSend'",
+    ///     query.to_string()
+    /// );
+    /// ```
+    pub fn for_message_action(action: &str) -> Self {
+        Self::from(EventType::Tx).and_eq("message.action", action)
+    }
+
+    /// Construct a query from a list of `(key, operator, value)` filters, e.g.
+    /// as supplied by users of an API, joined with `AND`.
+    ///
+    /// The value is ignored for [`Op::Exists`]. Fails if a key is not valid
+    /// according to the Tendermint query grammar (i.e. if it is empty, or
+    /// contains whitespace, quotes, backslashes, parentheses or any of `=`,
+    /// `<` and `>`), or if a [`Op::Contains`] or [`Op::NotContains`] filter
+    /// is given a value other than a string. Composite keys may consist of
+    /// any number of dot-separated segments, e.g. `coin_received.receiver`.
+    pub fn try_from_filters(
+        event_type: Option<EventType>,
+        filters: &[(String, Op, Operand)],
+    ) -> Result<Self> {
+        let query = match event_type {
+            Some(t) => Self::from(t),
+            None => Self::default(),
+        };
+        filters.iter().try_fold(query, |query, (key, op, value)| {
+            if !is_valid_key(key) {
+                return Err(Error::invalid_params(&format!(
+                    "invalid key in query filter: \"{}\"",
+                    key
+                )));
+            }
+            let substring = || match value {
+                Operand::String(s) => Ok(s.clone()),
+                other => Err(Error::invalid_params(&format!(
+                    "operator {} requires a string value, but got {}",
+                    op, other
+                ))),
+            };
+            let key = key.clone();
+            let value = value.clone();
+            Ok(match op {
+                Op::Eq => query.and_eq(key, value),
+                Op::Lt => query.and_lt(key, value),
+                Op::Lte => query.and_lte(key, value),
+                Op::Gt => query.and_gt(key, value),
+                Op::Gte => query.and_gte(key, value),
+                Op::Contains => query.and_contains(key, substring()?),
+                Op::Exists => query.and_exists(key),
+                Op::NotEq => query.and_not_eq(key, value),
+                Op::NotContains => query.and_not_contains(key, substring()?),
+            })
+        })
     }
 
     /// Query constructor testing whether `<key> CONTAINS <value>` (assuming
     /// `key` contains a string, this tests whether `value` is a sub-string
     /// within it).
     pub fn contains(key: impl ToString, value: impl ToString) -> Self {
-        Self {
-            event_type: None,
-            conditions: vec![Condition::Contains(key.to_string(), value.to_string())],
-        }
+        Self::default().and_contains(key, value)
     }
 
     /// Query constructor testing whether `<key> EXISTS`.
     pub fn exists(key: impl ToString) -> Self {
-        Self {
-            event_type: None,
-            conditions: vec![Condition::Exists(key.to_string())],
-        }
+        Self::default().and_exists(key)
     }
 
     /// Add the condition `<key> = <value>` to the query.
-    pub fn and_eq(mut self, key: impl ToString, value: impl Into<Operand>) -> Self {
-        self.conditions
-            .push(Condition::Eq(key.to_string(), value.into()));
-        self
+    pub fn and_eq(self, key: impl ToString, value: impl Into<Operand>) -> Self {
+        self.push(Condition::Eq(key.to_string(), value.into()))
     }
 
     /// Add the condition `<key> < <value>` to the query.
-    pub fn and_lt(mut self, key: impl ToString, value: impl Into<Operand>) -> Self {
-        self.conditions
-            .push(Condition::Lt(key.to_string(), value.into()));
-        self
+    pub fn and_lt(self, key: impl ToString, value: impl Into<Operand>) -> Self {
+        self.push(Condition::Lt(key.to_string(), value.into()))
     }
 
     /// Add the condition `<key> <= <value>` to the query.
-    pub fn and_lte(mut self, key: impl ToString, value: impl Into<Operand>) -> Self {
-        self.conditions
-            .push(Condition::Lte(key.to_string(), value.into()));
-        self
+    pub fn and_lte(self, key: impl ToString, value: impl Into<Operand>) -> Self {
+        self.push(Condition::Lte(key.to_string(), value.into()))
     }
 
     /// Add the condition `<key> > <value>` to the query.
-    pub fn and_gt(mut self, key: impl ToString, value: impl Into<Operand>) -> Self {
-        self.conditions
-            .push(Condition::Gt(key.to_string(), value.into()));
-        self
+    pub fn and_gt(self, key: impl ToString, value: impl Into<Operand>) -> Self {
+        self.push(Condition::Gt(key.to_string(), value.into()))
     }
 
     /// Add the condition `<key> >= <value>` to the query.
-    pub fn and_gte(mut self, key: impl ToString, value: impl Into<Operand>) -> Self {
-        self.conditions
-            .push(Condition::Gte(key.to_string(), value.into()));
-        self
+    pub fn and_gte(self, key: impl ToString, value: impl Into<Operand>) -> Self {
+        self.push(Condition::Gte(key.to_string(), value.into()))
+    }
+
+    /// Add the pair of conditions `<key> >= TIME <start> AND <key> <= TIME
+    /// <end>` to the query if `inclusive` is set, or `<key> > TIME <start> AND
+    /// <key> < TIME <end>` otherwise.
+    ///
+    /// Fails if `start` is later than `end`.
+    pub fn and_time_between(
+        self,
+        key: impl ToString,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        inclusive: bool,
+    ) -> Result<Self> {
+        if start > end {
+            return Err(Error::invalid_params(&format!(
+                "invalid time range: start ({}) is later than end ({})",
+                start.to_rfc3339(),
+                end.to_rfc3339()
+            )));
+        }
+        let key = key.to_string();
+        Ok(if inclusive {
+            self.and_gte(key.clone(), start).and_lte(key, end)
+        } else {
+            self.and_gt(key.clone(), start).and_lt(key, end)
+        })
+    }
+
+    /// Add the pair of conditions `<key> >= <low> AND <key> <= <high>` to the
+    /// query if `inclusive` is set, or `<key> > <low> AND <key> < <high>`
+    /// otherwise.
+    ///
+    /// Fails if either bound is not a number, or if `low` is greater than
+    /// `high` (or cannot be compared to it).
+    pub fn and_between(
+        self,
+        key: impl ToString,
+        low: impl Into<Operand>,
+        high: impl Into<Operand>,
+        inclusive: bool,
+    ) -> Result<Self> {
+        let (low, high) = (low.into(), high.into());
+        if !low.is_number() || !high.is_number() {
+            return Err(Error::invalid_params(&format!(
+                "invalid range: bounds must be numbers, but got {} and {}",
+                low, high
+            )));
+        }
+        match low.cmp_operand(&high) {
+            Some(Ordering::Less) | Some(Ordering::Equal) => {}
+            Some(Ordering::Greater) => {
+                return Err(Error::invalid_params(&format!(
+                    "invalid range: low ({}) is greater than high ({})",
+                    low, high
+                )))
+            }
+            None => {
+                return Err(Error::invalid_params(&format!(
+                    "invalid range: low ({}) cannot be compared to high ({})",
+                    low, high
+                )))
+            }
+        }
+        let key = key.to_string();
+        Ok(if inclusive {
+            self.and_gte(key.clone(), low).and_lte(key, high)
+        } else {
+            self.and_gt(key.clone(), low).and_lt(key, high)
+        })
     }
 
     /// Add the condition `<key> CONTAINS <value>` to the query.
-    pub fn and_contains(mut self, key: impl ToString, value: impl ToString) -> Self {
-        self.conditions
-            .push(Condition::Contains(key.to_string(), value.to_string()));
-        self
+    pub fn and_contains(self, key: impl ToString, value: impl ToString) -> Self {
+        self.push(Condition::Contains(key.to_string(), value.to_string()))
     }
 
     /// Add the condition `<key> EXISTS` to the query.
-    pub fn and_exists(mut self, key: impl ToString) -> Self {
-        self.conditions.push(Condition::Exists(key.to_string()));
+    pub fn and_exists(self, key: impl ToString) -> Self {
+        self.push(Condition::Exists(key.to_string()))
+    }
+
+    /// Append the given clause to the query verbatim, joined to any other
+    /// conditions with `AND`.
+    ///
+    /// This is an escape hatch for parts of the query grammar (e.g. newly
+    /// supported operators) that cannot otherwise be expressed. The clause is
+    /// neither escaped nor validated, so it is the caller's responsibility to
+    /// ensure that it is well-formed and that any strings it contains are
+    /// properly quoted. Raw clauses cannot be evaluated locally, so a query
+    /// containing one never [matches](Query::matches) any events.
+    pub fn and_raw(self, clause: impl Into<String>) -> Self {
+        self.push(Condition::Raw(clause.into()))
+    }
+
+    /// Add the local-only condition `<key> != <value>` to the query.
+    ///
+    /// See [`Condition::NotEq`].
+    pub fn and_not_eq(self, key: impl ToString, value: impl Into<Operand>) -> Self {
+        self.push(Condition::NotEq(key.to_string(), value.into()))
+    }
+
+    /// Add the local-only condition `<key> NOT CONTAINS <value>` to the query.
+    ///
+    /// See [`Condition::NotContains`].
+    pub fn and_not_contains(self, key: impl ToString, value: impl ToString) -> Self {
+        self.push(Condition::NotContains(key.to_string(), value.to_string()))
+    }
+
+    /// Add the given condition to the query, e.g. one obtained by parsing.
+    pub fn and_condition(self, condition: Condition) -> Self {
+        self.push(condition)
+    }
+
+    /// This query, with the given conditions instead of any it previously
+    /// had, restricted to the same event type (if any).
+    pub fn with_conditions(mut self, conditions: Vec<Condition>) -> Self {
+        self.conditions = conditions;
+        self.rendered = Rendered::default();
+        self
+    }
+
+    /// Put this query's conditions into a canonical order, and remove any
+    /// duplicates, so that queries which differ only in the order in which
+    /// their conditions were added become equal (and render identically),
+    /// e.g. for use as cache keys.
+    ///
+    /// Conditions are ordered by their rendering, and hence primarily by
+    /// key. The event type, if any, is always rendered first. Conditions
+    /// whose operands differ only in their numeric representation (see
+    /// [`Operand::equivalent`]) are duplicates, of which the first is kept.
+    pub fn normalize(mut self) -> Self {
+        self.conditions.sort_by_cached_key(|c| c.to_string());
+        self.conditions.dedup_by(|a, b| a.equivalent(b));
+        self.rendered = Rendered::default();
+        self
+    }
+
+    /// This query, restricted to events of the given type instead of any type
+    /// it was previously restricted to, with the same conditions.
+    pub fn with_event_type(mut self, event_type: EventType) -> Self {
+        self.event_type = Some(event_type);
+        self.rendered = Rendered::default();
+        self
+    }
+
+    /// This query, no longer restricted to events of any particular type,
+    /// with the same conditions.
+    pub fn without_event_type(mut self) -> Self {
+        self.event_type = None;
+        self.rendered = Rendered::default();
+        self
+    }
+
+    /// The number of conditions in this query, including its event type (if
+    /// any), which the node treats as a `tm.event` condition. Raw clauses (see
+    /// [`Query::and_raw`]) count as a single condition each.
+    pub fn condition_count(&self) -> usize {
+        self.event_type.iter().count() + self.conditions.len()
+    }
+
+    /// Check that this query only makes use of conditions supported by the
+    /// Tendermint query grammar, and can therefore be sent to a node.
+    ///
+    /// Tendermint itself does not limit the number of conditions in a query;
+    /// see [`Query::validate_condition_count`] to check against a limit.
+    pub fn validate_for_server(&self) -> Result<()> {
+        if let Some(condition) = self.conditions.iter().find(|c| c.is_local_only()) {
+            return Err(Error::invalid_params(&format!(
+                "condition \"{}\" can only be used for local matching and cannot be sent to the server",
+                condition
+            )));
+        }
+        Ok(())
+    }
+
+    /// As per [`Query::validate_for_server`], additionally checking that this
+    /// query has at most the given number of conditions, if any (as
+    /// configured for clients via e.g.
+    /// [`crate::HttpClientBuilder::max_query_conditions`]).
+    pub fn validate_for_server_with_max(&self, max_conditions: Option<usize>) -> Result<()> {
+        self.validate_for_server()?;
+        match max_conditions {
+            Some(max) => self.validate_condition_count(max),
+            None => Ok(()),
+        }
+    }
+
+    /// Check that this query has at most the given number of conditions (see
+    /// [`Query::condition_count`]), e.g. to match a node's configured limit.
+    ///
+    /// This catches queries which were built up programmatically (e.g. from
+    /// a long list of addresses) before they are sent to a node.
+    pub fn validate_condition_count(&self, max: usize) -> Result<()> {
+        let count = self.condition_count();
+        if count > max {
+            return Err(Error::invalid_params(&format!(
+                "query has {} conditions, exceeding the maximum of {}",
+                count, max
+            )));
+        }
+        Ok(())
+    }
+
+    /// Whether every event matched by this query is also matched by the given
+    /// query, e.g. so that a subscription for this query can be served by an
+    /// existing subscription for the other.
+    ///
+    /// The check is conservative: it only holds if each of the other query's
+    /// conditions (including its event type) is implied by one of this
+    /// query's conditions on its own, and is `false` whenever this cannot be
+    /// decided, e.g. for raw clauses (see [`Query::and_raw`]) not present in
+    /// both queries, or for operands of different types.
+    ///
+    /// ```rust
+    /// use tendermint_rpc::query::{EventType, Query};
+    ///
+    /// let narrow = Query::from(EventType::Tx)
+    ///     .and_eq("transfer.sender", "AddrA")
+    ///     .and_gt("tx.height", 100_u64);
+    /// let broad = Query::from(EventType::Tx).and_exists("transfer.sender");
+    /// assert!(narrow.is_subset_of(&broad));
+    /// assert!(!broad.is_subset_of(&narrow));
+    /// ```
+    pub fn is_subset_of(&self, other: &Query) -> bool {
+        let conditions = self.all_conditions();
+        other
+            .all_conditions()
+            .iter()
+            .all(|required| conditions.iter().any(|c| c.implies(required)))
+    }
+
+    // This query's conditions, along with its event type as the `tm.event`
+    // condition the node treats it as.
+    fn all_conditions(&self) -> Vec<Condition> {
+        self.event_type
+            .iter()
+            .map(|t| Condition::Eq("tm.event".to_owned(), Operand::String(t.to_string())))
+            .chain(self.conditions.iter().cloned())
+            .collect()
+    }
+
+    /// Evaluate this query locally against the given event attributes (for
+    /// example, the `events` of an [`Event`](crate::event::Event)), which map
+    /// composite keys such as `transfer.sender` to their values.
+    ///
+    /// As with Tendermint's own evaluator, a condition holds if any of the
+    /// values associated with its key satisfies it, and the event type is
+    /// matched against the `tm.event` key. Negated conditions hold if their
+    /// positive counterpart does not, including when the key is absent.
+    pub fn matches(&self, events: &HashMap<String, Vec<String>>) -> bool {
+        if let Some(t) = &self.event_type {
+            let t = t.to_string();
+            let type_matches = events
+                .get("tm.event")
+                .map_or(false, |values| values.iter().any(|v| *v == t));
+            if !type_matches {
+                return false;
+            }
+        }
+        self.conditions.iter().all(|c| c.matches(events))
+    }
+
+    /// Evaluate this query locally against an [`Event`] received from a
+    /// subscription.
+    ///
+    /// Events produced by a subscription to an identical query always match.
+    /// Otherwise the query is evaluated against the event's attributes (see
+    /// [`Query::matches`]), supplemented by the `tm.event`, `tx.height` and
+    /// `block.height` keys derived from the event's data where the node did
+    /// not provide them.
+    pub fn matches_event(&self, event: &Event) -> bool {
+        if event.query == self.as_str() {
+            return true;
+        }
+
+        let mut events = event.events.clone().unwrap_or_default();
+        if let Some(t) = event.event_type() {
+            events
+                .entry("tm.event".to_owned())
+                .or_insert_with(|| vec![t.to_string()]);
+        }
+        match &event.data {
+            EventData::NewBlock {
+                block: Some(block), ..
+            } => {
+                events
+                    .entry(EventType::NewBlock.height_key().to_owned())
+                    .or_insert_with(|| vec![block.header.height.to_string()]);
+            }
+            EventData::Tx { tx_result } => {
+                events
+                    .entry(EventType::Tx.height_key().to_owned())
+                    .or_insert_with(|| vec![tx_result.height.to_string()]);
+            }
+            _ => (),
+        }
+        self.matches(&events)
+    }
+
+    /// Render this query in the form expected by the Tendermint RPC.
+    ///
+    /// The rendered string is computed once and cached, so queries that are
+    /// sent repeatedly (e.g. for subscriptions or repeated searches) are only
+    /// formatted once. Modifying the query discards the cached rendering.
+    pub fn as_str(&self) -> &str {
+        self.rendered.0.get_or_init(|| self.render())
+    }
+
+    /// The value of the `query` parameter sent to the Tendermint RPC for this
+    /// query (e.g. by `/subscribe` and `/tx_search`).
+    ///
+    /// This is identical to the query's `Display` rendering: string operands
+    /// are already quoted and escaped, and any further escaping required
+    /// (e.g. of double quotes within JSON-RPC requests) is applied by the
+    /// transport when serializing the request. See [`Query::to_url_param`]
+    /// for the form expected in URIs.
+    pub fn to_param(&self) -> String {
+        self.as_str().to_owned()
+    }
+
+    /// The `query` parameter for this query in the form expected by the
+    /// Tendermint RPC's URI-over-HTTP interface (e.g.
+    /// `/tx_search?query=<param>`), i.e. double-quoted and URL-encoded.
+    pub fn to_url_param(&self) -> String {
+        let quoted = format!("\"{}\"", self.as_str());
+        url::form_urlencoded::byte_serialize(quoted.as_bytes()).collect()
+    }
+
+    /// A structured representation of this query's conditions, as expected
+    /// by tooling which does not parse the query grammar.
+    ///
+    /// This is an array with an object of the form `{"key": ..., "op": ...,
+    /// "value": ..., "type": ...}` for each condition, including one for the
+    /// event type (if any). The `type` is one of `"string"`, `"number"`,
+    /// `"date"` or `"time"`, and the `value` is a JSON number for numbers and
+    /// a string otherwise (dates as `YYYY-MM-DD` and times in RFC 3339
+    /// format). Both are `null` for `EXISTS` conditions. Raw clauses (see
+    /// [`Query::and_raw`]) are of type `"raw"`, with the clause as their
+    /// value and a `null` key and operator.
+    pub fn to_conditions_json(&self) -> Value {
+        let event_type = self.event_type.iter().map(|t| {
+            json!({
+                "key": "tm.event",
+                "op": Op::Eq.to_string(),
+                "value": t.to_string(),
+                "type": "string",
+            })
+        });
+        event_type
+            .chain(self.conditions.iter().map(Condition::to_json))
+            .collect()
+    }
+
+    fn push(mut self, condition: Condition) -> Self {
+        self.conditions.push(condition);
+        self.rendered = Rendered::default();
         self
     }
+
+    fn render(&self) -> String {
+        let mut s = String::new();
+        if let Some(t) = &self.event_type {
+            // Writing to a `String` cannot fail.
+            write!(s, "tm.event = '{}'", t).unwrap();
+
+            if !self.conditions.is_empty() {
+                s.push_str(" AND ");
+            }
+        }
+
+        join(&mut s, " AND ", &self.conditions).unwrap();
+        s
+    }
 }
 
 impl Default for Query {
@@ -162,6 +594,7 @@ impl Default for Query {
         Self {
             event_type: None,
             conditions: Vec::new(),
+            rendered: Rendered::default(),
         }
     }
 }
@@ -170,29 +603,39 @@ impl From<EventType> for Query {
     fn from(t: EventType) -> Self {
         Self {
             event_type: Some(t),
-            conditions: Vec::new(),
+            ..Self::default()
         }
     }
 }
 
+impl From<&Query> for String {
+    fn from(query: &Query) -> Self {
+        query.as_str().to_owned()
+    }
+}
+
 impl fmt::Display for Query {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        if let Some(t) = &self.event_type {
-            write!(f, "tm.event = '{}'", t)?;
-
-            if !self.conditions.is_empty() {
-                write!(f, " AND ")?;
-            }
-        }
-
-        join(f, " AND ", &self.conditions)?;
+        f.write_str(self.as_str())
+    }
+}
 
-        Ok(())
+/// Lazily computed rendering of a [`Query`].
+///
+/// Since this is derived entirely from the query's other fields, it never
+/// affects equality comparisons.
+#[derive(Debug, Clone, Default)]
+struct Rendered(OnceCell<String>);
+
+impl PartialEq for Rendered {
+    fn eq(&self, _other: &Self) -> bool {
+        true
     }
 }
 
-fn join<S, I>(f: &mut fmt::Formatter<'_>, separator: S, iterable: I) -> fmt::Result
+fn join<W, S, I>(f: &mut W, separator: S, iterable: I) -> fmt::Result
 where
+    W: fmt::Write,
     S: fmt::Display,
     I: IntoIterator,
     I::Item: fmt::Display,
@@ -210,12 +653,26 @@ where
 }
 
 /// The types of Tendermint events for which we can query at present.
-#[derive(Debug, Clone, PartialEq)]
+///
+/// Event types are serialized in the same form as they are displayed (and
+/// parsed), e.g. `"NewBlock"`, as in `tm.event` conditions.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum EventType {
     NewBlock,
     Tx,
 }
 
+impl EventType {
+    /// The key of the height attribute of events of this type (e.g.
+    /// `tx.height` for `Tx` events).
+    pub fn height_key(&self) -> &'static str {
+        match self {
+            EventType::NewBlock => "block.height",
+            EventType::Tx => "tx.height",
+        }
+    }
+}
+
 impl fmt::Display for EventType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -240,6 +697,85 @@ impl FromStr for EventType {
     }
 }
 
+impl TryFrom<&str> for EventType {
+    type Error = Error;
+
+    fn try_from(s: &str) -> Result<Self> {
+        s.parse()
+    }
+}
+
+impl TryFrom<String> for EventType {
+    type Error = Error;
+
+    fn try_from(s: String) -> Result<Self> {
+        s.parse()
+    }
+}
+
+/// The operators which may be used in the filters passed to
+/// [`Query::try_from_filters`], each corresponding to a type of
+/// [`Condition`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    /// `=`
+    Eq,
+    /// `<`
+    Lt,
+    /// `<=`
+    Lte,
+    /// `>`
+    Gt,
+    /// `>=`
+    Gte,
+    /// `CONTAINS`
+    Contains,
+    /// `EXISTS`
+    Exists,
+    /// `!=` (local matching only)
+    NotEq,
+    /// `NOT CONTAINS` (local matching only)
+    NotContains,
+}
+
+impl fmt::Display for Op {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Op::Eq => "=",
+            Op::Lt => "<",
+            Op::Lte => "<=",
+            Op::Gt => ">",
+            Op::Gte => ">=",
+            Op::Contains => "CONTAINS",
+            Op::Exists => "EXISTS",
+            Op::NotEq => "!=",
+            Op::NotContains => "NOT CONTAINS",
+        })
+    }
+}
+
+impl FromStr for Op {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_uppercase().as_str() {
+            "=" => Ok(Op::Eq),
+            "<" => Ok(Op::Lt),
+            "<=" => Ok(Op::Lte),
+            ">" => Ok(Op::Gt),
+            ">=" => Ok(Op::Gte),
+            "CONTAINS" => Ok(Op::Contains),
+            "EXISTS" => Ok(Op::Exists),
+            "!=" => Ok(Op::NotEq),
+            "NOT CONTAINS" => Ok(Op::NotContains),
+            _ => Err(Error::invalid_params(&format!(
+                "unsupported query operator: {}",
+                s
+            ))),
+        }
+    }
+}
+
 /// The different types of conditions supported by a [`Query`].
 ///
 /// [`Query`]: struct.Query.html
@@ -259,6 +795,149 @@ pub enum Condition {
     Contains(String, String),
     /// Exists (to check if a key exists)
     Exists(String),
+    /// Not equal to (local matching only; not supported by the server)
+    NotEq(String, Operand),
+    /// Does not contain the given sub-string (local matching only; not
+    /// supported by the server)
+    NotContains(String, String),
+    /// A clause rendered verbatim, without escaping or validation (see
+    /// [`Query::and_raw`])
+    Raw(String),
+}
+
+impl Condition {
+    /// Whether this condition can only be evaluated locally, and would be
+    /// rejected by a Tendermint node.
+    pub fn is_local_only(&self) -> bool {
+        matches!(self, Condition::NotEq(_, _) | Condition::NotContains(_, _))
+    }
+
+    fn to_json(&self) -> Value {
+        let substring = |s: &String| (json!(s), json!("string"));
+        let (key, op, (value, value_type)) = match self {
+            Condition::Eq(key, op) => (key, Op::Eq, op.to_json()),
+            Condition::Lt(key, op) => (key, Op::Lt, op.to_json()),
+            Condition::Lte(key, op) => (key, Op::Lte, op.to_json()),
+            Condition::Gt(key, op) => (key, Op::Gt, op.to_json()),
+            Condition::Gte(key, op) => (key, Op::Gte, op.to_json()),
+            Condition::Contains(key, s) => (key, Op::Contains, substring(s)),
+            Condition::Exists(key) => (key, Op::Exists, (Value::Null, Value::Null)),
+            Condition::NotEq(key, op) => (key, Op::NotEq, op.to_json()),
+            Condition::NotContains(key, s) => (key, Op::NotContains, substring(s)),
+            Condition::Raw(clause) => {
+                return json!({ "key": null, "op": null, "value": clause, "type": "raw" })
+            }
+        };
+        json!({
+            "key": key,
+            "op": op.to_string(),
+            "value": value,
+            "type": value_type,
+        })
+    }
+
+    /// Whether this condition is the same as the given condition, but for
+    /// the numeric representation of its operand (see
+    /// [`Operand::equivalent`]).
+    fn equivalent(&self, other: &Condition) -> bool {
+        use Condition::*;
+
+        match (self, other) {
+            (Eq(k1, a), Eq(k2, b))
+            | (Lt(k1, a), Lt(k2, b))
+            | (Lte(k1, a), Lte(k2, b))
+            | (Gt(k1, a), Gt(k2, b))
+            | (Gte(k1, a), Gte(k2, b))
+            | (NotEq(k1, a), NotEq(k2, b)) => k1 == k2 && a.equivalent(b),
+            _ => self == other,
+        }
+    }
+
+    /// The key this condition applies to, unless it is a raw clause.
+    fn key(&self) -> Option<&str> {
+        match self {
+            Condition::Eq(key, _)
+            | Condition::Lt(key, _)
+            | Condition::Lte(key, _)
+            | Condition::Gt(key, _)
+            | Condition::Gte(key, _)
+            | Condition::Contains(key, _)
+            | Condition::Exists(key)
+            | Condition::NotEq(key, _)
+            | Condition::NotContains(key, _) => Some(key),
+            Condition::Raw(_) => None,
+        }
+    }
+
+    /// Whether every event satisfying this condition is known to satisfy the
+    /// given condition too.
+    fn implies(&self, other: &Condition) -> bool {
+        use Condition::*;
+        use Ordering::*;
+
+        if self == other {
+            return true;
+        }
+        if self.key().is_none() || self.key() != other.key() {
+            return false;
+        }
+        // Since both conditions apply to the same key, any value satisfying
+        // this condition is compared against the other's operand.
+        let cmp = |a: &Operand, b: &Operand| a.cmp_operand(b);
+        match (self, other) {
+            // Negated conditions also hold when the key is absent.
+            (NotEq(..), Exists(_)) | (NotContains(..), Exists(_)) => false,
+            (_, Exists(_)) => true,
+            (Eq(_, a), Eq(_, b)) => cmp(a, b) == Some(Equal),
+            (Eq(_, a), Lt(_, b)) | (Lte(_, a), Lt(_, b)) => cmp(a, b) == Some(Less),
+            (Eq(_, a), Lte(_, b)) | (Lt(_, a), Lt(_, b)) | (Lt(_, a), Lte(_, b)) => {
+                matches!(cmp(a, b), Some(Less) | Some(Equal))
+            }
+            (Lte(_, a), Lte(_, b)) => matches!(cmp(a, b), Some(Less) | Some(Equal)),
+            (Eq(_, a), Gt(_, b)) | (Gte(_, a), Gt(_, b)) => cmp(a, b) == Some(Greater),
+            (Eq(_, a), Gte(_, b)) | (Gt(_, a), Gt(_, b)) | (Gt(_, a), Gte(_, b)) => {
+                matches!(cmp(a, b), Some(Greater) | Some(Equal))
+            }
+            (Gte(_, a), Gte(_, b)) => matches!(cmp(a, b), Some(Greater) | Some(Equal)),
+            (Eq(_, Operand::String(a)), Contains(_, b)) | (Contains(_, a), Contains(_, b)) => {
+                a.contains(b.as_str())
+            }
+            // No value contains `a`, and so none contains (or equals) any
+            // string containing it.
+            (NotContains(_, a), NotContains(_, b))
+            | (NotContains(_, a), NotEq(_, Operand::String(b))) => b.contains(a.as_str()),
+            _ => false,
+        }
+    }
+
+    fn matches(&self, events: &HashMap<String, Vec<String>>) -> bool {
+        let key = match self.key() {
+            Some(key) => key,
+            // We cannot interpret raw clauses.
+            None => return false,
+        };
+        let values = events.get(key).map(Vec::as_slice).unwrap_or_default();
+        let any = |f: &dyn Fn(&str) -> bool| values.iter().any(|v| f(v));
+        match self {
+            Condition::Eq(_, op) => any(&|v| op.compare(v) == Some(Ordering::Equal)),
+            Condition::Lt(_, op) => any(&|v| op.compare(v) == Some(Ordering::Less)),
+            Condition::Lte(_, op) => {
+                any(&|v| matches!(op.compare(v), Some(Ordering::Less) | Some(Ordering::Equal)))
+            }
+            Condition::Gt(_, op) => any(&|v| op.compare(v) == Some(Ordering::Greater)),
+            Condition::Gte(_, op) => any(&|v| {
+                matches!(
+                    op.compare(v),
+                    Some(Ordering::Greater) | Some(Ordering::Equal)
+                )
+            }),
+            Condition::Contains(_, sub) => any(&|v| v.contains(sub.as_str())),
+            Condition::Exists(_) => events.contains_key(key),
+            Condition::NotEq(_, op) => !any(&|v| op.compare(v) == Some(Ordering::Equal)),
+            Condition::NotContains(_, sub) => !any(&|v| v.contains(sub.as_str())),
+            Condition::Raw(_) => false,
+        }
+    }
 }
 
 impl fmt::Display for Condition {
@@ -271,6 +950,11 @@ impl fmt::Display for Condition {
             Condition::Gte(key, op) => write!(f, "{} >= {}", key, op),
             Condition::Contains(key, op) => write!(f, "{} CONTAINS {}", key, escape(op)),
             Condition::Exists(key) => write!(f, "{} EXISTS", key),
+            Condition::NotEq(key, op) => write!(f, "{} != {}", key, op),
+            Condition::NotContains(key, op) => {
+                write!(f, "{} NOT CONTAINS {}", key, escape(op))
+            }
+            Condition::Raw(clause) => f.write_str(clause),
         }
     }
 }
@@ -281,6 +965,14 @@ impl fmt::Display for Condition {
 /// an operand can be a string, number, date or time. We differentiate here
 /// between integer and floating point numbers.
 ///
+/// Raw bytes (e.g. hashes and addresses) are matched as hex-encoded strings,
+/// which Tendermint renders in uppercase: see [`Operand::hex`], which is also
+/// used to convert from `&[u8]`.
+///
+/// Operands convert back into the native types they hold via `TryFrom`
+/// (e.g. `i64::try_from(operand)`), which fails if the operand holds a value
+/// of a different type, e.g. to inspect a parsed query's conditions.
+///
 /// [`Condition`]: enum.Condition.html
 /// [tm-subscribe]: https://docs.tendermint.com/master/rpc/#/Websocket/subscribe
 #[derive(Debug, Clone, PartialEq)]
@@ -306,6 +998,174 @@ impl fmt::Display for Operand {
     }
 }
 
+impl Operand {
+    /// A string operand holding the uppercase hex encoding of the given
+    /// bytes, as Tendermint renders hashes (e.g. `tx.hash`).
+    ///
+    /// String operands are compared exactly, so a hex string in the wrong
+    /// case silently matches nothing.
+    pub fn hex(bytes: impl AsRef<[u8]>) -> Self {
+        Operand::String(
+            bytes
+                .as_ref()
+                .iter()
+                .map(|b| format!("{:02X}", b))
+                .collect(),
+        )
+    }
+
+    /// A string operand holding the lowercase hex encoding of the given
+    /// bytes, for attributes which an application emits in lowercase.
+    pub fn hex_lower(bytes: impl AsRef<[u8]>) -> Self {
+        Operand::String(
+            bytes
+                .as_ref()
+                .iter()
+                .map(|b| format!("{:02x}", b))
+                .collect(),
+        )
+    }
+
+    /// This operand's value and type, as represented by
+    /// [`Query::to_conditions_json`].
+    fn to_json(&self) -> (Value, Value) {
+        match self {
+            Operand::String(s) => (json!(s), json!("string")),
+            Operand::Signed(i) => (json!(i), json!("number")),
+            Operand::Unsigned(u) => (json!(u), json!("number")),
+            Operand::Float(h) => (json!(h), json!("number")),
+            Operand::Date(d) => (json!(d.format("%Y-%m-%d").to_string()), json!("date")),
+            Operand::DateTime(dt) => (json!(dt.to_rfc3339()), json!("time")),
+        }
+    }
+
+    /// Whether this operand is a number.
+    fn is_number(&self) -> bool {
+        matches!(
+            self,
+            Operand::Signed(_) | Operand::Unsigned(_) | Operand::Float(_)
+        )
+    }
+
+    /// Whether this operand has the same value as the given operand.
+    ///
+    /// Unlike `==`, which also compares their representations, numbers are
+    /// compared by value, so that e.g. `Signed(5)`, `Unsigned(5)` and
+    /// `Float(5.0)` are all equivalent. Integers and floats are compared
+    /// exactly, rather than by converting the integer to a float, so that
+    /// e.g. `Unsigned(2^53 + 1)` is not equivalent to `Float(2^53)`, even
+    /// though converting the integer would round it to that float. As for
+    /// floats themselves, `NaN` is not equivalent to any operand (not even
+    /// itself), whereas `0.0` and `-0.0` are equivalent.
+    pub fn equivalent(&self, other: &Operand) -> bool {
+        self.cmp_operand(other) == Some(Ordering::Equal)
+    }
+
+    /// Compare this operand to the given operand of the same type, returning
+    /// `None` for operands of different types. Numbers are compared by value
+    /// regardless of their representation (see [`Operand::equivalent`]).
+    fn cmp_operand(&self, other: &Operand) -> Option<Ordering> {
+        match (self, other) {
+            (Operand::String(a), Operand::String(b)) => Some(a.cmp(b)),
+            (Operand::Date(a), Operand::Date(b)) => Some(a.cmp(b)),
+            (Operand::DateTime(a), Operand::DateTime(b)) => Some(a.cmp(b)),
+            (Operand::Float(a), Operand::Float(b)) => a.partial_cmp(b),
+            (Operand::Float(a), b) => cmp_integer_float(b.as_integer()?, *a).map(Ordering::reverse),
+            (a, Operand::Float(b)) => cmp_integer_float(a.as_integer()?, *b),
+            (a, b) => Some(a.as_integer()?.cmp(&b.as_integer()?)),
+        }
+    }
+
+    /// This operand's value, if it is an integer.
+    fn as_integer(&self) -> Option<i128> {
+        match self {
+            Operand::Signed(i) => Some(i128::from(*i)),
+            Operand::Unsigned(u) => Some(i128::from(*u)),
+            _ => None,
+        }
+    }
+
+    /// Compare the given event attribute value to this operand, returning
+    /// `None` if the value cannot be interpreted as the operand's type.
+    ///
+    /// Strings are compared lexicographically.
+    fn compare(&self, value: &str) -> Option<Ordering> {
+        match self {
+            Operand::String(s) => Some(value.cmp(s.as_str())),
+            Operand::Signed(i) => match value.parse::<i64>() {
+                Ok(v) => Some(v.cmp(i)),
+                Err(_) => value.parse::<f64>().ok()?.partial_cmp(&(*i as f64)),
+            },
+            Operand::Unsigned(u) => match value.parse::<u64>() {
+                Ok(v) => Some(v.cmp(u)),
+                Err(_) => value.parse::<f64>().ok()?.partial_cmp(&(*u as f64)),
+            },
+            Operand::Float(h) => value.parse::<f64>().ok()?.partial_cmp(h),
+            Operand::Date(d) => {
+                let v = match NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+                    Ok(v) => v,
+                    Err(_) => DateTime::parse_from_rfc3339(value)
+                        .ok()?
+                        .with_timezone(&Utc)
+                        .naive_utc()
+                        .date(),
+                };
+                Some(v.cmp(&d.naive_utc()))
+            }
+            Operand::DateTime(dt) => {
+                let v = DateTime::parse_from_rfc3339(value).ok()?;
+                Some(v.with_timezone(&Utc).cmp(dt))
+            }
+        }
+    }
+}
+
+/// Compare the given integer to the given float exactly.
+fn cmp_integer_float(i: i128, f: f64) -> Option<Ordering> {
+    // Integral floats (within range) convert to integers exactly. Any other
+    // finite float is smaller in magnitude than 2^52, and so converting the
+    // integer to a float instead cannot change the result.
+    if f.fract() == 0.0 && f.abs() < 2_f64.powi(127) {
+        Some(i.cmp(&(f as i128)))
+    } else {
+        (i as f64).partial_cmp(&f)
+    }
+}
+
+impl FromStr for Operand {
+    type Err = Error;
+
+    /// Parse an operand as rendered by its `Display` implementation.
+    ///
+    /// Strings must be single-quoted, with any backslashes and single quotes
+    /// within them escaped by a backslash. Numbers are parsed as the first of
+    /// `Unsigned`, `Signed` and `Float` that fits.
+    fn from_str(s: &str) -> Result<Self> {
+        if let Some(quoted) = s.strip_prefix('\'') {
+            return unescape(quoted).map(Operand::String);
+        }
+        if let Some(date) = s.strip_prefix("DATE ") {
+            return NaiveDate::parse_from_str(date, "%Y-%m-%d")
+                .map(|d| Operand::Date(Date::from_utc(d, Utc)))
+                .map_err(|e| Error::invalid_params(&format!("invalid date {}: {}", date, e)));
+        }
+        if let Some(time) = s.strip_prefix("TIME ") {
+            return DateTime::parse_from_rfc3339(time)
+                .map(|dt| Operand::DateTime(dt.with_timezone(&Utc)))
+                .map_err(|e| Error::invalid_params(&format!("invalid time {}: {}", time, e)));
+        }
+        if let Ok(u) = s.parse::<u64>() {
+            Ok(Operand::Unsigned(u))
+        } else if let Ok(i) = s.parse::<i64>() {
+            Ok(Operand::Signed(i))
+        } else if let Ok(h) = s.parse::<f64>() {
+            Ok(Operand::Float(h))
+        } else {
+            Err(Error::invalid_params(&format!("invalid operand: {}", s)))
+        }
+    }
+}
+
 impl From<String> for Operand {
     fn from(source: String) -> Self {
         Operand::String(source)
@@ -324,6 +1184,13 @@ impl From<&str> for Operand {
     }
 }
 
+impl From<&[u8]> for Operand {
+    /// Bytes are hex-encoded in uppercase (see [`Operand::hex`]).
+    fn from(source: &[u8]) -> Self {
+        Operand::hex(source)
+    }
+}
+
 impl From<i64> for Operand {
     fn from(source: i64) -> Self {
         Operand::Signed(source)
@@ -408,6 +1275,88 @@ impl From<DateTime<FixedOffset>> for Operand {
     }
 }
 
+impl TryFrom<Operand> for String {
+    type Error = Error;
+
+    fn try_from(operand: Operand) -> Result<Self> {
+        match operand {
+            Operand::String(s) => Ok(s),
+            other => Err(operand_mismatch("a string", &other)),
+        }
+    }
+}
+
+/// Unsigned operands are also converted, provided they are in range (as
+/// parsing produces an unsigned operand for any non-negative integer).
+impl TryFrom<Operand> for i64 {
+    type Error = Error;
+
+    fn try_from(operand: Operand) -> Result<Self> {
+        match operand {
+            Operand::Signed(i) => Ok(i),
+            Operand::Unsigned(u) if i64::try_from(u).is_ok() => Ok(u as i64),
+            other => Err(operand_mismatch("a signed integer", &other)),
+        }
+    }
+}
+
+/// Signed operands are also converted, provided they are not negative.
+impl TryFrom<Operand> for u64 {
+    type Error = Error;
+
+    fn try_from(operand: Operand) -> Result<Self> {
+        match operand {
+            Operand::Unsigned(u) => Ok(u),
+            Operand::Signed(i) if i >= 0 => Ok(i as u64),
+            other => Err(operand_mismatch("an unsigned integer", &other)),
+        }
+    }
+}
+
+/// Only float operands are converted, as integers may not be representable
+/// exactly as floats.
+impl TryFrom<Operand> for f64 {
+    type Error = Error;
+
+    fn try_from(operand: Operand) -> Result<Self> {
+        match operand {
+            Operand::Float(h) => Ok(h),
+            other => Err(operand_mismatch("a float", &other)),
+        }
+    }
+}
+
+impl TryFrom<Operand> for Date<Utc> {
+    type Error = Error;
+
+    fn try_from(operand: Operand) -> Result<Self> {
+        match operand {
+            Operand::Date(d) => Ok(d),
+            other => Err(operand_mismatch("a date", &other)),
+        }
+    }
+}
+
+impl TryFrom<Operand> for DateTime<Utc> {
+    type Error = Error;
+
+    fn try_from(operand: Operand) -> Result<Self> {
+        match operand {
+            Operand::DateTime(dt) => Ok(dt),
+            other => Err(operand_mismatch("a time", &other)),
+        }
+    }
+}
+
+/// The error converting the given operand into a native type other than that
+/// which it holds.
+fn operand_mismatch(expected: &str, operand: &Operand) -> Error {
+    Error::invalid_params(&format!(
+        "expected {} operand, but got {}",
+        expected, operand
+    ))
+}
+
 /// Escape backslashes and single quotes within the given string with a backslash.
 fn escape(s: &str) -> String {
     let mut result = String::new();
@@ -420,6 +1369,52 @@ fn escape(s: &str) -> String {
     format!("'{}'", result)
 }
 
+/// Whether the given key is permitted by the Tendermint query grammar, which
+/// accepts any non-empty sequence of characters other than whitespace,
+/// quotes, backslashes, parentheses and comparison operators.
+fn is_valid_key(key: &str) -> bool {
+    !key.is_empty()
+        && !key.contains(|c: char| {
+            c.is_whitespace() || matches!(c, '\'' | '"' | '\\' | '(' | ')' | '=' | '<' | '>')
+        })
+}
+
+/// The inverse of [`escape`], given its output without the opening quote.
+///
+/// Fails if the closing quote is missing or is followed by anything else, or
+/// if a backslash escapes anything other than a backslash or single quote.
+fn unescape(s: &str) -> Result<String> {
+    let mut result = String::new();
+    let mut chars = s.chars();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\\' => match chars.next() {
+                Some(escaped @ '\\') | Some(escaped @ '\'') => result.push(escaped),
+                Some(other) => {
+                    return Err(Error::invalid_params(&format!(
+                        "invalid escape sequence in string operand: \\{}",
+                        other
+                    )))
+                }
+                None => break,
+            },
+            '\'' => {
+                let rest = chars.as_str();
+                return if rest.is_empty() {
+                    Ok(result)
+                } else {
+                    Err(Error::invalid_params(&format!(
+                        "unexpected input after string operand: {}",
+                        rest
+                    )))
+                };
+            }
+            _ => result.push(ch),
+        }
+    }
+    Err(Error::invalid_params("unterminated string operand"))
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -440,6 +1435,52 @@ mod test {
         assert_eq!("tm.event = 'Tx'", query.to_string());
     }
 
+    #[test]
+    fn event_type_conversions() {
+        assert_eq!(
+            EventType::NewBlock,
+            EventType::try_from("NewBlock").unwrap()
+        );
+        assert_eq!(
+            EventType::Tx,
+            EventType::try_from("Tx".to_string()).unwrap()
+        );
+
+        let err = EventType::try_from("Vote").unwrap_err();
+        assert_eq!(
+            "Vote".parse::<EventType>().unwrap_err().to_string(),
+            err.to_string()
+        );
+        assert!(err.to_string().contains("unrecognized event type: Vote"));
+        assert!(EventType::try_from(String::new()).is_err());
+    }
+
+    #[test]
+    fn event_type_serde() {
+        for event_type in &[EventType::NewBlock, EventType::Tx] {
+            let json = serde_json::to_string(event_type).unwrap();
+            assert_eq!(format!("\"{}\"", event_type), json);
+            assert_eq!(
+                *event_type,
+                serde_json::from_str::<EventType>(&json).unwrap()
+            );
+        }
+
+        #[derive(Debug, PartialEq, Deserialize)]
+        struct Config {
+            subscriptions: Vec<EventType>,
+        }
+        let config: Config =
+            serde_json::from_str(r#"{"subscriptions": ["Tx", "NewBlock"]}"#).unwrap();
+        assert_eq!(
+            vec![EventType::Tx, EventType::NewBlock],
+            config.subscriptions
+        );
+
+        assert!(serde_json::from_str::<EventType>("\"Vote\"").is_err());
+        assert!(serde_json::from_str::<EventType>("\"newblock\"").is_err());
+    }
+
     #[test]
     fn simple_condition() {
         let query = Query::eq("key", "value");
@@ -500,6 +1541,712 @@ mod test {
         );
     }
 
+    #[test]
+    fn time_between() {
+        let start = DateTime::parse_from_rfc3339("2020-09-24T10:17:23-04:00")
+            .unwrap()
+            .with_timezone(&Utc);
+        let end = DateTime::parse_from_rfc3339("2020-09-25T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        let query = Query::from(EventType::Tx)
+            .and_time_between("tx.time", start, end, true)
+            .unwrap();
+        assert_eq!(
+            "tm.event = 'Tx' AND tx.time >= TIME 2020-09-24T14:17:23+00:00 \
+             AND tx.time <= TIME 2020-09-25T00:00:00+00:00",
+            query.to_string()
+        );
+
+        let query = Query::default()
+            .and_time_between("tx.time", start, end, false)
+            .unwrap();
+        assert_eq!(
+            "tx.time > TIME 2020-09-24T14:17:23+00:00 AND tx.time < TIME 2020-09-25T00:00:00+00:00",
+            query.to_string()
+        );
+
+        // An empty range is allowed, but a reversed one is not.
+        assert!(Query::default()
+            .and_time_between("tx.time", start, start, true)
+            .is_ok());
+        let err = Query::default()
+            .and_time_between("tx.time", end, start, true)
+            .unwrap_err();
+        assert!(err.to_string().contains("is later than end"));
+    }
+
+    #[test]
+    fn numeric_between() {
+        let query = Query::from(EventType::Tx)
+            .and_between("tx.height", 5_u64, 10_u64, true)
+            .unwrap();
+        assert_eq!(
+            "tm.event = 'Tx' AND tx.height >= 5 AND tx.height <= 10",
+            query.to_string()
+        );
+
+        let query = Query::default()
+            .and_between("fee.rate", -0.5, 0.5, false)
+            .unwrap();
+        assert_eq!("fee.rate > -0.5 AND fee.rate < 0.5", query.to_string());
+
+        // An empty range is allowed, but a reversed one is not.
+        assert!(Query::default()
+            .and_between("tx.height", 5_i64, 5_i64, false)
+            .is_ok());
+        let err = Query::default()
+            .and_between("tx.height", 10_u64, 5_u64, true)
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("low (10) is greater than high (5)"));
+
+        // Bounds must be comparable numbers.
+        assert!(Query::default()
+            .and_between("tx.height", 5_u64, 10.5, true)
+            .is_ok());
+        let err = Query::default()
+            .and_between("fee.rate", 0.5, f64::NAN, true)
+            .unwrap_err();
+        assert!(err.to_string().contains("cannot be compared"));
+        let err = Query::default()
+            .and_between("tx.height", "5", "10", true)
+            .unwrap_err();
+        assert!(err.to_string().contains("bounds must be numbers"));
+    }
+
+    #[test]
+    fn conditions_json() {
+        let query = Query::from(EventType::Tx)
+            .and_eq("transfer.sender", "AddrA")
+            .and_lt("tx.height", -1_i64)
+            .and_lte("tx.height", 100_u64)
+            .and_gt("fee.rate", 0.5)
+            .and_gte(
+                "account.created",
+                Date::from_utc(NaiveDate::from_ymd(2020, 9, 24), Utc),
+            )
+            .and_eq(
+                "tx.time",
+                DateTime::parse_from_rfc3339("2020-09-24T10:17:23-04:00").unwrap(),
+            )
+            .and_contains("message.memo", "it's")
+            .and_exists("message.action")
+            .and_not_eq("transfer.recipient", "AddrB")
+            .and_not_contains("message.module", "gov")
+            .and_raw("app.key MATCHES 'x'");
+
+        assert_eq!(
+            serde_json::json!([
+                { "key": "tm.event", "op": "=", "value": "Tx", "type": "string" },
+                { "key": "transfer.sender", "op": "=", "value": "AddrA", "type": "string" },
+                { "key": "tx.height", "op": "<", "value": -1, "type": "number" },
+                { "key": "tx.height", "op": "<=", "value": 100, "type": "number" },
+                { "key": "fee.rate", "op": ">", "value": 0.5, "type": "number" },
+                { "key": "account.created", "op": ">=", "value": "2020-09-24", "type": "date" },
+                { "key": "tx.time", "op": "=", "value": "2020-09-24T14:17:23+00:00", "type": "time" },
+                { "key": "message.memo", "op": "CONTAINS", "value": "it's", "type": "string" },
+                { "key": "message.action", "op": "EXISTS", "value": null, "type": null },
+                { "key": "transfer.recipient", "op": "!=", "value": "AddrB", "type": "string" },
+                { "key": "message.module", "op": "NOT CONTAINS", "value": "gov", "type": "string" },
+                { "key": null, "op": null, "value": "app.key MATCHES 'x'", "type": "raw" },
+            ]),
+            query.to_conditions_json()
+        );
+        assert_eq!(serde_json::json!([]), Query::default().to_conditions_json());
+    }
+
+    #[test]
+    fn normalization() {
+        let a = Query::from(EventType::Tx)
+            .and_eq("transfer.sender", "AddrA")
+            .and_gte("tx.height", 5_u64)
+            .and_exists("message.action")
+            .and_lte("tx.height", 10_u64);
+        let b = Query::from(EventType::Tx)
+            .and_lte("tx.height", 10_u64)
+            .and_exists("message.action")
+            .and_eq("transfer.sender", "AddrA")
+            .and_gte("tx.height", 5_u64)
+            .and_exists("message.action");
+        assert_ne!(a, b);
+        assert_ne!(a.to_string(), b.to_string());
+
+        let (a, b) = (a.normalize(), b.normalize());
+        assert_eq!(a, b);
+        assert_eq!(
+            "tm.event = 'Tx' AND message.action EXISTS AND transfer.sender = 'AddrA' \
+             AND tx.height <= 10 AND tx.height >= 5",
+            a.to_string()
+        );
+        assert_eq!(a.to_string(), b.to_string());
+
+        // Normalization is idempotent.
+        assert_eq!(a.clone(), a.normalize());
+    }
+
+    #[test]
+    fn numeric_operand_equivalence() {
+        assert!(Operand::Signed(5).equivalent(&Operand::Unsigned(5)));
+        assert!(Operand::Unsigned(5).equivalent(&Operand::Signed(5)));
+        assert!(Operand::Signed(5).equivalent(&Operand::Float(5.0)));
+        assert!(Operand::Float(5.0).equivalent(&Operand::Unsigned(5)));
+        assert!(Operand::Float(0.0).equivalent(&Operand::Float(-0.0)));
+        assert!(Operand::Signed(0).equivalent(&Operand::Float(-0.0)));
+        assert_ne!(Operand::Signed(5), Operand::Unsigned(5));
+
+        assert!(!Operand::Signed(5).equivalent(&Operand::Float(5.5)));
+        assert!(!Operand::Signed(-1).equivalent(&Operand::Unsigned(u64::MAX)));
+        assert!(!Operand::Unsigned((1 << 53) + 1).equivalent(&Operand::Float(2_f64.powi(53))));
+        assert!(!Operand::Float(f64::NAN).equivalent(&Operand::Float(f64::NAN)));
+        assert!(!Operand::Unsigned(5).equivalent(&Operand::String("5".to_string())));
+
+        // Numerically equal conditions are deduplicated by normalization.
+        let query = Query::default()
+            .and_eq("tx.height", 5_i64)
+            .and_eq("tx.height", 5_u64)
+            .and_eq("tx.height", 5.0)
+            .normalize();
+        assert_eq!(1, query.condition_count());
+        assert_eq!("tx.height = 5", query.to_string());
+    }
+
+    #[test]
+    fn subset_queries() {
+        let narrow = Query::from(EventType::Tx)
+            .and_eq("transfer.sender", "AddrA")
+            .and_eq("transfer.amount", 10_u64)
+            .and_gt("tx.height", 100_u64)
+            .and_contains("message.action", "delegate")
+            .and_not_contains("message.module", "staking");
+        for broad in &[
+            narrow.clone(),
+            Query::default(),
+            Query::from(EventType::Tx),
+            Query::from(EventType::Tx)
+                .and_exists("transfer.sender")
+                .and_exists("message.action"),
+            Query::from(EventType::Tx).and_exists("transfer.sender"),
+            Query::eq("tm.event", "Tx").and_contains("transfer.sender", "A"),
+            Query::from(EventType::Tx)
+                .and_gte("tx.height", 100_u64)
+                .and_gt("tx.height", 50_u64)
+                .and_lte("transfer.amount", 10_u64)
+                .and_lt("transfer.amount", 11_u64),
+            Query::from(EventType::Tx)
+                .and_contains("message.action", "leg")
+                .and_not_contains("message.module", "staking-v2")
+                .and_not_eq("message.module", "staking"),
+        ] {
+            assert!(
+                narrow.is_subset_of(broad),
+                "{} is not a subset of {}",
+                narrow,
+                broad
+            );
+        }
+    }
+
+    #[test]
+    fn non_subset_queries() {
+        let narrow = Query::from(EventType::Tx)
+            .and_eq("transfer.sender", "AddrA")
+            .and_gt("tx.height", 100_u64)
+            .and_not_eq("message.module", "staking");
+        for other in &[
+            Query::from(EventType::NewBlock),
+            Query::from(EventType::Tx).and_eq("transfer.sender", "AddrB"),
+            Query::from(EventType::Tx).and_exists("transfer.recipient"),
+            Query::from(EventType::Tx).and_gt("tx.height", 101_u64),
+            Query::from(EventType::Tx).and_lt("tx.height", 1000_u64),
+            // The key may be absent altogether.
+            Query::from(EventType::Tx).and_exists("message.module"),
+        ] {
+            assert!(
+                !narrow.is_subset_of(other),
+                "{} is a subset of {}",
+                narrow,
+                other
+            );
+        }
+        // Without an event type, any type of event may match.
+        assert!(!Query::eq("transfer.sender", "AddrA").is_subset_of(&Query::from(EventType::Tx)));
+    }
+
+    #[test]
+    fn undecidable_subset_queries() {
+        let query = Query::from(EventType::Tx)
+            .and_gte("tx.height", 5_u64)
+            .and_lte("tx.height", 5_u64)
+            .and_raw("transfer.amount > 10");
+        // The query is only a subset of these when its conditions are taken
+        // together, or when operands of different types are compared.
+        assert!(!query.is_subset_of(&Query::from(EventType::Tx).and_eq("tx.height", 5_u64)));
+        assert!(!query.is_subset_of(&Query::from(EventType::Tx).and_gt("tx.height", 4_i64)));
+        // Raw clauses are only known to be implied by identical clauses.
+        assert!(!query.is_subset_of(&Query::from(EventType::Tx).and_raw("transfer.amount > 5")));
+        assert!(query.is_subset_of(&Query::from(EventType::Tx).and_raw("transfer.amount > 10")));
+    }
+
+    #[test]
+    fn message_action_query() {
+        let query = Query::for_message_action("/cosmos.bank.v1beta1.MsgSend");
+        assert_eq!(
+            "tm.event = 'Tx' AND message.action = '/cosmos.bank.v1beta1.MsgSend'",
+            query.to_string()
+        );
+        assert_eq!(
+            "tm.event = 'Tx' AND message.action = '/cosmos.bank.v1beta1.MsgSend' \
+             AND transfer.sender = 'AddrA' AND tx.height > 5",
+            query
+                .and_eq("transfer.sender", "AddrA")
+                .and_gt("tx.height", 5_u64)
+                .to_string()
+        );
+        // Legacy action names (e.g. "send") are matched in the same way.
+        assert_eq!(
+            "tm.event = 'Tx' AND message.action = 'send'",
+            Query::for_message_action("send").to_string()
+        );
+    }
+
+    #[test]
+    fn prebuilt_conditions() {
+        let eq = Condition::Eq("transfer.sender".to_owned(), Operand::from("AddrA"));
+        let exists = Condition::Exists("message.action".to_owned());
+        let query = Query::from(EventType::Tx)
+            .and_condition(eq.clone())
+            .and_condition(exists.clone());
+        assert_eq!(
+            Query::from(EventType::Tx)
+                .and_eq("transfer.sender", "AddrA")
+                .and_exists("message.action"),
+            query
+        );
+        assert_eq!(
+            "tm.event = 'Tx' AND transfer.sender = 'AddrA' AND message.action EXISTS",
+            query.to_string()
+        );
+
+        let replaced = query.with_conditions(vec![exists, eq]);
+        assert_eq!(
+            "tm.event = 'Tx' AND message.action EXISTS AND transfer.sender = 'AddrA'",
+            replaced.to_string()
+        );
+        assert_eq!(
+            Query::from(EventType::Tx),
+            replaced.with_conditions(Vec::new())
+        );
+    }
+
+    #[test]
+    fn event_type_replacement() {
+        let base = Query::eq("transfer.sender", "AddrA").and_gte("tx.height", 5_u64);
+        assert_eq!(
+            "transfer.sender = 'AddrA' AND tx.height >= 5",
+            base.to_string()
+        );
+
+        let tx = base.clone().with_event_type(EventType::Tx);
+        assert_eq!(
+            "tm.event = 'Tx' AND transfer.sender = 'AddrA' AND tx.height >= 5",
+            tx.to_string()
+        );
+        let new_block = tx.with_event_type(EventType::NewBlock);
+        assert_eq!(
+            "tm.event = 'NewBlock' AND transfer.sender = 'AddrA' AND tx.height >= 5",
+            new_block.to_string()
+        );
+        assert_eq!(
+            Query::from(EventType::Tx).and_eq("transfer.sender", "AddrA"),
+            Query::from(EventType::NewBlock)
+                .and_eq("transfer.sender", "AddrA")
+                .with_event_type(EventType::Tx)
+        );
+
+        let cleared = new_block.without_event_type();
+        assert_eq!(base, cleared);
+        assert_eq!(base.to_string(), cleared.to_string());
+        assert_eq!(
+            Query::default(),
+            Query::from(EventType::Tx).without_event_type()
+        );
+    }
+
+    #[test]
+    fn rendering_is_cached() {
+        let query = Query::from(EventType::Tx).and_eq("tx.height", 3_i64);
+        let first = query.as_str();
+        let second = query.as_str();
+        assert_eq!("tm.event = 'Tx' AND tx.height = 3", first);
+        // The second call must reuse the first rendering.
+        assert!(std::ptr::eq(first, second));
+        assert_eq!(String::from(&query), query.to_string());
+
+        // Modifying the query must discard the cached rendering.
+        let query = query.and_exists("transfer.sender");
+        assert_eq!(
+            "tm.event = 'Tx' AND tx.height = 3 AND transfer.sender EXISTS",
+            query.as_str()
+        );
+    }
+
+    fn events(pairs: &[(&str, &str)]) -> HashMap<String, Vec<String>> {
+        let mut events: HashMap<String, Vec<String>> = HashMap::new();
+        for (key, value) in pairs {
+            events
+                .entry(key.to_string())
+                .or_default()
+                .push(value.to_string());
+        }
+        events
+    }
+
+    #[test]
+    fn local_matching() {
+        let events = events(&[
+            ("tm.event", "Tx"),
+            ("tx.height", "42"),
+            ("transfer.sender", "AddrA"),
+            ("transfer.sender", "AddrB"),
+            ("message.action", "send"),
+        ]);
+
+        assert!(Query::default().matches(&events));
+        assert!(Query::from(EventType::Tx).matches(&events));
+        assert!(!Query::from(EventType::NewBlock).matches(&events));
+        assert!(Query::eq("transfer.sender", "AddrB").matches(&events));
+        assert!(Query::gte("tx.height", 42_u64).matches(&events));
+        assert!(Query::lt("tx.height", 100_i64).matches(&events));
+        assert!(!Query::gt("tx.height", 42.5).matches(&events));
+        assert!(Query::contains("message.action", "en").matches(&events));
+        assert!(Query::exists("message.action").matches(&events));
+        assert!(!Query::exists("message.module").matches(&events));
+        assert!(!Query::eq("message.module", "bank").matches(&events));
+    }
+
+    #[test]
+    fn local_only_conditions() {
+        let events = events(&[
+            ("tm.event", "Tx"),
+            ("transfer.sender", "AddrA"),
+            ("message.action", "send"),
+        ]);
+
+        let query = Query::from(EventType::Tx).and_not_eq("transfer.sender", "AddrB");
+        assert!(query.matches(&events));
+        let query = Query::from(EventType::Tx).and_not_eq("transfer.sender", "AddrA");
+        assert!(!query.matches(&events));
+        // A missing key is never equal to the value.
+        assert!(Query::default()
+            .and_not_eq("message.module", "bank")
+            .matches(&events));
+
+        assert!(Query::default()
+            .and_not_contains("message.action", "delegate")
+            .matches(&events));
+        assert!(!Query::default()
+            .and_not_contains("message.action", "se")
+            .matches(&events));
+
+        assert_eq!(
+            "tm.event = 'Tx' AND a != 1 AND b NOT CONTAINS 'x'",
+            Query::from(EventType::Tx)
+                .and_not_eq("a", 1_u64)
+                .and_not_contains("b", "x")
+                .to_string()
+        );
+    }
+
+    fn read_event(path: &str) -> Event {
+        use crate::Response;
+        Event::from_string(std::fs::read_to_string(path).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn local_matching_of_new_block_events() {
+        let mut event = read_event("tests/support/event_new_block_1.json");
+
+        assert!(Query::from(EventType::NewBlock).matches_event(&event));
+        assert!(!Query::from(EventType::Tx).matches_event(&event));
+        // The event carries no block height attribute, so it is derived from
+        // the block itself.
+        let query = Query::from(EventType::NewBlock).and_gte("block.height", 1600_u64);
+        assert!(query.matches_event(&event));
+        let query = Query::from(EventType::NewBlock).and_lt("block.height", 1600_u64);
+        assert!(!query.matches_event(&event));
+
+        event.events = None;
+        assert!(Query::from(EventType::NewBlock)
+            .and_eq("block.height", 1608_u64)
+            .matches_event(&event));
+    }
+
+    #[test]
+    fn local_matching_of_tx_events() {
+        let mut event = read_event("tests/kvstore_fixtures/incoming/subscribe_txs_0.json");
+
+        assert!(Query::from(EventType::Tx).matches_event(&event));
+        assert!(!Query::from(EventType::NewBlock).matches_event(&event));
+        assert!(Query::from(EventType::Tx)
+            .and_eq("app.key", "tx0")
+            .and_gte("tx.height", 23_u64)
+            .matches_event(&event));
+        assert!(!Query::from(EventType::Tx)
+            .and_eq("app.key", "tx1")
+            .matches_event(&event));
+        assert!(!Query::from(EventType::Tx)
+            .and_not_eq("app.creator", "Cosmoshi Netowoko")
+            .matches_event(&event));
+
+        event.events = None;
+        assert!(Query::from(EventType::Tx)
+            .and_eq("tx.height", 23_u64)
+            .matches_event(&event));
+        assert!(!Query::from(EventType::Tx)
+            .and_exists("app.key")
+            .matches_event(&event));
+    }
+
+    #[test]
+    fn local_only_conditions_are_rejected_for_server() {
+        let query = Query::from(EventType::Tx).and_eq("tx.height", 3_i64);
+        assert!(query.validate_for_server().is_ok());
+
+        let err = query
+            .clone()
+            .and_not_eq("transfer.sender", "AddrA")
+            .validate_for_server()
+            .unwrap_err();
+        assert!(err.to_string().contains("transfer.sender != 'AddrA'"));
+
+        assert!(query
+            .and_not_contains("message.action", "send")
+            .validate_for_server()
+            .is_err());
+    }
+
+    #[test]
+    fn condition_count_is_limited() {
+        assert_eq!(0, Query::default().condition_count());
+        let query = (0..63).fold(Query::from(EventType::Tx), |q, i| {
+            q.and_eq("transfer.sender", format!("addr{}", i))
+        });
+        assert_eq!(64, query.condition_count());
+        assert!(query.validate_for_server_with_max(Some(64)).is_ok());
+
+        // There is no limit unless one is given.
+        let query = query.and_exists("message.action");
+        assert!(query.validate_for_server().is_ok());
+        assert!(query.validate_for_server_with_max(None).is_ok());
+        let err = query.validate_for_server_with_max(Some(64)).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("query has 65 conditions, exceeding the maximum of 64"),
+            "unexpected error: {}",
+            err
+        );
+
+        // Other limits can be checked explicitly.
+        assert!(query.validate_condition_count(100).is_ok());
+        assert!(Query::eq("app.key", "value")
+            .and_exists("app.other")
+            .validate_condition_count(1)
+            .is_err());
+    }
+
+    #[test]
+    fn operand_rendering() {
+        let cases = vec![
+            (Operand::from(""), "''"),
+            (Operand::from("plain"), "'plain'"),
+            (Operand::from("it's"), r"'it\'s'"),
+            (Operand::from(r"back\slash"), r"'back\\slash'"),
+            (Operand::from(r"\'"), r"'\\\''"),
+            (
+                Operand::from("' OR tm.event = 'Tx"),
+                r"'\' OR tm.event = \'Tx'",
+            ),
+            (Operand::from("ünïcødé ✓"), "'ünïcødé ✓'"),
+            (Operand::from(-42_i64), "-42"),
+            (Operand::from(42_u64), "42"),
+            (Operand::from(4.5_f64), "4.5"),
+        ];
+        for (operand, expected) in cases {
+            assert_eq!(expected, operand.to_string());
+            assert_eq!(operand, expected.parse::<Operand>().unwrap());
+        }
+    }
+
+    #[test]
+    fn hex_operands() {
+        let bytes: &[u8] = &[0x00, 0x9f, 0xab, 0x10];
+        assert_eq!("'009FAB10'", Operand::from(bytes).to_string());
+        assert_eq!(
+            "'009FAB10'",
+            Operand::hex(vec![0x00, 0x9f, 0xab, 0x10]).to_string()
+        );
+        assert_eq!("'009fab10'", Operand::hex_lower(bytes).to_string());
+        assert_eq!("''", Operand::hex(b"").to_string());
+        assert_eq!(
+            "tm.event = 'Tx' AND tx.hash = '009FAB10'",
+            Query::from(EventType::Tx)
+                .and_eq("tx.hash", bytes)
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn operand_extraction() {
+        let parse = |s: &str| s.parse::<Operand>().unwrap();
+        assert_eq!("it's", String::try_from(parse(r"'it\'s'")).unwrap());
+        assert_eq!(-42_i64, i64::try_from(parse("-42")).unwrap());
+        assert_eq!(42_i64, i64::try_from(parse("42")).unwrap());
+        assert_eq!(42_u64, u64::try_from(parse("42")).unwrap());
+        assert_eq!(42_u64, u64::try_from(Operand::Signed(42)).unwrap());
+        assert_eq!(4.5_f64, f64::try_from(parse("4.5")).unwrap());
+        assert_eq!(
+            Date::from_utc(NaiveDate::from_ymd(2020, 9, 24), Utc),
+            Date::<Utc>::try_from(parse("DATE 2020-09-24")).unwrap()
+        );
+        assert_eq!(
+            DateTime::parse_from_rfc3339("2020-09-24T10:17:23-04:00").unwrap(),
+            DateTime::<Utc>::try_from(parse("TIME 2020-09-24T14:17:23+00:00")).unwrap()
+        );
+    }
+
+    #[test]
+    fn operand_extraction_mismatches() {
+        let err = i64::try_from(Operand::from("42")).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("expected a signed integer operand, but got '42'"),
+            "unexpected error: {}",
+            err
+        );
+        assert!(i64::try_from(Operand::Unsigned(u64::MAX)).is_err());
+        assert!(u64::try_from(Operand::Signed(-1)).is_err());
+        assert!(f64::try_from(Operand::Unsigned(1)).is_err());
+        assert!(String::try_from(Operand::Float(1.5)).is_err());
+        assert!(Date::<Utc>::try_from(Operand::from(Utc::now())).is_err());
+        assert!(DateTime::<Utc>::try_from(Operand::from(Utc::today())).is_err());
+    }
+
+    #[test]
+    fn operand_parsing_rejects_malformed_strings() {
+        // Unterminated, or with an unescaped quote terminating it early.
+        for invalid in &["'abc", r"'abc\'", "'abc' OR 'x'", r"'a\b'", "abc"] {
+            assert!(invalid.parse::<Operand>().is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn height_range_query() {
+        let query = Query::for_type_height_range(EventType::NewBlock, 10, 20);
+        assert_eq!(
+            "tm.event = 'NewBlock' AND block.height >= 10 AND block.height <= 20",
+            query.to_string()
+        );
+
+        let query = Query::for_type_height_range(EventType::Tx, 10, 20);
+        assert_eq!(
+            "tm.event = 'Tx' AND tx.height >= 10 AND tx.height <= 20",
+            query.to_string()
+        );
+    }
+
+    #[test]
+    fn param_forms() {
+        let query = Query::from(EventType::Tx).and_eq("transfer.sender", "it's");
+        assert_eq!(query.to_string(), query.to_param());
+        assert_eq!(
+            "%22tm.event+%3D+%27Tx%27+AND+transfer.sender+%3D+%27it%5C%27s%27%22",
+            query.to_url_param()
+        );
+    }
+
+    #[test]
+    fn raw_clause() {
+        let query = Query::from(EventType::Tx)
+            .and_raw("tx.fee MATCHES '^[0-9]+uatom$'")
+            .and_eq("transfer.sender", "AddrA");
+        assert_eq!(
+            "tm.event = 'Tx' AND tx.fee MATCHES '^[0-9]+uatom$' AND transfer.sender = 'AddrA'",
+            query.to_string()
+        );
+        assert_eq!(
+            "tx.fee MATCHES '^[0-9]+uatom$'",
+            Query::default()
+                .and_raw("tx.fee MATCHES '^[0-9]+uatom$'")
+                .to_string()
+        );
+        assert!(query.validate_for_server().is_ok());
+
+        let mut events = HashMap::new();
+        events.insert("tm.event".to_string(), vec!["Tx".to_string()]);
+        events.insert("transfer.sender".to_string(), vec!["AddrA".to_string()]);
+        assert!(!query.matches(&events));
+    }
+
+    #[test]
+    fn query_from_filters() {
+        let filters = vec![
+            (
+                "transfer.sender".to_string(),
+                "=".parse().unwrap(),
+                Operand::from("AddrA"),
+            ),
+            (
+                "tx.height".to_string(),
+                ">=".parse().unwrap(),
+                Operand::from(100_u64),
+            ),
+            (
+                "message.action".to_string(),
+                "contains".parse().unwrap(),
+                Operand::from("send"),
+            ),
+            ("fee.amount".to_string(), Op::Exists, Operand::from("")),
+        ];
+        let query = Query::try_from_filters(Some(EventType::Tx), &filters).unwrap();
+        assert_eq!(
+            "tm.event = 'Tx' AND transfer.sender = 'AddrA' AND tx.height >= 100 \
+             AND message.action CONTAINS 'send' AND fee.amount EXISTS",
+            query.to_string()
+        );
+        assert_eq!(
+            Query::eq("a.b", 1_u64),
+            Query::try_from_filters(None, &[("a.b".to_string(), Op::Eq, Operand::from(1_u64))])
+                .unwrap()
+        );
+
+        // Composite keys may have any number of segments.
+        for key in &["a.b.c", "coin_received.receiver", "wasm-swap.pool_id.denom"] {
+            let query =
+                Query::try_from_filters(None, &[(key.to_string(), Op::Exists, Operand::from(""))])
+                    .unwrap();
+            assert_eq!(format!("{} EXISTS", key), query.to_string());
+        }
+    }
+
+    #[test]
+    fn query_from_invalid_filters() {
+        assert!("LIKE".parse::<Op>().is_err());
+        assert!("=~".parse::<Op>().is_err());
+
+        let invalid = |key: &str, op: Op, value: Operand| {
+            Query::try_from_filters(None, &[(key.to_string(), op, value)]).unwrap_err()
+        };
+        invalid("tx.height", Op::Contains, Operand::from(100_u64));
+        invalid("", Op::Eq, Operand::from(1_u64));
+        invalid("tx.height' OR 'a", Op::Eq, Operand::from(1_u64));
+        invalid("coin received.receiver", Op::Eq, Operand::from(1_u64));
+        invalid("tx.height>=1", Op::Exists, Operand::from(""));
+        invalid("(tx.height)", Op::Exists, Operand::from(""));
+    }
+
     #[test]
     fn complex_query() {
         let query = Query::from(EventType::Tx).and_eq("tx.height", 3_i64);
@@ -1,6 +1,6 @@
 //! JSON-RPC requests
 
-use super::{Id, Method, Version};
+use super::{CompatMode, Id, Method, Version};
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use std::fmt::Debug;
 
@@ -16,6 +16,29 @@ pub trait Request: Debug + DeserializeOwned + Serialize + Sized + Send {
     fn into_json(self) -> String {
         Wrapper::new(self).into_json()
     }
+
+    /// Serialize this request as JSON, in the form expected by nodes speaking
+    /// the given version of the RPC protocol.
+    fn into_json_compat(self, compat: CompatMode) -> String {
+        Wrapper::new(self).into_json_compat(compat)
+    }
+
+    /// Serialize this request as JSON, in the form expected by nodes speaking
+    /// the given version of the RPC protocol, pretty-printed if `pretty` is
+    /// set.
+    fn into_json_formatted(self, compat: CompatMode, pretty: bool) -> String {
+        Wrapper::new(self).into_json_formatted(compat, pretty)
+    }
+
+    /// This request's parameters, in the form expected by nodes speaking the
+    /// given version of the RPC protocol.
+    ///
+    /// Defaults to the request's own serialization, which is appropriate for
+    /// requests encoded identically across versions.
+    fn params_compat(&self, compat: CompatMode) -> serde_json::Value {
+        let _ = compat;
+        serde_json::to_value(self).unwrap()
+    }
 }
 
 /// Simple JSON-RPC requests which correlate with a single response from the
@@ -74,7 +97,58 @@ where
         &self.params
     }
 
+    /// Serialize this request as compact JSON.
     pub fn into_json(self) -> String {
-        serde_json::to_string_pretty(&self).unwrap()
+        serde_json::to_string(&self).unwrap()
+    }
+
+    /// Serialize this request as compact JSON, encoding its parameters in the
+    /// form expected by nodes speaking the given version of the RPC protocol.
+    pub fn into_json_compat(self, compat: CompatMode) -> String {
+        self.into_json_formatted(compat, false)
+    }
+
+    /// Serialize this request as JSON, encoding its parameters in the form
+    /// expected by nodes speaking the given version of the RPC protocol.
+    ///
+    /// The JSON is pretty-printed if `pretty` is set, which is useful when
+    /// inspecting requests, and compact otherwise.
+    pub fn into_json_formatted(self, compat: CompatMode, pretty: bool) -> String {
+        let wrapper = Wrapper {
+            params: self.params.params_compat(compat),
+            jsonrpc: self.jsonrpc,
+            id: self.id,
+            method: self.method,
+        };
+        if pretty {
+            serde_json::to_string_pretty(&wrapper).unwrap()
+        } else {
+            serde_json::to_string(&wrapper).unwrap()
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::endpoint::abci_query;
+
+    #[test]
+    fn request_json_formatting() {
+        let request = || {
+            Wrapper::new_with_id(
+                Id::Num(1),
+                abci_query::Request::new(None, b"key".to_vec(), None, true),
+            )
+        };
+        let compact = request().into_json_formatted(CompatMode::V0_34, false);
+        let pretty = request().into_json_formatted(CompatMode::V0_34, true);
+        assert_ne!(compact, pretty);
+        assert!(!compact.contains('\n'));
+        assert!(pretty.contains('\n'));
+        assert_eq!(compact, request().into_json_compat(CompatMode::V0_34));
+
+        let parse = |json: &str| serde_json::from_str::<serde_json::Value>(json).unwrap();
+        assert_eq!(parse(&compact), parse(&pretty));
     }
 }
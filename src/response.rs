@@ -1,25 +1,182 @@
 //! JSON-RPC response types
 
 use super::{Error, Id, Version};
-use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Deserializer, Serialize};
+use serde_json::{Map, Value};
+use std::fmt::Display;
 use std::io::Read;
 
 /// JSON-RPC responses
 pub trait Response: Serialize + DeserializeOwned + Sized {
     /// Parse a JSON-RPC response from a JSON string
+    ///
+    /// If a field fails to deserialize, the resulting error includes the path
+    /// to that field (e.g. `result.validators[3].pub_key`). A response which
+    /// is not valid UTF-8 (e.g. a truncated or binary body) is rejected up
+    /// front with a distinct error.
+    ///
+    /// With the `simd-json` feature enabled, the response is parsed using
+    /// [`simd-json`](https://docs.rs/simd-json) rather than `serde_json`.
     fn from_string(response: impl AsRef<[u8]>) -> Result<Self, Error> {
-        let wrapper: Wrapper<Self> =
-            serde_json::from_slice(response.as_ref()).map_err(Error::parse_error)?;
+        let wrapper: Wrapper<Self> = parse(response.as_ref())?;
         wrapper.into_result()
     }
 
+    /// Parse a JSON-RPC response from a JSON string, rejecting responses which
+    /// contain both a `result` and an `error` (see
+    /// [`Wrapper::into_result_strict`]).
+    fn from_string_strict(response: impl AsRef<[u8]>) -> Result<Self, Error> {
+        let wrapper: Wrapper<Self> = parse(response.as_ref())?;
+        wrapper.into_result_strict()
+    }
+
     /// Parse a JSON-RPC response from an `io::Reader`
+    ///
+    /// The whole response is read into memory before being parsed, so that it
+    /// is checked in the same way as by [`Response::from_string`].
     fn from_reader(reader: impl Read) -> Result<Self, Error> {
-        let wrapper: Wrapper<Self> = serde_json::from_reader(reader).map_err(Error::parse_error)?;
+        let wrapper: Wrapper<Self> = parse_reader(reader)?;
         wrapper.into_result()
     }
 }
 
+/// Reject non-UTF-8 input up front, so that it can be told apart from
+/// structurally invalid JSON.
+fn ensure_utf8(json: &[u8]) -> Result<&str, Error> {
+    std::str::from_utf8(json)
+        .map_err(|e| Error::parse_error(format!("response body is not valid UTF-8: {}", e)))
+}
+
+/// Parse the given JSON using `serde_json`.
+#[cfg(not(feature = "simd-json"))]
+fn parse<T: DeserializeOwned>(json: &[u8]) -> Result<T, Error> {
+    parse_json(json)
+}
+
+#[cfg(not(feature = "simd-json"))]
+fn parse_reader<T: DeserializeOwned>(reader: impl Read) -> Result<T, Error> {
+    parse_json_reader(reader)
+}
+
+/// Parse the given JSON using `serde_json`, even if the `simd-json` feature
+/// is enabled, e.g. for responses which borrow parts of it as a
+/// [`serde_json::value::RawValue`].
+pub(crate) fn parse_json<T: DeserializeOwned>(json: &[u8]) -> Result<T, Error> {
+    let mut deserializer = serde_json::Deserializer::from_str(ensure_utf8(json)?);
+    let value = deserialize(&mut deserializer)?;
+    // Reject anything (other than whitespace) following the value.
+    deserializer.end().map_err(Error::parse_error)?;
+    Ok(value)
+}
+
+/// Parse JSON from the given reader using `serde_json` (see [`parse_json`]).
+pub(crate) fn parse_json_reader<T: DeserializeOwned>(reader: impl Read) -> Result<T, Error> {
+    parse_json(&read_all(reader)?)
+}
+
+/// Read the whole of the given response into memory.
+fn read_all(mut reader: impl Read) -> Result<Vec<u8>, Error> {
+    let mut json = Vec::new();
+    reader
+        .read_to_end(&mut json)
+        .map_err(|e| Error::parse_error(format!("failed to read response: {}", e)))?;
+    Ok(json)
+}
+
+/// Parse the given JSON using `simd-json`, which parses in place and so
+/// requires a mutable copy of the input.
+#[cfg(feature = "simd-json")]
+fn parse<T: DeserializeOwned>(json: &[u8]) -> Result<T, Error> {
+    let mut json = ensure_utf8(json)?.as_bytes().to_vec();
+    // Unlike `serde_json`, `simd-json` indexes the whole input up front, and
+    // so rejects anything (other than whitespace) following the value here.
+    let mut deserializer =
+        simd_json::Deserializer::from_slice(&mut json).map_err(Error::parse_error)?;
+    deserialize(&mut deserializer)
+}
+
+#[cfg(feature = "simd-json")]
+fn parse_reader<T: DeserializeOwned>(reader: impl Read) -> Result<T, Error> {
+    parse(&read_all(reader)?)
+}
+
+/// Deserialize the given JSON, tracking the path to the field being
+/// deserialized so that it can be reported if deserialization fails.
+fn deserialize<'de, T, D>(deserializer: D) -> Result<T, Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+    D::Error: Display,
+{
+    serde_path_to_error::deserialize(deserializer).map_err(|e| {
+        if e.path().iter().next().is_none() {
+            Error::parse_error(e.inner())
+        } else {
+            Error::parse_error(format!("{}: {}", e.path(), e.inner()))
+        }
+    })
+}
+
+/// An error encountered while leniently parsing a single top-level field of a
+/// response's `result`.
+#[derive(Debug, Clone)]
+pub struct FieldError {
+    /// Name of the field which failed to parse
+    pub field: &'static str,
+
+    /// Why the field failed to parse
+    pub error: Error,
+}
+
+/// The top-level fields of a response's `result`, parsed one at a time so
+/// that a single malformed field does not prevent the others from being
+/// parsed.
+#[derive(Debug, Default)]
+pub(crate) struct LenientFields {
+    fields: Map<String, Value>,
+    errors: Vec<FieldError>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct ResultFields(Map<String, Value>);
+
+impl Response for ResultFields {}
+
+impl LenientFields {
+    /// Parse the JSON-RPC envelope of the given response, deferring parsing
+    /// of the individual fields of its `result`.
+    ///
+    /// The envelope itself (and the `result` being a JSON object) is still
+    /// required to be well-formed.
+    pub(crate) fn from_string(response: impl AsRef<[u8]>) -> Result<Self, Error> {
+        Ok(Self {
+            fields: ResultFields::from_string(response)?.0,
+            errors: Vec::new(),
+        })
+    }
+
+    /// Parse the given field, recording an error (and returning `None`) if it
+    /// is missing or malformed.
+    pub(crate) fn take<T: DeserializeOwned>(&mut self, field: &'static str) -> Option<T> {
+        let result = match self.fields.remove(field) {
+            Some(value) => deserialize(value),
+            None => Err(Error::parse_error(format!("missing field `{}`", field))),
+        };
+        match result {
+            Ok(value) => Some(value),
+            Err(error) => {
+                self.errors.push(FieldError { field, error });
+                None
+            }
+        }
+    }
+
+    /// The errors recorded so far.
+    pub(crate) fn into_errors(self) -> Vec<FieldError> {
+        self.errors
+    }
+}
+
 /// JSON-RPC response wrapper (i.e. message envelope)
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct Wrapper<R> {
@@ -40,22 +197,47 @@ impl<R> Wrapper<R>
 where
     R: Response,
 {
+    /// Parse a JSON-RPC response from a JSON string, retaining its envelope
+    /// rather than converting it into a result (see
+    /// [`Response::from_string`]).
+    pub fn from_string(response: impl AsRef<[u8]>) -> Result<Self, Error> {
+        parse(response.as_ref())
+    }
+
     /// Get JSON-RPC version
     pub fn version(&self) -> &Version {
         &self.jsonrpc
     }
 
     /// Get JSON-RPC ID
-    #[allow(dead_code)]
     pub fn id(&self) -> &Id {
         &self.id
     }
 
+    /// Get the result, if the request was successful
+    pub fn result(&self) -> Option<&R> {
+        self.result.as_ref()
+    }
+
+    /// Get the error, if the request failed
+    pub fn error(&self) -> Option<&Error> {
+        self.error.as_ref()
+    }
+
     /// Convert this wrapper into a result type
+    ///
+    /// A response containing both a `result` and an `error` violates the
+    /// JSON-RPC specification (and usually indicates misbehaving middleware).
+    /// Such a response is treated as an error, and a warning is logged (if the
+    /// `tracing` dependency is enabled). See [`Wrapper::into_result_strict`]
+    /// to reject such responses outright.
     pub fn into_result(self) -> Result<R, Error> {
         // Ensure we're using a supported RPC version
         self.version().ensure_supported()?;
 
+        #[cfg(feature = "tracing")]
+        self.warn_if_ambiguous();
+
         if let Some(error) = self.error {
             Err(error)
         } else if let Some(result) = self.result {
@@ -67,6 +249,30 @@ where
         }
     }
 
+    /// Convert this wrapper into a result type, returning a distinct error if
+    /// it contains both a `result` and an `error`, rather than preferring the
+    /// `error` as [`Wrapper::into_result`] does.
+    pub fn into_result_strict(self) -> Result<R, Error> {
+        if self.result.is_some() && self.error.is_some() {
+            self.version().ensure_supported()?;
+            return Err(Error::server_error(
+                "server returned malformatted JSON (both 'result' and 'error')",
+            ));
+        }
+        self.into_result()
+    }
+
+    #[cfg(feature = "tracing")]
+    fn warn_if_ambiguous(&self) {
+        if self.result.is_some() && self.error.is_some() {
+            tracing::warn!(
+                "server returned both a 'result' and an 'error' for request {}; \
+                 treating it as an error",
+                self.id
+            );
+        }
+    }
+
     #[cfg(test)]
     pub fn new_with_id(id: Id, result: Option<R>, error: Option<Error>) -> Self {
         Self {
@@ -77,3 +283,38 @@ where
         }
     }
 }
+
+#[cfg(all(test, feature = "simd-json"))]
+mod test {
+    use super::*;
+    use crate::endpoint::{
+        block, block_results, blockchain, genesis, net_info, status, tx_search, validators,
+    };
+    use crate::test_support::fixture;
+
+    // Parses the given fixture with both backends, comparing the results.
+    fn assert_backends_agree<R: Response>(name: &str) {
+        let json = fixture(name);
+        let expected: Wrapper<R> = serde_json::from_str(&json).unwrap();
+        let actual: Wrapper<R> = parse(json.as_bytes()).unwrap();
+        assert_eq!(
+            serde_json::to_value(&expected).unwrap(),
+            serde_json::to_value(&actual).unwrap(),
+            "backends disagree on {}",
+            name
+        );
+    }
+
+    #[test]
+    fn simd_json_parses_identically_to_serde_json() {
+        assert_backends_agree::<block::Response>("block");
+        assert_backends_agree::<block::Response>("block_with_evidences");
+        assert_backends_agree::<block_results::Response>("block_results");
+        assert_backends_agree::<blockchain::Response>("blockchain");
+        assert_backends_agree::<genesis::Response>("genesis");
+        assert_backends_agree::<net_info::Response>("net_info");
+        assert_backends_agree::<status::Response>("status");
+        assert_backends_agree::<tx_search::Response>("tx_search_with_prove");
+        assert_backends_agree::<validators::Response>("validators");
+    }
+}
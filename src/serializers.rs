@@ -0,0 +1,125 @@
+//! Serde helpers for fields which nodes encode differently across versions.
+
+use serde::de::{DeserializeOwned, IntoDeserializer};
+use serde::{Deserialize, Deserializer};
+use tendermint::{account, validator, vote, PublicKey};
+
+/// Serialize and deserialize integers which may be encoded either as JSON
+/// strings (as by Tendermint's Go implementation) or as JSON numbers.
+///
+/// Values are always serialized as strings.
+///
+/// ## Examples
+///
+/// ```rust
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Counts {
+///     #[serde(with = "tendermint_rpc::serializers::string_or_number")]
+///     total: u64,
+/// }
+///
+/// let a: Counts = serde_json::from_str(r#"{"total": "42"}"#).unwrap();
+/// let b: Counts = serde_json::from_str(r#"{"total": 42}"#).unwrap();
+/// assert_eq!(a.total, b.total);
+/// ```
+pub mod string_or_number {
+    use serde::de::Error as _;
+    use serde::{Deserializer, Serializer};
+    use std::fmt::Display;
+    use std::str::FromStr;
+
+    /// Deserialize a value from either its string or its numeric form.
+    pub fn deserialize<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: FromStr,
+        T::Err: Display,
+    {
+        super::string_or_number_repr(deserializer)?
+            .parse()
+            .map_err(D::Error::custom)
+    }
+
+    /// Serialize a value as a string.
+    pub fn serialize<S, T>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        T: Display,
+    {
+        serializer.collect_str(value)
+    }
+}
+
+/// Deserialize validator information (a [`tendermint::validator::Info`])
+/// whose voting power and proposer priority may be encoded either as JSON
+/// strings or as JSON numbers, depending on the version of the node.
+pub fn validator_info<'de, D>(deserializer: D) -> Result<validator::Info, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    ValidatorInfo::deserialize(deserializer).map(Into::into)
+}
+
+/// Deserialize a list of validator information, as per [`validator_info`].
+pub fn validator_infos<'de, D>(deserializer: D) -> Result<Vec<validator::Info>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let infos = Vec::<ValidatorInfo>::deserialize(deserializer)?;
+    Ok(infos.into_iter().map(Into::into).collect())
+}
+
+/// Validator information, as encoded by any version of the node.
+///
+/// The fields are deserialized directly (rather than via an intermediate
+/// JSON value) so that parse errors report the path to the failing field.
+#[derive(Deserialize)]
+struct ValidatorInfo {
+    address: account::Id,
+    pub_key: PublicKey,
+    #[serde(alias = "power", deserialize_with = "from_string_or_number")]
+    voting_power: vote::Power,
+    #[serde(default, deserialize_with = "from_string_or_number")]
+    proposer_priority: validator::ProposerPriority,
+}
+
+impl From<ValidatorInfo> for validator::Info {
+    fn from(raw: ValidatorInfo) -> Self {
+        let mut info = validator::Info::new(raw.pub_key, raw.voting_power);
+        info.address = raw.address;
+        info.proposer_priority = raw.proposer_priority;
+        info
+    }
+}
+
+/// Deserialize a value whose own `Deserialize` implementation expects a
+/// string from either a JSON string or a JSON number.
+fn from_string_or_number<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: DeserializeOwned,
+{
+    T::deserialize(string_or_number_repr(deserializer)?.into_deserializer())
+}
+
+/// Deserialize either a JSON string or a JSON number, as a string.
+fn string_or_number_repr<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(match StringOrNumber::deserialize(deserializer)? {
+        StringOrNumber::String(s) => s,
+        StringOrNumber::Unsigned(u) => u.to_string(),
+        StringOrNumber::Signed(i) => i.to_string(),
+    })
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StringOrNumber {
+    String(String),
+    Unsigned(u64),
+    Signed(i64),
+}
@@ -0,0 +1,148 @@
+//! Helpers shared by the crate's unit tests.
+
+#[cfg(any(
+    feature = "http-client",
+    feature = "websocket-client",
+    feature = "wasm-client"
+))]
+pub use self::client::*;
+
+/// The contents of the JSON fixture with the given name from `tests/support`.
+pub fn fixture(name: &str) -> String {
+    std::fs::read_to_string(format!("tests/support/{}.json", name)).unwrap()
+}
+
+/// A local listener which never accepts connections, and whose backlog has
+/// been filled by the returned connections, so that further attempts to
+/// connect to it hang.
+#[cfg(any(feature = "http-client", feature = "websocket-client"))]
+pub async fn hanging_listener() -> (tokio::net::TcpListener, Vec<tokio::net::TcpStream>) {
+    use std::time::Duration;
+    use tokio::net::{TcpSocket, TcpStream};
+
+    let socket = TcpSocket::new_v4().unwrap();
+    socket.bind(([127, 0, 0, 1], 0).into()).unwrap();
+    let listener = socket.listen(1).unwrap();
+    let addr = listener.local_addr().unwrap();
+    let mut backlog = Vec::new();
+    for _ in 0..64 {
+        match tokio::time::timeout(Duration::from_millis(200), TcpStream::connect(addr)).await {
+            Ok(stream) => backlog.push(stream.unwrap()),
+            Err(_) => return (listener, backlog),
+        }
+    }
+    panic!("the listener's backlog never filled up");
+}
+
+#[cfg(any(
+    feature = "http-client",
+    feature = "websocket-client",
+    feature = "wasm-client"
+))]
+mod client {
+    use crate::client::{MockClient, MockRequestMatcher, MockRequestMethodMatcher, TimedClient};
+    use crate::{Method, Request, Response, Result};
+    use std::sync::{Arc, Mutex};
+
+    /// A mock transport which responds to requests for each of the given
+    /// methods with the given response (or error), and records the requests
+    /// reaching it (see [`transport_requests`]).
+    pub fn counting_transport(
+        responses: impl IntoIterator<Item = (Method, Result<String>)>,
+    ) -> TimedClient<MockClient<MockRequestMethodMatcher>> {
+        let matcher = responses.into_iter().fold(
+            MockRequestMethodMatcher::default(),
+            |matcher, (method, response)| matcher.map(method, response),
+        );
+        let (mock, _driver) = MockClient::new(matcher);
+        TimedClient::new(mock)
+    }
+
+    /// The methods of the requests which have reached the given transport, in
+    /// the order in which they were sent.
+    pub fn transport_requests<C>(transport: &TimedClient<C>) -> Vec<Method> {
+        transport
+            .request_durations()
+            .iter()
+            .map(|d| d.method)
+            .collect()
+    }
+
+    /// Responds to requests as the given matcher does, recording the `height`
+    /// parameter (or the given height parameters, see
+    /// [`HeightRecordingMatcher::recording`]) of each request for the given
+    /// method.
+    pub struct HeightRecordingMatcher {
+        inner: MockRequestMethodMatcher,
+        method: Method,
+        params: &'static [&'static str],
+        respond: Option<Box<Responder>>,
+        heights: Arc<Mutex<Vec<serde_json::Value>>>,
+    }
+
+    type Responder = dyn Fn(&serde_json::Value) -> serde_json::Value + Send + Sync;
+
+    impl HeightRecordingMatcher {
+        pub fn new(inner: MockRequestMethodMatcher, method: Method) -> Self {
+            Self {
+                inner,
+                method,
+                params: &["height"],
+                respond: None,
+                heights: Arc::new(Mutex::new(Vec::new())),
+            }
+        }
+
+        /// Record the given parameters of each request, as an array of their
+        /// values, rather than just its `height`.
+        pub fn recording(mut self, params: &'static [&'static str]) -> Self {
+            self.params = params;
+            self
+        }
+
+        /// Respond to requests for the matcher's method with the result
+        /// returned by `respond` given their parameters (e.g. to respond with
+        /// data at the requested height), rather than via the inner matcher.
+        pub fn responding_with<F>(mut self, respond: F) -> Self
+        where
+            F: Fn(&serde_json::Value) -> serde_json::Value + Send + Sync + 'static,
+        {
+            self.respond = Some(Box::new(respond));
+            self
+        }
+
+        /// The heights recorded so far, which remain accessible once the
+        /// matcher has been moved into a [`MockClient`].
+        pub fn heights(&self) -> Arc<Mutex<Vec<serde_json::Value>>> {
+            self.heights.clone()
+        }
+    }
+
+    impl MockRequestMatcher for HeightRecordingMatcher {
+        fn response_for<R>(&self, request: R) -> Option<Result<R::Response>>
+        where
+            R: Request,
+        {
+            if request.method() != self.method {
+                return self.inner.response_for(request);
+            }
+            let params = serde_json::to_value(&request).unwrap();
+            let recorded = match self.params {
+                [name] => params[*name].clone(),
+                names => names.iter().map(|name| params[*name].clone()).collect(),
+            };
+            self.heights.lock().unwrap().push(recorded);
+            match &self.respond {
+                Some(respond) => {
+                    let response = serde_json::json!({
+                        "jsonrpc": "2.0",
+                        "id": "",
+                        "result": respond(&params),
+                    });
+                    Some(R::Response::from_string(response.to_string()))
+                }
+                None => self.inner.response_for(request),
+            }
+        }
+    }
+}
@@ -0,0 +1,67 @@
+//! Requests via the `fetch`-based client, against a mock `fetch` function.
+//!
+//! Run with `wasm-pack test --node --features wasm-client`.
+
+#![cfg(all(feature = "wasm-client", target_arch = "wasm32"))]
+
+use tendermint_rpc::error::ErrorKind;
+use tendermint_rpc::{Client, FetchClient};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+#[wasm_bindgen(inline_js = r#"
+    const requests = [];
+
+    export function mockFetch(status, body) {
+        globalThis.fetch = async (request) => {
+            requests.push(await request.text());
+            return new Response(body, { status });
+        };
+    }
+
+    export function takeRequests() {
+        return requests.splice(0, requests.length);
+    }
+"#)]
+extern "C" {
+    // Replace the global `fetch` function with one which responds to every
+    // request with the given status and body.
+    #[wasm_bindgen(js_name = mockFetch)]
+    fn mock_fetch(status: u16, body: &str);
+
+    // The bodies of the requests received since the last call.
+    #[wasm_bindgen(js_name = takeRequests)]
+    fn take_requests() -> js_sys::Array;
+}
+
+fn requested_methods() -> Vec<String> {
+    take_requests()
+        .iter()
+        .map(|body| {
+            let request: serde_json::Value =
+                serde_json::from_str(&body.as_string().unwrap()).unwrap();
+            request["method"].as_str().unwrap().to_string()
+        })
+        .collect()
+}
+
+#[wasm_bindgen_test]
+async fn requests_are_sent_via_fetch() {
+    mock_fetch(200, include_str!("support/status.json"));
+    let client = FetchClient::new("http://127.0.0.1:26657").unwrap();
+
+    let status = client.status().await.unwrap();
+    assert_eq!(410744_u64, status.sync_info.latest_block_height.value());
+    assert_eq!(vec!["status".to_string()], requested_methods());
+}
+
+#[wasm_bindgen_test]
+async fn unavailable_endpoints_are_retryable() {
+    mock_fetch(503, "busy");
+    let client = FetchClient::new("http://127.0.0.1:26657").unwrap();
+
+    let err = client.health().await.unwrap_err();
+    assert!(err.to_string().contains("503"), "unexpected error: {}", err);
+    assert_eq!(ErrorKind::Unavailable, err.kind());
+    assert!(err.is_retryable());
+}
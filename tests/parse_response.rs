@@ -132,6 +132,77 @@ fn block_results() {
     assert_eq!(validator_updates[0].power.value(), 1_233_243);
 }
 
+#[test]
+fn block_results_lazy() {
+    // Repeat the recorded transaction results to obtain a busy block.
+    let mut json: serde_json::Value =
+        serde_json::from_str(&read_json_fixture("block_results")).unwrap();
+    let txs_results = json["result"]["txs_results"].as_array().unwrap().clone();
+    let mut repeated: Vec<_> = txs_results.iter().cycle().take(5000).cloned().collect();
+    repeated[42]["gas_used"] = "not a number".into();
+    json["result"]["txs_results"] = repeated.into();
+
+    let response = endpoint::block_results::LazyResponse::from_string(json.to_string()).unwrap();
+    assert_eq!(response.height.value(), 1814);
+    assert_eq!(response.validator_updates[0].power.value(), 1_233_243);
+
+    let results: Vec<_> = response.txs_results().unwrap().collect();
+    assert_eq!(results.len(), 5000);
+    assert!(results[42].is_err());
+    let deliver_tx = results[43].as_ref().unwrap();
+    assert_eq!(deliver_tx.gas_used.value(), 105_662);
+    assert_eq!(deliver_tx.events[0].attributes[0].key.as_ref(), "action");
+
+    // Explicit nulls are treated as empty.
+    assert_eq!(response.begin_block_events().unwrap().count(), 0);
+    assert_eq!(response.end_block_events().unwrap().count(), 0);
+
+    // Eagerly decoded fields are parsed like any other response.
+    json["result"]["height"] = "not a height".into();
+    let err = endpoint::block_results::LazyResponse::from_string(json.to_string()).unwrap_err();
+    assert!(
+        err.to_string().contains("result.height"),
+        "unexpected error: {}",
+        err
+    );
+    let err = endpoint::block_results::LazyResponse::from_string(b"\xff\xfe").unwrap_err();
+    assert!(err.to_string().contains("response body is not valid UTF-8"));
+}
+
+#[test]
+fn new_block_event_block_results() {
+    let event =
+        rpc::event::Event::from_string(&read_json_fixture("event_new_block_with_results")).unwrap();
+    let response = event.block_results().unwrap();
+    assert_eq!(response.height.value(), 1608);
+    assert!(response.txs_results.is_none());
+
+    let begin_block_events = response.begin_block_events.unwrap();
+    assert_eq!(begin_block_events.len(), 1);
+    assert_eq!(begin_block_events[0].type_str, "transfer");
+    assert_eq!(begin_block_events[0].attributes.len(), 3);
+    assert_eq!(begin_block_events[0].attributes[2].key.as_ref(), "amount");
+    assert_eq!(
+        begin_block_events[0].attributes[2].value.as_ref(),
+        "2517stake"
+    );
+
+    let end_block_events = response.end_block_events.unwrap();
+    assert_eq!(end_block_events[0].type_str, "complete_unbonding");
+    assert_eq!(response.validator_updates.len(), 1);
+    assert_eq!(response.validator_updates[0].power.value(), 1_233_243);
+    assert!(response.consensus_param_updates.is_none());
+
+    // Empty results are still reported as such.
+    let event = rpc::event::Event::from_string(&read_json_fixture("event_new_block_1")).unwrap();
+    assert!(event
+        .block_results()
+        .unwrap()
+        .begin_block_events
+        .unwrap()
+        .is_empty());
+}
+
 #[test]
 fn blockchain() {
     let response =
@@ -206,6 +277,43 @@ fn broadcast_tx_commit() {
     assert_eq!(5, response.deliver_tx.events.len());
 }
 
+#[test]
+fn broadcast_tx_commit_phases() {
+    use endpoint::broadcast::tx_commit::{Response, TxPhase};
+
+    let fixture: serde_json::Value =
+        serde_json::from_str(&read_json_fixture("broadcast_tx_commit")).unwrap();
+
+    let response = Response::from_string(fixture.to_string()).unwrap();
+    assert!(response.is_check_tx_ok());
+    assert!(response.is_deliver_tx_ok());
+    assert!(response.into_result().is_ok());
+
+    let mut check_tx_failed = fixture.clone();
+    check_tx_failed["result"]["check_tx"]["code"] = serde_json::json!(19);
+    check_tx_failed["result"]["check_tx"]["log"] = serde_json::json!("tx already exists in cache");
+    check_tx_failed["result"]["deliver_tx"] = serde_json::json!({});
+    check_tx_failed["result"]["height"] = serde_json::json!("0");
+    let response = Response::from_string(check_tx_failed.to_string()).unwrap();
+    assert!(!response.is_check_tx_ok());
+    assert!(!response.is_deliver_tx_ok());
+    let err = response.into_result().unwrap_err();
+    assert_eq!(TxPhase::CheckTx, err.phase);
+    assert_eq!(19, err.result.code.value());
+    assert!(err.to_string().contains("tx already exists in cache"));
+
+    let mut deliver_tx_failed = fixture;
+    deliver_tx_failed["result"]["deliver_tx"]["code"] = serde_json::json!(5);
+    deliver_tx_failed["result"]["deliver_tx"]["log"] = serde_json::json!("insufficient funds");
+    let response = Response::from_string(deliver_tx_failed.to_string()).unwrap();
+    assert!(response.is_check_tx_ok());
+    assert!(!response.is_deliver_tx_ok());
+    let err = response.into_result().unwrap_err();
+    assert_eq!(TxPhase::DeliverTx, err.phase);
+    assert_eq!(5, err.result.code.value());
+    assert!(err.to_string().starts_with("DeliverTx failed"));
+}
+
 #[test]
 fn broadcast_tx_commit_null_data() {
     let response = endpoint::broadcast::tx_commit::Response::from_string(&read_json_fixture(
@@ -254,6 +362,82 @@ fn genesis() {
 
     assert_eq!(chain_id.as_str(), EXAMPLE_CHAIN);
     assert_eq!(consensus_params.block.max_bytes, 200_000);
+    // Nodes prior to Tendermint v0.34 do not report the initial height.
+    assert_eq!(None, response.initial_height);
+}
+
+#[test]
+fn genesis_with_initial_height() {
+    let response =
+        endpoint::genesis::Response::from_string(&read_json_fixture("genesis_initial_height"))
+            .unwrap();
+    assert_eq!(Some(5_200_791), response.initial_height);
+    assert_eq!(response.genesis.chain_id.as_str(), "cosmoshub-4");
+
+    // The initial height is serialized back into the genesis data.
+    let serialized = serde_json::to_value(&response).unwrap();
+    assert_eq!("5200791", serialized["genesis"]["initial_height"]);
+    assert!(serialized.get("initial_height").is_none());
+}
+
+#[test]
+fn genesis_validators() {
+    let genesis = endpoint::genesis::Response::from_string(&read_json_fixture("genesis"))
+        .unwrap()
+        .genesis;
+    let response =
+        endpoint::genesis::ValidatorsResponse::from_string(&read_json_fixture("genesis")).unwrap();
+    let validators = response.genesis.validators;
+    assert_eq!(genesis.validators, validators);
+    assert_eq!(1, validators.len());
+    assert_eq!(
+        "B00A6323737F321EB0B8D59C6FD497A14B60938A",
+        validators[0].address.to_string()
+    );
+    assert_eq!(9_328_525, validators[0].voting_power.value());
+
+    // The rest of the genesis data is not decoded.
+    let mut fixture: serde_json::Value =
+        serde_json::from_str(&read_json_fixture("genesis")).unwrap();
+    fixture["result"]["genesis"]["consensus_params"] = "invalid".into();
+    fixture["result"]["genesis"]["app_state"] = serde_json::json!({ "accounts": [1, 2, 3] });
+    let response = endpoint::genesis::ValidatorsResponse::from_string(fixture.to_string()).unwrap();
+    assert_eq!(validators, response.genesis.validators);
+}
+
+#[test]
+fn genesis_validate() {
+    let response = endpoint::genesis::Response::from_string(&read_json_fixture("genesis")).unwrap();
+    assert_eq!(Ok(()), response.validate());
+
+    let mut fixture: serde_json::Value =
+        serde_json::from_str(&read_json_fixture("genesis")).unwrap();
+    fixture["result"]["genesis"]["validators"] = serde_json::json!([]);
+    fixture["result"]["genesis"]["consensus_params"]["block"]["max_gas"] = "-2".into();
+    let response = endpoint::genesis::Response::from_string(fixture.to_string()).unwrap();
+    assert_eq!(
+        Err(vec![
+            endpoint::genesis::GenesisValidationError::NoValidators,
+            endpoint::genesis::GenesisValidationError::InvalidBlockMaxGas(-2),
+        ]),
+        response.validate()
+    );
+
+    // The initial height is checked when reported.
+    let response =
+        endpoint::genesis::Response::from_string(&read_json_fixture("genesis_initial_height"))
+            .unwrap();
+    assert_eq!(Ok(()), response.validate());
+    let mut fixture: serde_json::Value =
+        serde_json::from_str(&read_json_fixture("genesis_initial_height")).unwrap();
+    fixture["result"]["genesis"]["initial_height"] = "0".into();
+    let response = endpoint::genesis::Response::from_string(fixture.to_string()).unwrap();
+    assert_eq!(
+        Err(vec![
+            endpoint::genesis::GenesisValidationError::InvalidInitialHeight(0)
+        ]),
+        response.validate()
+    );
 }
 
 #[test]
@@ -261,6 +445,65 @@ fn health() {
     endpoint::health::Response::from_string(&read_json_fixture("health")).unwrap();
 }
 
+#[test]
+fn response_with_both_result_and_error() {
+    let body = r#"{
+        "jsonrpc": "2.0",
+        "id": "",
+        "result": {},
+        "error": {"code": -32603, "message": "Internal error", "data": "boom"}
+    }"#;
+
+    // Leniently, the error takes precedence.
+    let err = endpoint::health::Response::from_string(body).unwrap_err();
+    assert_eq!(err.code(), rpc::error::Code::InternalError);
+
+    let err = endpoint::health::Response::from_string_strict(body).unwrap_err();
+    assert_eq!(err.code(), rpc::error::Code::ServerError);
+    assert!(
+        err.to_string().contains("both 'result' and 'error'"),
+        "unexpected error: {}",
+        err
+    );
+
+    // Well-formed responses are unaffected by strictness.
+    endpoint::health::Response::from_string_strict(&read_json_fixture("health")).unwrap();
+}
+
+#[test]
+fn invalid_utf8_response() {
+    // Structurally valid JSON, but with a non-UTF-8 byte in a string.
+    let body = b"{\"jsonrpc\": \"2.0\", \"id\": \"\xff\", \"result\": {}}";
+
+    let err = endpoint::health::Response::from_string(&body[..]).unwrap_err();
+    assert!(
+        err.to_string().contains("response body is not valid UTF-8"),
+        "unexpected error: {}",
+        err
+    );
+
+    // The same check applies when reading the response.
+    let err = endpoint::health::Response::from_reader(&body[..]).unwrap_err();
+    assert!(
+        err.to_string().contains("response body is not valid UTF-8"),
+        "unexpected error: {}",
+        err
+    );
+}
+
+#[test]
+fn trailing_data_is_rejected() {
+    let body = format!("{}garbage", read_json_fixture("health").trim_end());
+
+    endpoint::health::Response::from_string(&body).unwrap_err();
+    endpoint::health::Response::from_reader(body.as_bytes()).unwrap_err();
+
+    // Trailing whitespace is fine, though.
+    let body = format!("{}\n\n", read_json_fixture("health").trim_end());
+    endpoint::health::Response::from_string(&body).unwrap();
+    endpoint::health::Response::from_reader(body.as_bytes()).unwrap();
+}
+
 #[test]
 fn net_info() {
     let response =
@@ -270,6 +513,85 @@ fn net_info() {
     assert_eq!(response.peers[0].node_info.network.as_str(), EXAMPLE_CHAIN);
 }
 
+#[test]
+fn net_info_connection_stats() {
+    let response =
+        endpoint::net_info::Response::from_string(&read_json_fixture("net_info_connection_stats"))
+            .unwrap();
+
+    let status = &response.peers[0].connection_status;
+    assert_eq!(status.duration.as_secs(), 86_400);
+    assert_eq!(status.send_monitor.bytes, 734_921_833);
+    assert_eq!(status.send_monitor.avg_rate, 8_506);
+    assert_eq!(status.send_monitor.peak_rate, 512_000);
+    assert_eq!(status.recv_monitor.bytes, 1_523_001_992);
+    assert_eq!(status.recv_monitor.cur_rate, 17_223);
+    assert_eq!(status.recv_monitor.idle.as_millis(), 40);
+    assert_eq!(status.channels.len(), 8);
+
+    // The second peer's node doesn't report connection statistics.
+    let status = &response.peers[1].connection_status;
+    assert_eq!(status.duration.as_secs(), 0);
+    assert_eq!(status.send_monitor.bytes, 0);
+    assert!(status.channels.is_empty());
+}
+
+#[test]
+fn net_info_peer_health() {
+    let thresholds = endpoint::net_info::HealthThresholds::default();
+    let response =
+        endpoint::net_info::Response::from_string(&read_json_fixture("net_info_stalled_peer"))
+            .unwrap();
+    let health = response.peer_health(&thresholds);
+
+    // A day-old outbound connection, active in both directions.
+    assert_eq!(
+        health[0].node_id.to_string(),
+        "9d55f7d40ba4925cca86e3880bc287f30451230e"
+    );
+    assert_eq!(health[0].score, 100);
+    assert!(!health[0].stalled);
+
+    // A two hour-old inbound connection, idle for 5 minutes.
+    assert_eq!(
+        health[1].node_id.to_string(),
+        "a5ceaad3a1907665b2514db4e741939f0a5ab7dd"
+    );
+    assert_eq!(health[1].score, 50);
+    assert!(health[1].stalled);
+
+    // Peers are not considered stalled when the thresholds are relaxed.
+    let relaxed = endpoint::net_info::HealthThresholds {
+        max_idle: std::time::Duration::from_secs(600),
+        min_rate: 0,
+        ..thresholds
+    };
+    assert!(!response.peers[1].health(&relaxed).stalled);
+    assert_eq!(response.peers[1].health(&relaxed).score, 90);
+
+    // Peers without connection statistics are scored on their direction.
+    let response =
+        endpoint::net_info::Response::from_string(&read_json_fixture("net_info_connection_stats"))
+            .unwrap();
+    let health = response.peers[1].health(&thresholds);
+    assert_eq!(health.score, 20);
+    assert!(!health.stalled);
+}
+
+#[test]
+fn validators_with_broken_pub_key() {
+    let mut fixture: serde_json::Value =
+        serde_json::from_str(&read_json_fixture("validators")).unwrap();
+    fixture["result"]["validators"][3]["pub_key"]["value"] = "not a key!".into();
+
+    let err = endpoint::validators::Response::from_string(fixture.to_string()).unwrap_err();
+    assert!(
+        err.to_string().contains("result.validators[3].pub_key"),
+        "unexpected error: {}",
+        err
+    );
+}
+
 #[test]
 fn status() {
     let response = endpoint::status::Response::from_string(&read_json_fixture("status")).unwrap();
@@ -277,6 +599,106 @@ fn status() {
     assert_eq!(response.node_info.network.as_str(), EXAMPLE_CHAIN);
     assert_eq!(response.sync_info.latest_block_height.value(), 410_744);
     assert_eq!(response.validator_info.voting_power.value(), 0);
+
+    // This node doesn't report its earliest block.
+    assert!(response.sync_info.earliest_block_height.is_none());
+    let (earliest, latest) = response.sync_info.available_height_range();
+    assert_eq!(earliest.value(), 1);
+    assert_eq!(latest.value(), 410_744);
+}
+
+#[test]
+fn validator_voting_power_as_string_or_number() {
+    let mut json: serde_json::Value = serde_json::from_str(&read_json_fixture("status")).unwrap();
+    for voting_power in &[serde_json::json!("1234"), serde_json::json!(1234)] {
+        json["result"]["validator_info"]["voting_power"] = voting_power.clone();
+        json["result"]["validator_info"]["proposer_priority"] = serde_json::json!(-5);
+        let response = endpoint::status::Response::from_string(json.to_string()).unwrap();
+        assert_eq!(response.validator_info.voting_power.value(), 1234);
+        assert_eq!(response.validator_info.proposer_priority.value(), -5);
+
+        let response = endpoint::status::Response::from_string_lenient(json.to_string()).unwrap();
+        assert!(response.errors.is_empty());
+        assert_eq!(response.validator_info.unwrap().voting_power.value(), 1234);
+    }
+
+    let mut json: serde_json::Value =
+        serde_json::from_str(&read_json_fixture("validators")).unwrap();
+    json["result"]["validators"][0]["voting_power"] = serde_json::json!(5000);
+    json["result"]["validators"][0]["proposer_priority"] = serde_json::json!(205000);
+    json["result"]["total"] = serde_json::json!(65);
+    let response = endpoint::validators::Response::from_string(json.to_string()).unwrap();
+    assert_eq!(response.validators[0].voting_power.value(), 5000);
+    assert_eq!(response.validators[0].proposer_priority.value(), 205_000);
+    assert_eq!(response.total, 65);
+    // The canonical string forms are serialized.
+    let serialized = serde_json::to_value(&response).unwrap();
+    assert_eq!(serialized["total"], "65");
+    assert_eq!(serialized["validators"][0]["voting_power"], "5000");
+
+    // Malformed numbers are reported at their own path.
+    json["result"]["validators"][2]["voting_power"] = serde_json::json!(true);
+    let err = endpoint::validators::Response::from_string(json.to_string()).unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("result.validators[2].voting_power"),
+        "unexpected error: {}",
+        err
+    );
+}
+
+#[test]
+fn status_pruned() {
+    let response =
+        endpoint::status::Response::from_string(&read_json_fixture("status_pruned")).unwrap();
+
+    let sync_info = &response.sync_info;
+    assert_eq!(sync_info.earliest_block_height.unwrap().value(), 400_001);
+    assert!(sync_info.earliest_block_time.unwrap() < sync_info.latest_block_time);
+    let (earliest, latest) = sync_info.available_height_range();
+    assert_eq!(earliest.value(), 400_001);
+    assert_eq!(latest.value(), 410_744);
+}
+
+#[test]
+fn status_lenient() {
+    let mut json: serde_json::Value = serde_json::from_str(&read_json_fixture("status")).unwrap();
+    json["result"]["sync_info"]["latest_block_height"] = serde_json::json!({ "unexpected": 1 });
+
+    let response = endpoint::status::Response::from_string_lenient(json.to_string()).unwrap();
+
+    assert!(response.sync_info.is_none());
+    assert_eq!(response.errors.len(), 1);
+    assert_eq!(response.errors[0].field, "sync_info");
+    assert!(response.errors[0]
+        .error
+        .to_string()
+        .contains("latest_block_height"));
+    assert_eq!(response.node_info.unwrap().network.as_str(), EXAMPLE_CHAIN);
+    assert_eq!(response.validator_info.unwrap().voting_power.value(), 0);
+}
+
+#[test]
+fn status_node_info_other() {
+    let response = endpoint::status::Response::from_string(&read_json_fixture("status")).unwrap();
+
+    assert!(response.tx_index_enabled());
+    assert_eq!(
+        Some(tendermint::net::Address::Tcp {
+            peer_id: None,
+            host: "0.0.0.0".to_owned(),
+            port: 26657,
+        }),
+        response.rpc_address()
+    );
+    assert_eq!(
+        Some(tendermint::net::Address::Tcp {
+            peer_id: None,
+            host: "0.0.0.0".to_owned(),
+            port: 26656,
+        }),
+        response.p2p_listen_address()
+    );
 }
 
 #[test]
@@ -306,6 +728,19 @@ fn jsonrpc_error() {
     }
 }
 
+#[test]
+fn jsonrpc_method_not_found_error() {
+    let err = endpoint::status::Response::from_string(&read_json_fixture("error_method_not_found"))
+        .unwrap_err();
+    assert_eq!(err.code(), rpc::error::Code::MethodNotFound);
+    assert!(err.is_method_not_found());
+    assert_eq!(rpc::error::ErrorKind::InvalidRequest, err.kind());
+
+    // Other errors are told apart from it.
+    let err = endpoint::blockchain::Response::from_string(&read_json_fixture("error")).unwrap_err();
+    assert!(!err.is_method_not_found());
+}
+
 #[test]
 fn tx_search_no_prove() {
     let response =
@@ -355,6 +790,99 @@ fn tx_search_with_prove() {
     assert_eq!(events[0].attributes[0].value.as_ref(), "Cosmoshi Netowoko");
 }
 
+#[test]
+fn tx_search_proofs() {
+    let response =
+        endpoint::tx_search::Response::from_string(&read_json_fixture("tx_search_with_prove"))
+            .unwrap();
+
+    for tx in &response.txs {
+        let proof = tx.inclusion_proof().unwrap().unwrap();
+        assert_eq!(tx.tx.as_bytes(), &proof.tx[..]);
+        proof.verify(&proof.root_hash).unwrap();
+    }
+
+    // The first three transactions are in the same block (of three), and the
+    // fourth is in a block of its own.
+    let proof = response.txs[0].inclusion_proof().unwrap().unwrap();
+    assert_eq!((0, 3), (proof.index, proof.total));
+    let other_root = response.txs[3]
+        .inclusion_proof()
+        .unwrap()
+        .unwrap()
+        .root_hash;
+    let err = proof.verify(&other_root).unwrap_err();
+    assert!(err.to_string().contains("rather than"));
+
+    // Tampered proofs are rejected.
+    let mut tampered = proof.clone();
+    tampered.tx = b"async-key=other".to_vec();
+    assert!(tampered.verify(&proof.root_hash).is_err());
+    let mut tampered = proof.clone();
+    tampered.index = 1;
+    assert!(tampered.verify(&proof.root_hash).is_err());
+    let mut tampered = proof.clone();
+    tampered.aunts.reverse();
+    assert!(tampered.verify(&proof.root_hash).is_err());
+    let mut tampered = proof.clone();
+    tampered.aunts.pop();
+    assert!(tampered.verify(&proof.root_hash).is_err());
+    // A genuine transaction's proof fails for a different position.
+    let mut tampered = response.txs[1].inclusion_proof().unwrap().unwrap();
+    tampered.index = 0;
+    assert!(tampered.verify(&proof.root_hash).is_err());
+
+    // Malformed proofs fail to decode, and no proof is given unless requested.
+    let malformed = read_json_fixture("tx_search_with_prove").replace(
+        "MIH5kVBA0TizrX+JVzLSdnwp6Ful2EOI0E4XpdgmK3o=",
+        "MIH5kVBA0Tiz",
+    );
+    let response = endpoint::tx_search::Response::from_string(&malformed).unwrap();
+    assert!(response.txs[0].inclusion_proof().is_err());
+    let response =
+        endpoint::tx_search::Response::from_string(&read_json_fixture("tx_search_no_prove"))
+            .unwrap();
+    assert_eq!(None, response.txs[0].inclusion_proof().unwrap());
+}
+
+#[test]
+fn tx_search_sorted_by_position() {
+    let mut response =
+        endpoint::tx_search::Response::from_string(&read_json_fixture("tx_search_no_prove"))
+            .unwrap();
+    let positions = |response: &endpoint::tx_search::Response| {
+        response
+            .txs
+            .iter()
+            .map(|tx| (tx.height.value(), tx.index))
+            .collect::<Vec<_>>()
+    };
+    let ascending = vec![
+        (11, 0),
+        (11, 1),
+        (11, 2),
+        (18, 0),
+        (19, 0),
+        (19, 1),
+        (20, 0),
+        (21, 0),
+    ];
+    assert_eq!(ascending, positions(&response));
+
+    // Transactions at the same height are ordered by index, however the
+    // node returned them.
+    response.txs.swap(0, 2);
+    response.txs.swap(4, 5);
+    response.txs.rotate_left(3);
+    response.sort_by_position(&rpc::Order::Ascending);
+    assert_eq!(ascending, positions(&response));
+
+    response.txs.swap(1, 2);
+    response.sort_by_position(&rpc::Order::Descending);
+    let descending: Vec<_> = ascending.into_iter().rev().collect();
+    assert_eq!(descending, positions(&response));
+}
+
 #[test]
 fn consensus_state() {
     let response =
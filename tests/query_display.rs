@@ -0,0 +1,117 @@
+//! Golden-file tests for the rendering of queries.
+//!
+//! The expected renderings in `tests/support/query_display.json` are in the
+//! form accepted by Tendermint's query parser, and must be matched exactly:
+//! a query the node cannot interpret the way we intended silently yields no
+//! results, rather than an error.
+
+use chrono::{Date, DateTime, NaiveDate, Utc};
+use std::collections::BTreeMap;
+use std::fs;
+use tendermint_rpc::query::{EventType, Query};
+
+fn read_golden_file() -> BTreeMap<String, String> {
+    let content = fs::read_to_string("./tests/support/query_display.json").unwrap();
+    serde_json::from_str(&content).unwrap()
+}
+
+fn time(s: &str) -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+}
+
+fn corpus() -> Vec<(&'static str, Query)> {
+    vec![
+        ("new_block_events", Query::from(EventType::NewBlock)),
+        (
+            "tx_at_height",
+            Query::from(EventType::Tx).and_eq("tx.height", 5_u64),
+        ),
+        (
+            "tx_height_range",
+            Query::for_type_height_range(EventType::Tx, 100, 200),
+        ),
+        (
+            "transfer_sender",
+            Query::from(EventType::Tx).and_eq(
+                "transfer.sender",
+                "cosmos1qypqxpq9qcrsszg2pvxq6rs0zqg3yyc5lzv7xu",
+            ),
+        ),
+        (
+            "message_action_exists",
+            Query::from(EventType::Tx).and_exists("message.action"),
+        ),
+        ("empty_string", Query::eq("app.key", "")),
+        ("unicode_string", Query::eq("message.memo", "héllo ✓")),
+        ("escaped_quote", Query::eq("message.memo", "it's")),
+        ("escaped_backslash", Query::eq("message.path", r"C:\tmp")),
+        (
+            "escaped_backslash_before_quote",
+            Query::contains("message.memo", r"\'"),
+        ),
+        (
+            "contains_escaped_quote",
+            Query::contains("message.memo", "O'Brien"),
+        ),
+        ("max_unsigned", Query::eq("tx.height", u64::MAX)),
+        ("negative_signed", Query::lt("balance.change", -25_i64)),
+        ("float", Query::gte("price.value", 0.25_f64)),
+        ("small_float", Query::lt("fee.rate", 0.0001_f64)),
+        ("whole_float", Query::eq("ratio.value", 2.0_f64)),
+        (
+            "date",
+            Query::gt(
+                "account.created",
+                Date::from_utc(NaiveDate::from_ymd(2020, 1, 31), Utc),
+            ),
+        ),
+        (
+            "time_utc",
+            Query::lte("block.time", time("2021-03-04T05:06:07Z")),
+        ),
+        (
+            "time_with_offset",
+            Query::lte("block.time", time("2021-03-04T05:06:07-04:00")),
+        ),
+        (
+            "time_with_nanoseconds",
+            Query::gt("block.time", time("2021-03-04T05:06:07.123456789Z")),
+        ),
+    ]
+}
+
+#[test]
+fn query_display_matches_golden_file() {
+    let golden = read_golden_file();
+    let corpus = corpus();
+
+    // Every golden rendering must be covered by the corpus, and vice versa.
+    let mut names: Vec<_> = corpus.iter().map(|(name, _)| name.to_string()).collect();
+    names.sort();
+    assert_eq!(golden.keys().cloned().collect::<Vec<_>>(), names);
+
+    let mismatches: Vec<_> = corpus
+        .iter()
+        .filter(|(name, query)| query.to_string() != golden[*name])
+        .map(|(name, query)| {
+            format!(
+                "{}:\n  expected: {}\n    actual: {}",
+                name, golden[*name], query
+            )
+        })
+        .collect();
+    assert!(
+        mismatches.is_empty(),
+        "rendered queries differ from the golden file:\n{}",
+        mismatches.join("\n")
+    );
+}
+
+#[test]
+fn golden_file_queries_are_sent_verbatim() {
+    // The `query` parameter sent to the node is exactly the rendering.
+    let golden = read_golden_file();
+    for (name, query) in corpus() {
+        assert_eq!(golden[name], query.to_param(), "{}", name);
+    }
+}
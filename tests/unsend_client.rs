@@ -0,0 +1,58 @@
+//! Using a client which is not thread-safe on a single-threaded executor,
+//! which is only possible with the `unsend` feature enabled.
+
+#![cfg(all(
+    feature = "unsend",
+    any(feature = "http-client", feature = "websocket-client"),
+    not(target_arch = "wasm32")
+))]
+
+use async_trait::async_trait;
+use std::cell::RefCell;
+use std::rc::Rc;
+use tendermint_rpc::{Client, Method, Request, Response, Result, SimpleRequest};
+use tokio::task::{self, LocalSet};
+
+/// Responds to requests with the corresponding fixtures, recording each
+/// request's method via an `Rc`, and so is neither `Send` nor `Sync`.
+struct LocalClient {
+    requests: Rc<RefCell<Vec<Method>>>,
+}
+
+#[async_trait(?Send)]
+impl Client for LocalClient {
+    async fn perform<R>(&self, request: R) -> Result<R::Response>
+    where
+        R: SimpleRequest,
+    {
+        let requests = self.requests.clone();
+        // The `Rc` is held across an await point.
+        task::yield_now().await;
+        requests.borrow_mut().push(request.method());
+        let fixture =
+            std::fs::read_to_string(format!("tests/support/{}.json", request.method())).unwrap();
+        R::Response::from_string(fixture)
+    }
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn client_futures_need_not_be_send() {
+    let requests = Rc::new(RefCell::new(Vec::new()));
+    let client = LocalClient {
+        requests: requests.clone(),
+    };
+
+    LocalSet::new()
+        .run_until(async move {
+            task::spawn_local(async move {
+                client.health().await.unwrap();
+                let status = client.status().await.unwrap();
+                assert_eq!(410744, status.sync_info.latest_block_height.value());
+            })
+            .await
+            .unwrap();
+        })
+        .await;
+
+    assert_eq!(vec![Method::Health, Method::Status], *requests.borrow());
+}